@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -14,6 +15,9 @@ pub struct VaultConfig {
     pub note_extensions: Vec<String>,
     /// File extensions (without dot) that are considered attachments.
     pub attachment_extensions: Vec<String>,
+    /// Extensions (without dot) of plain-text files that content search scans alongside
+    /// notes. They are never parsed for links, tags or fields.
+    pub text_attachment_extensions: Vec<String>,
     /// Debounce window for filesystem events.
     pub watch_debounce: Duration,
     /// Minimum similarity score for note similarity health checks.
@@ -30,8 +34,79 @@ pub struct VaultConfig {
     pub embedding_model_url: String,
     /// URL to download the tokenizer JSON from.
     pub embedding_tokenizer_url: String,
+    /// Prepend frontmatter values to a note's cleaned body before embedding it.
+    pub embedding_include_frontmatter: bool,
+    /// Frontmatter keys embedded when `embedding_include_frontmatter` is set; empty means all.
+    pub embedding_frontmatter_keys: Vec<String>,
     /// Vault schema TOML path (relative to vault root).
     pub schema_path: PathBuf,
+    /// Characters permitted inside inline `#tags`.
+    pub tag_charset: TagCharset,
+    /// How inline `key:: value` fields combine with a frontmatter key of the same name.
+    pub field_merge_policy: FieldMergePolicy,
+    /// Maximum directory depth to index, where files in the vault root are at depth 1.
+    /// Paths nested deeper are skipped; `None` means unlimited.
+    pub max_walk_depth: Option<usize>,
+    /// Also treat blocks indented by four spaces or a tab (after a blank line, outside
+    /// lists) as code. Off by default since nested list items are often indented alike.
+    pub indented_code_blocks: bool,
+    /// Detect each note's body language while indexing ([`NoteMeta::language`]); has no
+    /// effect unless built with the `language` feature.
+    ///
+    /// [`NoteMeta::language`]: crate::NoteMeta::language
+    pub detect_language: bool,
+    /// Extra checkbox marks mapped to task statuses, e.g. `'☑' => Done` for `- [☑]`.
+    /// They take precedence over the built-in ` `, `x`, `X`, `>`, `-` and `?` marks.
+    pub task_marks: BTreeMap<char, crate::TaskStatus>,
+}
+
+/// Precedence between frontmatter and inline (`key:: value`) fields sharing a key.
+///
+/// Repeated inline fields for the same key always append to each other; the policy
+/// only decides what happens when the key is also set in frontmatter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FieldMergePolicy {
+    /// Keep both: the frontmatter value and inline values are merged into a list.
+    #[default]
+    Append,
+    /// Inline values replace the frontmatter value.
+    InlineOverrides,
+    /// The frontmatter value wins; inline values for that key are ignored.
+    FrontmatterWins,
+}
+
+/// Character class accepted inside inline `#tags`.
+///
+/// The default mirrors Obsidian: any Unicode letter or digit plus `-`, `_` and `/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagCharset {
+    /// Accept any Unicode alphanumeric character (otherwise ASCII only).
+    pub unicode: bool,
+    /// Additional non-alphanumeric characters allowed in tags.
+    pub extra: Vec<char>,
+}
+
+impl TagCharset {
+    /// Returns true if `c` may appear inside a tag.
+    pub fn allows(&self, c: char) -> bool {
+        if self.unicode {
+            if c.is_alphanumeric() {
+                return true;
+            }
+        } else if c.is_ascii_alphanumeric() {
+            return true;
+        }
+        self.extra.contains(&c)
+    }
+}
+
+impl Default for TagCharset {
+    fn default() -> Self {
+        Self {
+            unicode: true,
+            extra: vec!['-', '_', '/'],
+        }
+    }
 }
 
 impl Default for VaultConfig {
@@ -54,6 +129,7 @@ impl Default for VaultConfig {
                 "svg".into(),
                 "pdf".into(),
             ],
+            text_attachment_extensions: Vec::new(),
             watch_debounce: Duration::from_millis(400),
             similarity_min_score: 0.75,
             similarity_top_k: 10,
@@ -62,7 +138,15 @@ impl Default for VaultConfig {
             embedding_cache_dir: PathBuf::from(".obsidian/oxidian/embeddings"),
             embedding_model_url: DEFAULT_EMBEDDING_MODEL_URL.into(),
             embedding_tokenizer_url: DEFAULT_EMBEDDING_TOKENIZER_URL.into(),
+            embedding_include_frontmatter: false,
+            embedding_frontmatter_keys: Vec::new(),
             schema_path: PathBuf::from(".obsidian/oxidian/schema.toml"),
+            tag_charset: TagCharset::default(),
+            field_merge_policy: FieldMergePolicy::default(),
+            max_walk_depth: None,
+            indented_code_blocks: false,
+            detect_language: false,
+            task_marks: BTreeMap::new(),
         }
     }
 }
@@ -0,0 +1,193 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Error;
+
+/// A calendar date (proleptic Gregorian, no time zone).
+///
+/// Field order makes the derived `Ord` chronological.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Self { year, month, day })
+    }
+
+    /// Parses an ISO-8601 date (`YYYY-MM-DD`), optionally followed by a time
+    /// (`THH:MM[:SS[.fff]]` with an optional `Z` or `±HH:MM` offset).
+    ///
+    /// Only the date part is kept.
+    pub fn parse_iso8601(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let (date, time) = match s.find(['T', 't', ' ']) {
+            Some(ix) => (&s[..ix], Some(&s[ix + 1..])),
+            None => (s, None),
+        };
+
+        let mut parts = date.splitn(3, '-');
+        let year = parse_digits(parts.next()?, 4)? as i32;
+        let month = parse_digits(parts.next()?, 2)?;
+        let day = parse_digits(parts.next()?, 2)?;
+        let out = Self::new(year, month, day)?;
+
+        if let Some(time) = time
+            && !is_valid_time(time)
+        {
+            return None;
+        }
+        Some(out)
+    }
+
+    /// Today's date in UTC.
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        Self::from_days_since_epoch(secs.div_euclid(86_400))
+    }
+
+    /// Returns the date `days` days later (or earlier, for negative values).
+    pub fn add_days(self, days: i64) -> Self {
+        Self::from_days_since_epoch(self.days_since_epoch() + days)
+    }
+
+    /// Days since 1970-01-01.
+    pub fn days_since_epoch(self) -> i64 {
+        // Howard Hinnant's days_from_civil.
+        let y = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = i64::from(self.month);
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        // Howard Hinnant's civil_from_days.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl std::str::FromStr for Date {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_iso8601(s).ok_or_else(|| Error::InvalidDate(s.to_string()))
+    }
+}
+
+impl serde::Serialize for Date {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+fn parse_digits(s: &str, len: usize) -> Option<u32> {
+    if s.len() != len || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn is_valid_time(s: &str) -> bool {
+    let (clock, offset) = match s.find(['Z', 'z', '+', '-']) {
+        Some(ix) => (&s[..ix], Some(&s[ix..])),
+        None => (s, None),
+    };
+
+    let (hms, frac) = match clock.split_once('.') {
+        Some((hms, frac)) => (hms, Some(frac)),
+        None => (clock, None),
+    };
+    let parts: Vec<&str> = hms.split(':').collect();
+    let limits = [23, 59, 60];
+    if !(2..=3).contains(&parts.len()) {
+        return false;
+    }
+    for (part, max) in parts.iter().zip(limits) {
+        if parse_digits(part, 2).is_none_or(|v| v > max) {
+            return false;
+        }
+    }
+    if let Some(frac) = frac
+        && (frac.is_empty() || !frac.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return false;
+    }
+
+    match offset {
+        None | Some("Z") | Some("z") => true,
+        Some(off) => {
+            let off = &off[1..];
+            let (h, m) = off.split_once(':').unwrap_or((off, "00"));
+            parse_digits(h, 2).is_some_and(|v| v <= 23)
+                && parse_digits(m, 2).is_some_and(|v| v <= 59)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dates_and_datetimes() {
+        assert_eq!(Date::parse_iso8601("2024-02-29"), Date::new(2024, 2, 29));
+        assert_eq!(
+            Date::parse_iso8601("2024-01-15T09:30:00Z"),
+            Date::new(2024, 1, 15)
+        );
+        assert_eq!(
+            Date::parse_iso8601("2024-01-15T09:30+02:00"),
+            Date::new(2024, 1, 15)
+        );
+        assert!(Date::parse_iso8601("2023-02-29").is_none());
+        assert!(Date::parse_iso8601("2024-1-5").is_none());
+        assert!(Date::parse_iso8601("2024-01-15T25:00").is_none());
+        assert!(Date::parse_iso8601("yesterday").is_none());
+    }
+
+    #[test]
+    fn epoch_day_roundtrip() {
+        let d = Date::new(2024, 3, 1).unwrap();
+        assert_eq!(d.add_days(-1), Date::new(2024, 2, 29).unwrap());
+        assert_eq!(Date::new(1970, 1, 1).unwrap().days_since_epoch(), 0);
+        assert_eq!(Date::from_days_since_epoch(d.days_since_epoch()), d);
+        assert_eq!(d.to_string(), "2024-03-01");
+    }
+}
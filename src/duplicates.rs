@@ -0,0 +1,276 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::{Backlink, FileKind, Result, Tag, VaultIndex, VaultPath};
+
+/// Notes that share a basename (case-insensitive stem), making bare `[[name]]` links ambiguous.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct DuplicateGroup {
+    pub name: String,
+    pub notes: Vec<VaultPath>,
+}
+
+/// Advisory rename suggestion for one member of a duplicate group.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct RenameSuggestion {
+    pub path: VaultPath,
+    pub suggested_path: VaultPath,
+    /// Inbound links that would need updating after the rename.
+    pub inbound: Vec<Backlink>,
+}
+
+/// Rename suggestions for every note in a duplicate group.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MergePlan {
+    pub name: String,
+    pub renames: Vec<RenameSuggestion>,
+}
+
+/// Shortest separator-free tag form compared by edit distance; shorter forms must match exactly.
+const MIN_FUZZY_TAG_LEN: usize = 5;
+
+/// A tag together with the number of files carrying it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TagUsage {
+    pub tag: Tag,
+    pub files: usize,
+}
+
+/// Tags that look like spellings of one another, with the suggested tag to merge into.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TagCluster {
+    /// The most used member; ties prefer the shorter, then alphabetically first tag.
+    pub canonical: Tag,
+    /// Every member including `canonical`, most used first.
+    pub tags: Vec<TagUsage>,
+}
+
+impl VaultIndex {
+    /// Clusters tags whose separator-free forms are equal or one edit apart.
+    ///
+    /// `#m-l` and `#ml` compare equal; forms of at least five characters also match
+    /// across a single insertion, deletion or substitution outside their numbers. Only
+    /// clusters with more than one tag are returned, ordered by canonical tag.
+    pub fn near_duplicate_tags(&self) -> Vec<TagCluster> {
+        let mut usages: Vec<TagUsage> = self
+            .all_tags()
+            .map(|tag| TagUsage {
+                tag: tag.clone(),
+                files: self.files_with_tag(tag).count(),
+            })
+            .collect();
+        usages.sort_by(|a, b| a.tag.cmp(&b.tag));
+        let forms: Vec<Vec<char>> = usages.iter().map(|u| tag_form(&u.tag.0)).collect();
+
+        let mut parent: Vec<usize> = (0..usages.len()).collect();
+        for i in 0..forms.len() {
+            for j in i + 1..forms.len() {
+                if tag_forms_match(&forms[i], &forms[j]) {
+                    let (a, b) = (find_root(&mut parent, i), find_root(&mut parent, j));
+                    parent[b.max(a)] = a.min(b);
+                }
+            }
+        }
+
+        let mut groups: BTreeMap<usize, Vec<TagUsage>> = BTreeMap::new();
+        for (i, usage) in usages.into_iter().enumerate() {
+            let root = find_root(&mut parent, i);
+            groups.entry(root).or_default().push(usage);
+        }
+
+        let mut clusters: Vec<TagCluster> = groups
+            .into_values()
+            .filter(|tags| tags.len() > 1)
+            .map(|mut tags| {
+                tags.sort_by(|a, b| {
+                    b.files
+                        .cmp(&a.files)
+                        .then_with(|| a.tag.0.len().cmp(&b.tag.0.len()))
+                        .then_with(|| a.tag.cmp(&b.tag))
+                });
+                TagCluster {
+                    canonical: tags[0].tag.clone(),
+                    tags,
+                }
+            })
+            .collect();
+        clusters.sort_by(|a, b| a.canonical.cmp(&b.canonical));
+        clusters
+    }
+
+    /// Groups markdown notes by lowercased stem, keeping only groups with more than one note.
+    pub fn duplicate_basenames(&self) -> Vec<DuplicateGroup> {
+        let mut by_stem: BTreeMap<String, Vec<VaultPath>> = BTreeMap::new();
+        for (path, note) in self.notes_iter() {
+            if note.file.kind != FileKind::Markdown {
+                continue;
+            }
+            let Some(stem) = path.as_path().file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            by_stem
+                .entry(stem.to_lowercase())
+                .or_default()
+                .push(path.clone());
+        }
+
+        by_stem
+            .into_iter()
+            .filter(|(_, notes)| notes.len() > 1)
+            .map(|(name, mut notes)| {
+                notes.sort();
+                DuplicateGroup { name, notes }
+            })
+            .collect()
+    }
+
+    /// Groups notes with identical content hashes (`name` is the hash), keeping only groups
+    /// with more than one note. Empty unless content hashes were computed.
+    pub fn duplicate_contents(&self) -> Vec<DuplicateGroup> {
+        let mut by_hash: BTreeMap<String, Vec<VaultPath>> = BTreeMap::new();
+        for (path, note) in self.notes_iter() {
+            if let Some(hash) = &note.file.content_hash {
+                by_hash.entry(hash.clone()).or_default().push(path.clone());
+            }
+        }
+
+        by_hash
+            .into_iter()
+            .filter(|(_, notes)| notes.len() > 1)
+            .map(|(name, mut notes)| {
+                notes.sort();
+                DuplicateGroup { name, notes }
+            })
+            .collect()
+    }
+
+    /// Suggests folder-qualified names for each duplicate and lists the links each rename touches.
+    pub fn duplicate_merge_plan(&self) -> Result<Vec<MergePlan>> {
+        let groups = self.duplicate_basenames();
+        if groups.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let graph = crate::graph::build_graph(self);
+        groups
+            .into_iter()
+            .map(|group| {
+                let suggested = suggest_disambiguated_paths(&group.notes)?;
+                let renames = group
+                    .notes
+                    .iter()
+                    .zip(suggested)
+                    .map(|(path, suggested_path)| RenameSuggestion {
+                        path: path.clone(),
+                        suggested_path,
+                        inbound: graph.rename_impact(path),
+                    })
+                    .collect();
+                Ok(MergePlan {
+                    name: group.name,
+                    renames,
+                })
+            })
+            .collect()
+    }
+}
+
+fn suggest_disambiguated_paths(notes: &[VaultPath]) -> Result<Vec<VaultPath>> {
+    // Prefer the immediate parent folder; fall back to the full parent path when that still collides.
+    let short: Vec<PathBuf> = notes
+        .iter()
+        .map(|p| qualified_path(p.as_path(), parent_label(p.as_path(), false)))
+        .collect();
+    let unique: BTreeSet<String> = short
+        .iter()
+        .map(|p| p.to_string_lossy().to_lowercase())
+        .collect();
+    let paths = if unique.len() == short.len() {
+        short
+    } else {
+        notes
+            .iter()
+            .map(|p| qualified_path(p.as_path(), parent_label(p.as_path(), true)))
+            .collect()
+    };
+
+    paths
+        .into_iter()
+        .map(|p| VaultPath::try_from(p.as_path()))
+        .collect()
+}
+
+fn parent_label(path: &Path, full: bool) -> String {
+    let parent = path.parent().unwrap_or(Path::new(""));
+    let components: Vec<String> = parent
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if components.is_empty() {
+        return "root".to_string();
+    }
+    if full {
+        components.join("-")
+    } else {
+        components.last().cloned().unwrap_or_default()
+    }
+}
+
+fn qualified_path(path: &Path, label: String) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let file_name = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{stem} ({label}).{ext}"),
+        None => format!("{stem} ({label})"),
+    };
+    path.with_file_name(file_name)
+}
+
+/// Lowercased tag with everything but letters, digits and `/` removed.
+fn tag_form(tag: &str) -> Vec<char> {
+    tag.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '/')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn tag_forms_match(a: &[char], b: &[char]) -> bool {
+    if a == b {
+        return true;
+    }
+    a.len().min(b.len()) >= MIN_FUZZY_TAG_LEN
+        && a.len().abs_diff(b.len()) <= 1
+        && digit_runs(a).eq(digit_runs(b))
+        && edit_distance(a, b) <= 1
+}
+
+/// Runs of consecutive digits; tags differing in them (`log/2024/01`, `project2`) are
+/// distinct series members, not misspellings.
+fn digit_runs(form: &[char]) -> impl Iterator<Item = &[char]> {
+    form.split(|c| !c.is_ascii_digit())
+        .filter(|run| !run.is_empty())
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != cb);
+            cur[j + 1] = substitute.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
@@ -6,7 +6,6 @@ use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Instant;
 
 use lru::LruCache;
-use sha2::{Digest, Sha256};
 use tokenizers::Tokenizer;
 use tracing::{debug, info};
 use tract_onnx::prelude::tract_ndarray as ndarray;
@@ -15,12 +14,63 @@ use tract_onnx::prelude::{
     tvec,
 };
 
-use crate::{Error, Result, Vault};
+use crate::{Error, Result, Vault, VaultConfig};
 
 pub(crate) struct EmbeddingModel {
     model: TypedRunnableModel<TypedModel>,
     tokenizer: Tokenizer,
     max_length: usize,
+    /// Inputs the model declares, in declaration order.
+    inputs: Vec<ModelInput>,
+}
+
+/// A tokenizer-derived tensor a transformer ONNX export may take as input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModelInput {
+    InputIds,
+    AttentionMask,
+    TokenTypeIds,
+}
+
+impl ModelInput {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "input_ids" => Some(Self::InputIds),
+            "attention_mask" => Some(Self::AttentionMask),
+            "token_type_ids" => Some(Self::TokenTypeIds),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::InputIds => "input_ids",
+            Self::AttentionMask => "attention_mask",
+            Self::TokenTypeIds => "token_type_ids",
+        }
+    }
+}
+
+/// Maps declared ONNX input names to tokenizer tensors; `token_type_ids` is optional.
+fn resolve_model_inputs(names: &[String]) -> Result<Vec<ModelInput>> {
+    let mut inputs = Vec::with_capacity(names.len());
+    for name in names {
+        let input = ModelInput::from_name(name).ok_or_else(|| {
+            Error::Embedding(format!(
+                "onnx model declares unsupported input {name:?} (expected input_ids, attention_mask, token_type_ids)"
+            ))
+        })?;
+        inputs.push(input);
+    }
+    for required in [ModelInput::InputIds, ModelInput::AttentionMask] {
+        if !inputs.contains(&required) {
+            return Err(Error::Embedding(format!(
+                "onnx model is missing required input {}",
+                required.name()
+            )));
+        }
+    }
+    Ok(inputs)
 }
 
 const EMBEDDING_MODEL_CACHE_CAP: usize = 4;
@@ -94,34 +144,42 @@ impl EmbeddingModel {
             .map_err(|e| Error::Embedding(format!("tokenizer load failed: {e}")))?;
 
         let max_length = cfg.embedding_max_length.max(8);
-        let model = tract_onnx::onnx()
+        let mut model = tract_onnx::onnx()
             .model_for_path(&assets.model_path)
-            .map_err(|e| Error::Embedding(format!("onnx load failed: {e}")))?
-            .with_input_fact(
-                0,
-                InferenceFact::dt_shape(DatumType::I64, tvec!(1, max_length as i64)),
-            )
-            .map_err(|e| Error::Embedding(format!("onnx input_ids shape failed: {e}")))?
-            .with_input_fact(
-                1,
-                InferenceFact::dt_shape(DatumType::I64, tvec!(1, max_length as i64)),
-            )
-            .map_err(|e| Error::Embedding(format!("onnx attention_mask shape failed: {e}")))?
-            .with_input_fact(
-                2,
-                InferenceFact::dt_shape(DatumType::I64, tvec!(1, max_length as i64)),
-            )
-            .map_err(|e| Error::Embedding(format!("onnx token_type_ids shape failed: {e}")))?
+            .map_err(|e| Error::Embedding(format!("onnx load failed: {e}")))?;
+        let input_names: Vec<String> = model
+            .input_outlets()
+            .map_err(|e| Error::Embedding(format!("onnx inputs failed: {e}")))?
+            .iter()
+            .map(|outlet| model.node(outlet.node).name.clone())
+            .collect();
+        let inputs = resolve_model_inputs(&input_names)?;
+        for (ix, input) in inputs.iter().enumerate() {
+            model = model
+                .with_input_fact(
+                    ix,
+                    InferenceFact::dt_shape(DatumType::I64, tvec!(1, max_length as i64)),
+                )
+                .map_err(|e| {
+                    Error::Embedding(format!("onnx {} shape failed: {e}", input.name()))
+                })?;
+        }
+        let model = model
             .into_optimized()
             .map_err(|e| Error::Embedding(format!("onnx optimize failed: {e}")))?
             .into_runnable()
             .map_err(|e| Error::Embedding(format!("onnx runnable failed: {e}")))?;
 
-        info!(pooling = "mean", "embedding model loaded");
+        info!(
+            pooling = "mean",
+            inputs = inputs.len(),
+            "embedding model loaded"
+        );
         Ok(Self {
             model,
             tokenizer,
             max_length,
+            inputs,
         })
     }
 
@@ -172,24 +230,26 @@ impl EmbeddingModel {
             "tokenized input"
         );
 
-        let input_ids_arr = ndarray::Array2::from_shape_vec((1, self.max_length), ids)
-            .map_err(|e| Error::Embedding(format!("input_ids shape failed: {e}")))?;
         let attention_mask_arr = ndarray::Array2::from_shape_vec((1, self.max_length), mask)
             .map_err(|e| Error::Embedding(format!("attention_mask shape failed: {e}")))?;
-        let token_type_ids_arr = ndarray::Array2::from_shape_vec((1, self.max_length), type_ids)
-            .map_err(|e| Error::Embedding(format!("token_type_ids shape failed: {e}")))?;
-
-        let input_ids: Tensor = Tensor::from(input_ids_arr);
-        let attention_mask: Tensor = Tensor::from(attention_mask_arr.clone());
-        let token_type_ids: Tensor = Tensor::from(token_type_ids_arr);
+        let mut run_inputs = tvec![];
+        for input in &self.inputs {
+            let arr = match input {
+                ModelInput::InputIds => {
+                    ndarray::Array2::from_shape_vec((1, self.max_length), ids.clone())
+                }
+                ModelInput::AttentionMask => Ok(attention_mask_arr.clone()),
+                ModelInput::TokenTypeIds => {
+                    ndarray::Array2::from_shape_vec((1, self.max_length), type_ids.clone())
+                }
+            }
+            .map_err(|e| Error::Embedding(format!("{} shape failed: {e}", input.name())))?;
+            run_inputs.push(Tensor::from(arr).into());
+        }
 
         let outputs = self
             .model
-            .run(tvec![
-                input_ids.into(),
-                attention_mask.clone().into(),
-                token_type_ids.into()
-            ])
+            .run(run_inputs)
             .map_err(|e| Error::Embedding(format!("onnx run failed: {e}")))?;
         let output = outputs[0]
             .to_array_view::<f32>()
@@ -289,25 +349,62 @@ fn ensure_asset(path: &Path, url: &str) -> Result<()> {
     Ok(())
 }
 
-pub(crate) fn hash_text(text: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(text.as_bytes());
-    let out = hasher.finalize();
-    hex::encode(out)
+/// Text embedded for a note: its cleaned body, preceded by frontmatter values when
+/// [`VaultConfig::embedding_include_frontmatter`] is set.
+pub(crate) fn note_text_for_embedding(cfg: &VaultConfig, text: &str) -> String {
+    let body = clean_markdown_for_embedding(text, cfg.indented_code_blocks);
+    if !cfg.embedding_include_frontmatter {
+        return body;
+    }
+    let Some((yaml, _)) = crate::parse::frontmatter_split(text) else {
+        return body;
+    };
+    let Ok(serde_yaml::Value::Mapping(map)) = serde_yaml::from_str(yaml) else {
+        return body;
+    };
+
+    let mut values = Vec::new();
+    for (key, value) in &map {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+        if cfg.embedding_frontmatter_keys.is_empty()
+            || cfg.embedding_frontmatter_keys.iter().any(|k| k == key)
+        {
+            collect_yaml_text(value, &mut values);
+        }
+    }
+    let prefix = clean_markdown_for_embedding(&values.join("\n"), false);
+    normalize_whitespace(&format!("{prefix} {body}"))
 }
 
-pub(crate) fn clean_markdown_for_embedding(text: &str) -> String {
-    let body = strip_frontmatter(text);
+fn collect_yaml_text(value: &serde_yaml::Value, out: &mut Vec<String>) {
+    match value {
+        serde_yaml::Value::String(s) => out.push(s.clone()),
+        serde_yaml::Value::Number(n) => out.push(n.to_string()),
+        serde_yaml::Value::Bool(b) => out.push(b.to_string()),
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                collect_yaml_text(item, out);
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for item in map.values() {
+                collect_yaml_text(item, out);
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => collect_yaml_text(&tagged.value, out),
+        serde_yaml::Value::Null => {}
+    }
+}
+
+pub(crate) fn clean_markdown_for_embedding(text: &str, indented_code: bool) -> String {
+    let body = crate::parse::frontmatter_split(text).map_or(text, |(_, body)| body);
     let mut out = String::new();
-    let mut in_fenced = false;
+    let mut code = crate::parse::CodeBlocks::new(indented_code);
 
     for line in body.lines() {
-        let trimmed = line.trim_start();
-        if trimmed.starts_with("```") {
-            in_fenced = !in_fenced;
-            continue;
-        }
-        if in_fenced {
+        if code.skip(line) {
             continue;
         }
 
@@ -353,30 +450,6 @@ pub(crate) fn clean_markdown_for_embedding(text: &str) -> String {
     normalize_whitespace(&out)
 }
 
-fn strip_frontmatter(text: &str) -> &str {
-    let Some(rest) = text
-        .strip_prefix("---\n")
-        .or_else(|| text.strip_prefix("---\r\n"))
-    else {
-        return text;
-    };
-    let bytes = rest.as_bytes();
-    let mut idx = 0usize;
-    while idx < bytes.len() {
-        let line_end = match bytes[idx..].iter().position(|b| *b == b'\n') {
-            Some(off) => idx + off + 1,
-            None => bytes.len(),
-        };
-        let line = &rest[idx..line_end];
-        let line_trim = line.trim_end_matches(['\r', '\n']);
-        if line_trim == "---" {
-            return &rest[line_end..];
-        }
-        idx = line_end;
-    }
-    text
-}
-
 fn scan_until<'a>(s: &'a str, start: usize, delim: &str) -> Option<(&'a str, usize)> {
     s[start..]
         .find(delim)
@@ -468,6 +541,60 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
 
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn two_input_model_binds_only_declared_inputs() {
+        let inputs = resolve_model_inputs(&names(&["input_ids", "attention_mask"]))
+            .expect("two-input model");
+        assert_eq!(
+            inputs,
+            vec![ModelInput::InputIds, ModelInput::AttentionMask]
+        );
+
+        let inputs =
+            resolve_model_inputs(&names(&["input_ids", "attention_mask", "token_type_ids"]))
+                .expect("three-input model");
+        assert_eq!(inputs.len(), 3);
+    }
+
+    #[test]
+    fn missing_required_input_is_a_clear_error() {
+        let err = resolve_model_inputs(&names(&["input_ids"])).expect_err("no mask");
+        assert!(
+            err.to_string()
+                .contains("missing required input attention_mask")
+        );
+
+        let err = resolve_model_inputs(&names(&["input_ids", "pixel_values"]))
+            .expect_err("unknown input");
+        assert!(
+            err.to_string()
+                .contains("unsupported input \"pixel_values\"")
+        );
+    }
+
+    #[test]
+    fn frontmatter_values_are_embedded_only_when_enabled() {
+        let note = "---\nsummary: Migrating billing to Postgres\ntags: [db]\n---\nBody text.\n";
+        let mut cfg = VaultConfig::default();
+        assert_eq!(note_text_for_embedding(&cfg, note), "Body text");
+
+        cfg.embedding_include_frontmatter = true;
+        assert_eq!(
+            note_text_for_embedding(&cfg, note),
+            "Migrating billing to Postgres db Body text"
+        );
+
+        cfg.embedding_frontmatter_keys = vec!["summary".into()];
+        assert_eq!(
+            note_text_for_embedding(&cfg, note),
+            "Migrating billing to Postgres Body text"
+        );
+    }
+
     #[test]
     fn cache_eviction_respects_lru_capacity() {
         let mut cache = LruCache::new(NonZeroUsize::new(1).expect("non-zero"));
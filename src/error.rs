@@ -31,6 +31,15 @@ pub enum Error {
 
     #[error("embedding error: {0}")]
     Embedding(String),
+
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+
+    #[error("invalid task status: {0:?}")]
+    InvalidTaskStatus(String),
+
+    #[error("invalid ISO-8601 date: {0:?}")]
+    InvalidDate(String),
 }
 
 impl Error {
@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{FieldValue, VaultIndex};
+
+/// Distinct scalar values beyond which a field is treated as free-form.
+const MAX_OBSERVED_VALUES: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldValueKind {
+    String,
+    Number,
+    Bool,
+    List,
+    Object,
+    Null,
+}
+
+impl FieldValueKind {
+    pub fn of(value: &FieldValue) -> Self {
+        match value {
+            FieldValue::Null => Self::Null,
+            FieldValue::Bool(_) => Self::Bool,
+            FieldValue::Number(_) => Self::Number,
+            FieldValue::String(_) => Self::String,
+            FieldValue::List(_) => Self::List,
+            FieldValue::Object(_) => Self::Object,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Bool => "bool",
+            Self::List => "list",
+            Self::Object => "object",
+            Self::Null => "null",
+        }
+    }
+}
+
+/// Usage of one field key across the vault's notes.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct InferredField {
+    pub key: String,
+    /// Most common value kind; ties prefer the earlier [`FieldValueKind`] variant.
+    pub kind: FieldValueKind,
+    /// Number of notes that set the key.
+    pub notes: usize,
+    /// Distinct scalar values seen, or `None` when there are too many to enumerate.
+    pub values: Option<Vec<String>>,
+}
+
+/// Field usage inferred from existing notes, ahead of writing a schema by hand.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct FieldSchemaDraft {
+    pub fields: Vec<InferredField>,
+}
+
+impl FieldSchemaDraft {
+    /// Renders the draft as a commented report, one line per field.
+    ///
+    /// Scopes have no per-field rules, so this is meant for reading, not for pasting
+    /// into `schema.toml`.
+    pub fn to_report(&self) -> String {
+        let mut out = String::from("# Field usage inferred from existing notes\n");
+        for f in &self.fields {
+            let mut line = format!(
+                "# {}: {}, {} notes",
+                toml_key(&f.key),
+                f.kind.as_str(),
+                f.notes
+            );
+            if let Some(values) = &f.values {
+                let values: Vec<String> = values.iter().map(|v| toml_string(v)).collect();
+                line.push_str(&format!(", values [{}]", values.join(", ")));
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl VaultIndex {
+    /// Infers each field key's dominant kind and observed values from all notes.
+    pub fn infer_field_schema(&self) -> FieldSchemaDraft {
+        #[derive(Default)]
+        struct Usage {
+            notes: usize,
+            kinds: BTreeMap<FieldValueKind, usize>,
+            values: BTreeSet<String>,
+        }
+
+        let mut by_key: BTreeMap<&str, Usage> = BTreeMap::new();
+        for (_, note) in self.notes_iter() {
+            for (key, value) in &note.fields {
+                let usage = by_key.entry(key.as_str()).or_default();
+                usage.notes += 1;
+                *usage.kinds.entry(FieldValueKind::of(value)).or_default() += 1;
+                // Stop collecting once past the cap; the overflow marks the field free-form.
+                if usage.values.len() <= MAX_OBSERVED_VALUES {
+                    collect_scalar_values(value, &mut usage.values);
+                }
+            }
+        }
+
+        let fields = by_key
+            .into_iter()
+            .map(|(key, usage)| {
+                let kind = usage
+                    .kinds
+                    .iter()
+                    .max_by(|(ka, na), (kb, nb)| na.cmp(nb).then_with(|| kb.cmp(ka)))
+                    .map(|(k, _)| *k)
+                    .unwrap_or(FieldValueKind::Null);
+                let values = (!usage.values.is_empty()
+                    && usage.values.len() <= MAX_OBSERVED_VALUES)
+                    .then(|| usage.values.into_iter().collect());
+                InferredField {
+                    key: key.to_string(),
+                    kind,
+                    notes: usage.notes,
+                    values,
+                }
+            })
+            .collect();
+        FieldSchemaDraft { fields }
+    }
+}
+
+fn collect_scalar_values(value: &FieldValue, out: &mut BTreeSet<String>) {
+    match value {
+        FieldValue::String(s) => {
+            out.insert(s.clone());
+        }
+        FieldValue::Number(n) => {
+            out.insert(n.to_string());
+        }
+        FieldValue::Bool(b) => {
+            out.insert(b.to_string());
+        }
+        FieldValue::List(items) => {
+            for item in items {
+                collect_scalar_values(item, out);
+            }
+        }
+        FieldValue::Null | FieldValue::Object(_) => {}
+    }
+}
+
+fn toml_string(s: &str) -> String {
+    toml::Value::String(s.to_string()).to_string()
+}
+
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if bare {
+        key.to_string()
+    } else {
+        toml_string(key)
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use crate::Error;
+use crate::{Date, Error};
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum FieldValue {
@@ -14,6 +14,35 @@ pub enum FieldValue {
 
 pub type FieldMap = BTreeMap<String, FieldValue>;
 
+impl FieldValue {
+    /// Interprets a string value as an ISO-8601 date (or datetime).
+    pub fn as_date(&self) -> Option<Date> {
+        match self {
+            FieldValue::String(s) => Date::parse_iso8601(s),
+            _ => None,
+        }
+    }
+
+    /// String values, flattening lists; other scalars and objects are skipped.
+    pub(crate) fn strings(&self) -> Vec<&str> {
+        let mut out = Vec::new();
+        collect_strings(self, &mut out);
+        out
+    }
+}
+
+fn collect_strings<'a>(value: &'a FieldValue, out: &mut Vec<&'a str>) {
+    match value {
+        FieldValue::String(s) => out.push(s),
+        FieldValue::List(items) => {
+            for item in items {
+                collect_strings(item, out);
+            }
+        }
+        FieldValue::Null | FieldValue::Bool(_) | FieldValue::Number(_) | FieldValue::Object(_) => {}
+    }
+}
+
 pub fn normalize_field_key(key: &str) -> Option<String> {
     let k = key.trim();
     if k.is_empty() {
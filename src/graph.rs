@@ -1,4 +1,9 @@
-use crate::{Backlink, BacklinksIndex, Link, LinkTarget, ResolveResult, VaultIndex, VaultPath};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    Backlink, BacklinksIndex, FileKind, FrontmatterStatus, Link, LinkTarget, ResolveResult,
+    Subpath, VaultIndex, VaultPath,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct ResolvedInternalLink {
@@ -13,6 +18,75 @@ pub struct GraphIndex {
     pub issues: Vec<ResolvedInternalLink>,
 }
 
+/// A set of notes connected by resolved internal links, ignoring link direction.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct GraphComponent {
+    pub size: usize,
+    pub members: Vec<VaultPath>,
+}
+
+/// Aggregate directed metrics over distinct resolved links between files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct DirectedStats {
+    /// Files with at least one resolved link in either direction.
+    pub nodes: usize,
+    /// Distinct `source -> target` pairs; self-links are ignored.
+    pub edges: usize,
+    /// Unordered pairs linked in both directions.
+    pub reciprocal_pairs: usize,
+    /// Number of nodes per in-degree.
+    pub in_degree: BTreeMap<usize, usize>,
+    /// Number of nodes per out-degree.
+    pub out_degree: BTreeMap<usize, usize>,
+}
+
+/// Plain node/edge export of the resolved link graph, free of UI decoration.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct GraphExport {
+    pub nodes: Vec<GraphExportNode>,
+    pub edges: Vec<GraphExportEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct GraphExportNode {
+    pub path: VaultPath,
+    /// Note title; `None` for non-note targets such as attachments.
+    pub title: Option<String>,
+    pub kind: FileKind,
+    /// Per-note counts; `None` for non-note targets.
+    #[serde(flatten)]
+    pub counts: Option<NoteCounts>,
+}
+
+/// Link, tag and task counts for one note.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct NoteCounts {
+    pub tags: usize,
+    /// Resolved outgoing internal link occurrences.
+    pub outgoing_links: usize,
+    /// Resolved inbound internal link occurrences.
+    pub backlinks: usize,
+    pub tasks: usize,
+    pub frontmatter: FrontmatterStatus,
+}
+
+/// One overview row per note.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct NoteSummary {
+    pub path: VaultPath,
+    pub title: String,
+    #[serde(flatten)]
+    pub counts: NoteCounts,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct GraphExportEdge {
+    pub source: VaultPath,
+    pub target: VaultPath,
+    pub embed: bool,
+    pub subpath: Option<Subpath>,
+}
+
 impl GraphIndex {
     pub fn unresolved(&self) -> impl Iterator<Item = &ResolvedInternalLink> {
         self.issues
@@ -29,6 +103,312 @@ impl GraphIndex {
     pub fn backlinks(&self, target: &VaultPath) -> &[Backlink] {
         self.backlinks.backlinks(target)
     }
+
+    /// Links that would need updating if `target` were renamed: resolved backlinks plus
+    /// ambiguous links that list `target` as a candidate.
+    pub fn rename_impact(&self, target: &VaultPath) -> Vec<Backlink> {
+        let mut out = self.backlinks(target).to_vec();
+        out.extend(
+            self.ambiguous()
+                .filter(
+                    |i| matches!(&i.resolution, ResolveResult::Ambiguous(c) if c.contains(target)),
+                )
+                .map(|i| Backlink {
+                    source: i.source.clone(),
+                    link: i.link.clone(),
+                }),
+        );
+        out.sort_by(|a, b| {
+            a.source
+                .cmp(&b.source)
+                .then_with(|| a.link.location.cmp(&b.link.location))
+        });
+        out
+    }
+
+    /// One row per note with its title and counts, sorted by path.
+    pub fn note_summaries(&self, index: &VaultIndex) -> Vec<NoteSummary> {
+        let mut out = Vec::new();
+        let mut counts = self.note_counts(index);
+        for (path, note) in index.notes_iter() {
+            let Some(counts) = counts.remove(path) else {
+                continue;
+            };
+            out.push(NoteSummary {
+                path: path.clone(),
+                title: note.title.clone(),
+                counts,
+            });
+        }
+        out.sort_by(|a, b| a.path.cmp(&b.path));
+        out
+    }
+
+    fn note_counts(&self, index: &VaultIndex) -> BTreeMap<VaultPath, NoteCounts> {
+        let mut outgoing: BTreeMap<&VaultPath, usize> = BTreeMap::new();
+        for inbound in self.backlinks.inbound.values() {
+            for b in inbound {
+                *outgoing.entry(&b.source).or_default() += 1;
+            }
+        }
+
+        index
+            .notes_iter()
+            .map(|(path, note)| {
+                let counts = NoteCounts {
+                    tags: note.tags.len(),
+                    outgoing_links: outgoing.get(path).copied().unwrap_or(0),
+                    backlinks: self.backlinks(path).len(),
+                    tasks: note.tasks.len(),
+                    frontmatter: note.frontmatter.clone(),
+                };
+                (path.clone(), counts)
+            })
+            .collect()
+    }
+
+    /// Every note plus each resolved link target as nodes, and one edge per resolved
+    /// internal link occurrence.
+    pub fn export(&self, index: &VaultIndex) -> GraphExport {
+        let mut counts = self.note_counts(index);
+        let mut nodes: BTreeMap<VaultPath, GraphExportNode> = BTreeMap::new();
+        for (path, note) in index.notes_iter() {
+            nodes.insert(
+                path.clone(),
+                GraphExportNode {
+                    path: path.clone(),
+                    title: Some(note.title.clone()),
+                    kind: note.file.kind,
+                    counts: counts.remove(path),
+                },
+            );
+        }
+
+        let mut edges = Vec::new();
+        for (target, inbound) in &self.backlinks.inbound {
+            if !nodes.contains_key(target)
+                && let Some(file) = index.file(target)
+            {
+                nodes.insert(
+                    target.clone(),
+                    GraphExportNode {
+                        path: target.clone(),
+                        title: None,
+                        kind: file.kind,
+                        counts: None,
+                    },
+                );
+            }
+            for b in inbound {
+                edges.push((
+                    b.link.location,
+                    GraphExportEdge {
+                        source: b.source.clone(),
+                        target: target.clone(),
+                        embed: b.link.embed,
+                        subpath: b.link.subpath.clone(),
+                    },
+                ));
+            }
+        }
+        edges.sort_by(|(la, a), (lb, b)| {
+            a.source
+                .cmp(&b.source)
+                .then_with(|| la.cmp(lb))
+                .then_with(|| a.target.cmp(&b.target))
+        });
+
+        GraphExport {
+            nodes: nodes.into_values().collect(),
+            edges: edges.into_iter().map(|(_, e)| e).collect(),
+        }
+    }
+
+    /// Serializes [`export`](Self::export) as GEXF 1.3 for Gephi.
+    ///
+    /// Nodes are keyed by path with `title` and `kind` attributes; edges are directed and
+    /// weighted by the number of link occurrences between the pair.
+    pub fn to_gexf(&self, index: &VaultIndex) -> String {
+        let export = self.export(index);
+        let mut weights: BTreeMap<(&VaultPath, &VaultPath), usize> = BTreeMap::new();
+        for e in &export.edges {
+            *weights.entry((&e.source, &e.target)).or_default() += 1;
+        }
+
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n\
+             <graph defaultedgetype=\"directed\">\n\
+             <attributes class=\"node\">\n\
+             <attribute id=\"title\" title=\"title\" type=\"string\"/>\n\
+             <attribute id=\"kind\" title=\"kind\" type=\"string\"/>\n\
+             </attributes>\n\
+             <nodes>\n",
+        );
+        for n in &export.nodes {
+            let path = xml_escape(&n.path.as_str_lossy());
+            let title = xml_escape(n.title.as_deref().unwrap_or(""));
+            let label = if title.is_empty() { &path } else { &title };
+            out.push_str(&format!(
+                "<node id=\"{path}\" label=\"{label}\"><attvalues>\
+                 <attvalue for=\"title\" value=\"{title}\"/>\
+                 <attvalue for=\"kind\" value=\"{}\"/>\
+                 </attvalues></node>\n",
+                file_kind_name(n.kind)
+            ));
+        }
+        out.push_str("</nodes>\n<edges>\n");
+        for (i, ((source, target), weight)) in weights.into_iter().enumerate() {
+            out.push_str(&format!(
+                "<edge id=\"{i}\" source=\"{}\" target=\"{}\" weight=\"{weight}\"/>\n",
+                xml_escape(&source.as_str_lossy()),
+                xml_escape(&target.as_str_lossy())
+            ));
+        }
+        out.push_str("</edges>\n</graph>\n</gexf>\n");
+        out
+    }
+
+    /// Notes with at least one resolved backlink but no resolved outgoing internal links,
+    /// sorted by path. Self-links count in neither direction; attachments are excluded.
+    pub fn dead_ends(&self, index: &VaultIndex) -> Vec<VaultPath> {
+        let mut linked_to = BTreeSet::new();
+        let mut links_out = BTreeSet::new();
+        for (target, inbound) in &self.backlinks.inbound {
+            for b in inbound.iter().filter(|b| &b.source != target) {
+                linked_to.insert(target);
+                links_out.insert(&b.source);
+            }
+        }
+
+        linked_to
+            .into_iter()
+            .filter(|p| !links_out.contains(p))
+            .filter(|p| index.note(p).is_some())
+            .cloned()
+            .collect()
+    }
+
+    /// Reciprocity and in/out-degree distribution of the resolved link graph.
+    ///
+    /// Repeated links between the same pair count once; nodes are only files that
+    /// take part in some link, so orphans do not inflate the zero-degree buckets.
+    pub fn directed_stats(&self) -> DirectedStats {
+        let mut edges: BTreeSet<(&VaultPath, &VaultPath)> = BTreeSet::new();
+        for (target, inbound) in &self.backlinks.inbound {
+            for b in inbound.iter().filter(|b| &b.source != target) {
+                edges.insert((&b.source, target));
+            }
+        }
+
+        let mut in_deg: BTreeMap<&VaultPath, usize> = BTreeMap::new();
+        let mut out_deg: BTreeMap<&VaultPath, usize> = BTreeMap::new();
+        let mut reciprocal_pairs = 0usize;
+        for &(source, target) in &edges {
+            *out_deg.entry(source).or_default() += 1;
+            *in_deg.entry(target).or_default() += 1;
+            in_deg.entry(source).or_default();
+            out_deg.entry(target).or_default();
+            if source < target && edges.contains(&(target, source)) {
+                reciprocal_pairs += 1;
+            }
+        }
+
+        let histogram = |degrees: BTreeMap<&VaultPath, usize>| {
+            let mut out: BTreeMap<usize, usize> = BTreeMap::new();
+            for d in degrees.into_values() {
+                *out.entry(d).or_default() += 1;
+            }
+            out
+        };
+        DirectedStats {
+            nodes: in_deg.len(),
+            edges: edges.len(),
+            reciprocal_pairs,
+            in_degree: histogram(in_deg),
+            out_degree: histogram(out_deg),
+        }
+    }
+
+    /// Connected components over resolved internal links (undirected), largest first.
+    ///
+    /// Only files that take part in at least one resolved link are considered, so
+    /// orphans never show up as singleton components.
+    pub fn connected_components(&self) -> Vec<GraphComponent> {
+        let mut ids: BTreeMap<&VaultPath, usize> = BTreeMap::new();
+        let mut edges = Vec::new();
+        for (target, inbound) in &self.backlinks.inbound {
+            for b in inbound {
+                edges.push((&b.source, target));
+            }
+        }
+        for (a, b) in &edges {
+            let next = ids.len();
+            ids.entry(a).or_insert(next);
+            let next = ids.len();
+            ids.entry(b).or_insert(next);
+        }
+
+        let mut parent: Vec<usize> = (0..ids.len()).collect();
+        fn find(parent: &mut [usize], mut x: usize) -> usize {
+            while parent[x] != x {
+                parent[x] = parent[parent[x]];
+                x = parent[x];
+            }
+            x
+        }
+        for (a, b) in &edges {
+            let ra = find(&mut parent, ids[a]);
+            let rb = find(&mut parent, ids[b]);
+            if ra != rb {
+                parent[ra.max(rb)] = ra.min(rb);
+            }
+        }
+
+        let mut groups: BTreeMap<usize, Vec<VaultPath>> = BTreeMap::new();
+        for (path, id) in &ids {
+            let root = find(&mut parent, *id);
+            groups.entry(root).or_default().push((*path).clone());
+        }
+
+        let mut out: Vec<GraphComponent> = groups
+            .into_values()
+            .map(|members| GraphComponent {
+                size: members.len(),
+                members,
+            })
+            .collect();
+        out.sort_by(|a, b| {
+            b.size
+                .cmp(&a.size)
+                .then_with(|| a.members.first().cmp(&b.members.first()))
+        });
+        out
+    }
+}
+
+fn file_kind_name(kind: FileKind) -> &'static str {
+    match kind {
+        FileKind::Markdown => "markdown",
+        FileKind::Canvas => "canvas",
+        FileKind::Attachment => "attachment",
+        FileKind::Other => "other",
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
 }
 
 pub(crate) fn build_graph(index: &VaultIndex) -> GraphIndex {
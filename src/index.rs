@@ -1,6 +1,7 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::ControlFlow;
 use std::path::Path;
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 
 use nucleo::{
     Matcher, Utf32Str,
@@ -11,14 +12,14 @@ use crate::fields::{
     FieldMap, extract_top_level_frontmatter_fields, inline_value_to_field_value, merge_field,
     normalize_field_key,
 };
-use crate::parse::{FrontmatterParse, parse_markdown_note};
+use crate::parse::{FrontmatterParse, ParsedNote, parse_markdown_note};
 use crate::schema::SchemaState;
 use crate::{
-    BacklinksIndex, Error, Query, QueryHit, Result, Schema, SchemaReport, SchemaSeverity,
-    SchemaStatus, SchemaViolation, SchemaViolationRecord, Vault, VaultPath,
+    BacklinksIndex, Error, FieldMergePolicy, Query, QueryHit, Result, Schema, SchemaReport,
+    SchemaSeverity, SchemaStatus, SchemaViolation, SchemaViolationRecord, Vault, VaultPath,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum FileKind {
     Markdown,
@@ -27,9 +28,37 @@ pub enum FileKind {
     Other,
 }
 
+impl FileKind {
+    /// Stable lowercase name, identical to the JSON serialization.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FileKind::Markdown => "markdown",
+            FileKind::Canvas => "canvas",
+            FileKind::Attachment => "attachment",
+            FileKind::Other => "other",
+        }
+    }
+}
+
+impl std::fmt::Display for FileKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Tag(pub String);
 
+impl Tag {
+    /// True for `ancestor` itself or any tag nested under it: `project` contains
+    /// `project/web` but not `projectx`.
+    pub fn is_within(&self, ancestor: &Tag) -> bool {
+        self.0
+            .strip_prefix(ancestor.0.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    }
+}
+
 impl serde::Serialize for Tag {
     fn serialize<S: serde::Serializer>(
         &self,
@@ -47,6 +76,8 @@ pub struct FileMeta {
     pub kind: FileKind,
     pub mtime: SystemTime,
     pub size: u64,
+    /// SHA-256 of the note text; only computed for notes (whose content is read anyway).
+    pub content_hash: Option<String>,
     pub schema_violations: Vec<SchemaViolation>,
 }
 
@@ -55,13 +86,23 @@ pub struct NoteMeta {
     pub file: FileMeta,
     pub title: String,
     pub aliases: BTreeSet<String>,
+    /// Frontmatter and inline tags, merged.
     pub tags: BTreeSet<Tag>,
+    /// Tags from the frontmatter `tags`/`tag` key.
+    pub frontmatter_tags: BTreeSet<Tag>,
+    /// `#tags` written in the note body.
+    pub inline_tags: BTreeSet<Tag>,
     pub links: BTreeSet<LinkTarget>,
     pub link_occurrences: Vec<crate::Link>,
     pub frontmatter: FrontmatterStatus,
     pub fields: FieldMap,
+    /// Keys set both in frontmatter and as inline fields with a differing value.
+    pub field_conflicts: BTreeSet<String>,
     pub tasks: Vec<Task>,
     pub schema_violations: Vec<SchemaViolation>,
+    /// ISO 639-1 code of the body language; only set with
+    /// [`VaultConfig::detect_language`](crate::VaultConfig::detect_language).
+    pub language: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
@@ -74,6 +115,73 @@ pub enum TaskStatus {
     Blocked,
 }
 
+impl TaskStatus {
+    pub const ALL: [TaskStatus; 5] = [
+        TaskStatus::Todo,
+        TaskStatus::Done,
+        TaskStatus::InProgress,
+        TaskStatus::Cancelled,
+        TaskStatus::Blocked,
+    ];
+
+    /// Stable lowercase name, identical to the JSON serialization.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskStatus::Todo => "todo",
+            TaskStatus::Done => "done",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Cancelled => "cancelled",
+            TaskStatus::Blocked => "blocked",
+        }
+    }
+}
+
+impl std::fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TaskStatus {
+    type Err = Error;
+
+    /// Parses the [`TaskStatus::as_str`] names, case-insensitively.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::InvalidTaskStatus(s.to_string()))
+    }
+}
+
+/// Tasks-plugin priority marker, ordered lowest to highest.
+///
+/// A task without a marker has [`TaskPriority::None`], which ranks between low and
+/// medium, as in the Tasks plugin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskPriority {
+    /// `⏬`
+    Lowest,
+    /// `🔽`
+    Low,
+    #[default]
+    None,
+    /// `🔼`
+    Medium,
+    /// `⏫`
+    High,
+    /// `🔺`
+    Highest,
+}
+
+impl TaskPriority {
+    pub fn is_none(&self) -> bool {
+        *self == TaskPriority::None
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct Task {
     pub path: VaultPath,
@@ -81,6 +189,18 @@ pub struct Task {
     pub line: u32,
     pub status: TaskStatus,
     pub text: String,
+    /// From `📅 YYYY-MM-DD` or `[due:: YYYY-MM-DD]` in the task text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due: Option<crate::Date>,
+    /// From `✅ YYYY-MM-DD` or `[completion:: YYYY-MM-DD]` in the task text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub done: Option<crate::Date>,
+    /// Parsed from a priority emoji, which is removed from `text`.
+    #[serde(skip_serializing_if = "TaskPriority::is_none")]
+    pub priority: TaskPriority,
+    /// Inline `#tags` on the task line; these also count toward the note's tags.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<Tag>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
@@ -108,6 +228,28 @@ pub struct VaultIndex {
     schema_status: SchemaStatus,
     schema_vault_violations: Vec<SchemaViolationRecord>,
     schema: Option<Schema>,
+    build_metrics: Option<BuildMetrics>,
+}
+
+/// Wall-clock time spent in each phase of a full index build.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BuildMetrics {
+    /// Directory traversal and file metadata, excluding the phases below.
+    pub walk: Duration,
+    /// Reading notes and parsing them into tags, links, tasks and fields.
+    pub parse: Duration,
+    /// Schema validation of notes, file layout and the vault layout.
+    pub schema: Duration,
+    /// Updating the file, tag and link maps.
+    pub reconcile: Duration,
+    /// Files indexed.
+    pub files: usize,
+}
+
+impl BuildMetrics {
+    pub fn total(&self) -> Duration {
+        self.walk + self.parse + self.schema + self.reconcile
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
@@ -118,6 +260,16 @@ pub struct IndexDelta {
     pub removed_links: BTreeSet<LinkTarget>,
 }
 
+/// A mismatch between the maintained tag reverse index and the notes' parsed tags.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TagIndexDiscrepancy {
+    /// The note carries the tag but the index does not list it.
+    Missing { tag: Tag, path: VaultPath },
+    /// The index lists the path under a tag the note no longer carries.
+    Stale { tag: Tag, path: VaultPath },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct SearchHit {
     pub path: VaultPath,
@@ -145,11 +297,13 @@ impl VaultIndex {
             schema: schema_state.schema.clone(),
             ..Self::default()
         };
-        for entry in walkdir::WalkDir::new(vault.root())
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
+        let mut metrics = BuildMetrics::default();
+        let started = Instant::now();
+        let mut walk = walkdir::WalkDir::new(vault.root()).follow_links(false);
+        if let Some(depth) = vault.config().max_walk_depth {
+            walk = walk.max_depth(depth);
+        }
+        for entry in walk.into_iter().filter_map(|e| e.ok()) {
             if !entry.file_type().is_file() {
                 continue;
             }
@@ -161,15 +315,39 @@ impl VaultIndex {
             if !vault.is_indexable_rel(rel.as_path()) {
                 continue;
             }
-            idx.upsert_path(vault, rel)?;
+            idx.upsert_path_timed(vault, rel, &mut metrics)?;
+            metrics.files += 1;
         }
+        metrics.walk = started
+            .elapsed()
+            .saturating_sub(metrics.parse + metrics.schema + metrics.reconcile);
         if let Some(schema) = &idx.schema {
+            let t = Instant::now();
             idx.schema_vault_violations = schema.validate_vault_layout(vault);
+            metrics.schema += t.elapsed();
         }
+        tracing::debug!(?metrics, "index built");
+        idx.build_metrics = Some(metrics);
         Ok(idx)
     }
 
+    /// Phase timings of the full build that produced this index.
+    ///
+    /// `None` for an index that was not built from a vault.
+    pub fn build_metrics(&self) -> Option<BuildMetrics> {
+        self.build_metrics
+    }
+
     pub fn upsert_path(&mut self, vault: &Vault, rel: VaultPath) -> Result<IndexDelta> {
+        self.upsert_path_timed(vault, rel, &mut BuildMetrics::default())
+    }
+
+    fn upsert_path_timed(
+        &mut self,
+        vault: &Vault,
+        rel: VaultPath,
+        metrics: &mut BuildMetrics,
+    ) -> Result<IndexDelta> {
         if !vault.is_indexable_rel(rel.as_path()) {
             return Ok(IndexDelta::default());
         }
@@ -188,39 +366,24 @@ impl VaultIndex {
             kind,
             mtime,
             size,
+            content_hash: None,
             schema_violations: Vec::new(),
         };
-        let file_for_note = base_file.clone();
+        let mut file_for_note = base_file.clone();
         let mut file = base_file;
         let (new_tags, new_links, note_meta) = match kind {
             FileKind::Markdown | FileKind::Canvas => {
+                let t = Instant::now();
                 let content = std::fs::read_to_string(&abs).map_err(|e| Error::io(&abs, e))?;
-                let parsed = parse_markdown_note(&rel, &content);
-                let mut fields = FieldMap::new();
-                let mut aliases = BTreeSet::new();
-                let frontmatter = match &parsed.frontmatter {
-                    FrontmatterParse::None => FrontmatterStatus::None,
-                    FrontmatterParse::Valid(fm) => {
-                        if let Ok(fm_fields) = extract_top_level_frontmatter_fields(fm) {
-                            for (k, v) in fm_fields {
-                                merge_field(&mut fields, k, v);
-                            }
-                        }
-                        aliases = extract_frontmatter_aliases(fm);
-                        FrontmatterStatus::Valid
-                    }
-                    FrontmatterParse::Broken { error } => FrontmatterStatus::Broken {
-                        error: error.clone(),
-                    },
-                };
-
-                for (k_raw, v_raw) in &parsed.inline_fields {
-                    let Some(k) = normalize_field_key(k_raw) else {
-                        continue;
-                    };
-                    let v = inline_value_to_field_value(v_raw);
-                    merge_field(&mut fields, k, v);
-                }
+                file.content_hash = content_hash(&content);
+                file_for_note.content_hash = file.content_hash.clone();
+                let parsed = parse_markdown_note(&rel, &content, vault.config());
+                let NoteFields {
+                    fields,
+                    aliases,
+                    frontmatter,
+                    field_conflicts,
+                } = note_fields(&parsed, vault.config().field_merge_policy);
 
                 let tasks = parsed
                     .tasks
@@ -230,6 +393,10 @@ impl VaultIndex {
                         line: t.line,
                         status: t.status,
                         text: t.text,
+                        due: t.due,
+                        done: t.done,
+                        priority: t.priority,
+                        tags: t.tags,
                     })
                     .collect();
 
@@ -238,15 +405,21 @@ impl VaultIndex {
                     title: parsed.title,
                     aliases,
                     tags: parsed.tags.clone(),
+                    frontmatter_tags: parsed.frontmatter_tags,
+                    inline_tags: parsed.inline_tags,
                     links: parsed.links.clone(),
                     link_occurrences: parsed.link_occurrences,
                     frontmatter,
                     fields,
+                    field_conflicts,
                     tasks,
                     schema_violations: Vec::new(),
+                    language: parsed.language,
                 };
+                metrics.parse += t.elapsed();
 
                 if let Some(schema) = &self.schema {
+                    let t = Instant::now();
                     let violations = schema.validate_note(
                         &rel,
                         &note_meta.fields,
@@ -254,6 +427,7 @@ impl VaultIndex {
                         &note_meta.tags,
                     );
                     note_meta.schema_violations = violations;
+                    metrics.schema += t.elapsed();
                 }
                 (parsed.tags, parsed.links, Some(note_meta))
             }
@@ -261,8 +435,11 @@ impl VaultIndex {
         };
 
         if let Some(schema) = &self.schema {
+            let t = Instant::now();
             file.schema_violations = schema.validate_layout_for_path(vault, &rel);
+            metrics.schema += t.elapsed();
         }
+        let t = Instant::now();
         self.files.insert(rel.clone(), file);
 
         let old_tags = self.file_tags.insert(rel.clone(), new_tags.clone());
@@ -279,6 +456,7 @@ impl VaultIndex {
 
         let delta = self.reconcile_tag_index(&rel, old_tags, &new_tags);
         let delta = self.reconcile_link_index(delta, &rel, old_links, &new_links);
+        metrics.reconcile += t.elapsed();
         Ok(delta)
     }
 
@@ -306,6 +484,53 @@ impl VaultIndex {
         }
     }
 
+    /// Moves the entry for `from` to `to` and re-parses it under the new path.
+    ///
+    /// Tag and link reverse indices are re-keyed rather than rebuilt, and an entry
+    /// already at `to` is replaced. The delta lists `from`'s old tags and links as removed
+    /// and `to`'s as added. If `to` is not indexable the entry is simply removed; if it
+    /// cannot be read, neither path stays in the index and the error is returned.
+    pub fn rename_path(
+        &mut self,
+        vault: &Vault,
+        from: &VaultPath,
+        to: VaultPath,
+    ) -> Result<IndexDelta> {
+        if *from == to || !self.files.contains_key(from) {
+            return self.upsert_path(vault, to);
+        }
+        if !vault.is_indexable_rel(to.as_path()) {
+            return Ok(self.remove_path(from));
+        }
+        if self.files.contains_key(&to) {
+            self.remove_path(&to);
+        }
+
+        self.files.remove(from);
+        self.notes.remove(from);
+        let old_tags = self.file_tags.remove(from).unwrap_or_default();
+        let old_links = self.file_links.remove(from).unwrap_or_default();
+        for tag in &old_tags {
+            if let Some(set) = self.tags.get_mut(tag) {
+                set.remove(from);
+                set.insert(to.clone());
+            }
+        }
+        self.file_tags.insert(to.clone(), old_tags.clone());
+        self.file_links.insert(to.clone(), old_links.clone());
+
+        if let Err(err) = self.upsert_path(vault, to.clone()) {
+            self.remove_path(&to);
+            return Err(err);
+        }
+        Ok(IndexDelta {
+            added_tags: self.file_tags.get(&to).cloned().unwrap_or_default(),
+            removed_tags: old_tags,
+            added_links: self.file_links.get(&to).cloned().unwrap_or_default(),
+            removed_links: old_links,
+        })
+    }
+
     pub fn note(&self, path: &VaultPath) -> Option<&NoteMeta> {
         self.notes.get(path)
     }
@@ -318,6 +543,41 @@ impl VaultIndex {
         self.files.values()
     }
 
+    /// [`FileKind::Other`] files grouped by lowercase extension (`""` when there is none),
+    /// each group sorted by path.
+    pub fn other_files_by_extension(&self) -> BTreeMap<String, Vec<VaultPath>> {
+        let mut out: BTreeMap<String, Vec<VaultPath>> = BTreeMap::new();
+        for f in self.files.values() {
+            if f.kind != FileKind::Other {
+                continue;
+            }
+            out.entry(lowercase_extension(f.path.as_path()))
+                .or_default()
+                .push(f.path.clone());
+        }
+        for paths in out.values_mut() {
+            paths.sort();
+        }
+        out
+    }
+
+    /// Indexed file counts per [`FileKind`]; every kind is present, even at zero.
+    pub fn note_count_by_kind(&self) -> BTreeMap<FileKind, usize> {
+        let mut out: BTreeMap<FileKind, usize> = [
+            FileKind::Markdown,
+            FileKind::Canvas,
+            FileKind::Attachment,
+            FileKind::Other,
+        ]
+        .into_iter()
+        .map(|k| (k, 0))
+        .collect();
+        for file in self.files.values() {
+            *out.entry(file.kind).or_default() += 1;
+        }
+        out
+    }
+
     pub fn all_tags(&self) -> impl Iterator<Item = &Tag> {
         self.tags.keys()
     }
@@ -326,10 +586,166 @@ impl VaultIndex {
         self.tags.get(tag).into_iter().flat_map(|s| s.iter())
     }
 
+    /// Files tagged with `tag` or any tag nested under it, in path order.
+    pub fn files_with_tag_prefix(&self, tag: &Tag) -> Vec<&VaultPath> {
+        let files: BTreeSet<&VaultPath> = self
+            .tags
+            .iter()
+            .filter(|(t, _)| t.is_within(tag))
+            .flat_map(|(_, paths)| paths.iter())
+            .collect();
+        files.into_iter().collect()
+    }
+
+    /// Maps each lowercased alias to the notes declaring it, in path order.
+    ///
+    /// An alias listed under more than one note is a resolution conflict.
+    pub fn aliases_index(&self) -> BTreeMap<String, Vec<VaultPath>> {
+        let mut out: BTreeMap<String, Vec<VaultPath>> = BTreeMap::new();
+        for (path, note) in self.notes_iter() {
+            for alias in &note.aliases {
+                out.entry(alias.to_lowercase())
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+        for paths in out.values_mut() {
+            paths.sort();
+            paths.dedup();
+        }
+        out
+    }
+
+    /// Recomputes tag membership from notes and diffs it against the maintained index.
+    pub fn verify_tag_index(&self) -> std::result::Result<(), Vec<TagIndexDiscrepancy>> {
+        let expected = self.tags_from_notes();
+        let mut out = Vec::new();
+        for (tag, paths) in &expected {
+            let actual = self.tags.get(tag);
+            for path in paths {
+                if !actual.is_some_and(|set| set.contains(path)) {
+                    out.push(TagIndexDiscrepancy::Missing {
+                        tag: tag.clone(),
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+        for (tag, paths) in &self.tags {
+            let expected = expected.get(tag);
+            for path in paths {
+                if !expected.is_some_and(|set| set.contains(path)) {
+                    out.push(TagIndexDiscrepancy::Stale {
+                        tag: tag.clone(),
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+
+        if out.is_empty() {
+            Ok(())
+        } else {
+            out.sort();
+            Err(out)
+        }
+    }
+
+    /// Discards the maintained tag index and rebuilds it from the notes' tags.
+    pub fn rebuild_tag_index(&mut self) {
+        self.tags = self.tags_from_notes();
+        self.file_tags = self
+            .notes
+            .iter()
+            .map(|(path, note)| (path.clone(), note.tags.clone()))
+            .collect();
+    }
+
+    fn tags_from_notes(&self) -> HashMap<Tag, BTreeSet<VaultPath>> {
+        let mut out: HashMap<Tag, BTreeSet<VaultPath>> = HashMap::new();
+        for (path, note) in &self.notes {
+            for tag in &note.tags {
+                out.entry(tag.clone()).or_default().insert(path.clone());
+            }
+        }
+        out
+    }
+
     pub fn outgoing_links(&self, from: &VaultPath) -> impl Iterator<Item = &LinkTarget> {
         self.file_links.get(from).into_iter().flat_map(|s| s.iter())
     }
 
+    /// Every external URL in the vault with its referencing locations, most used first.
+    pub fn external_links(&self) -> Vec<crate::ExternalLink> {
+        let mut by_url: BTreeMap<String, Vec<crate::ExternalLinkSource>> = BTreeMap::new();
+        for (path, note) in self.notes_iter() {
+            for l in &note.link_occurrences {
+                if let LinkTarget::ExternalUrl(url) = &l.target {
+                    by_url
+                        .entry(url.clone())
+                        .or_default()
+                        .push(crate::ExternalLinkSource {
+                            source: path.clone(),
+                            location: l.location,
+                        });
+                }
+            }
+        }
+
+        let mut out: Vec<crate::ExternalLink> = by_url
+            .into_iter()
+            .map(|(url, mut sources)| {
+                sources.sort_by(|a, b| {
+                    a.source
+                        .cmp(&b.source)
+                        .then_with(|| a.location.cmp(&b.location))
+                });
+                crate::ExternalLink {
+                    url,
+                    count: sources.len(),
+                    sources,
+                }
+            })
+            .collect();
+        out.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.url.cmp(&b.url)));
+        out
+    }
+
+    /// Counts internal link occurrences by subpath kind, keeping the `top` most
+    /// referenced headings.
+    pub fn subpath_usage(&self, top: usize) -> crate::SubpathUsage {
+        let mut usage = crate::SubpathUsage::default();
+        let mut headings: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+        for (_, note) in self.notes_iter() {
+            for l in &note.link_occurrences {
+                let LinkTarget::Internal { reference } = &l.target else {
+                    continue;
+                };
+                match &l.subpath {
+                    None => usage.whole_note += 1,
+                    Some(crate::Subpath::Heading(h)) => {
+                        usage.heading += 1;
+                        *headings.entry((reference, h)).or_default() += 1;
+                    }
+                    Some(crate::Subpath::Block(_)) => usage.block += 1,
+                }
+            }
+        }
+
+        let mut top_headings: Vec<crate::HeadingUsage> = headings
+            .into_iter()
+            .map(|((target, heading), count)| crate::HeadingUsage {
+                target: target.to_string(),
+                heading: heading.to_string(),
+                count,
+            })
+            .collect();
+        top_headings.sort_by_key(|h| std::cmp::Reverse(h.count));
+        top_headings.truncate(top);
+        usage.top_headings = top_headings;
+        usage
+    }
+
     pub fn query(&self, q: &Query) -> Vec<QueryHit> {
         q.execute(self)
     }
@@ -338,6 +754,10 @@ impl VaultIndex {
         &self.schema_status
     }
 
+    pub(crate) fn schema(&self) -> Option<&Schema> {
+        self.schema.as_ref()
+    }
+
     pub(crate) fn schema_state(&self) -> SchemaState {
         SchemaState {
             status: self.schema_status.clone(),
@@ -345,6 +765,14 @@ impl VaultIndex {
         }
     }
 
+    /// Static lint of the loaded schema; empty when no schema is loaded.
+    pub fn schema_lint(&self, vault: &Vault) -> Vec<SchemaViolation> {
+        self.schema
+            .as_ref()
+            .map(|s| s.lint(vault))
+            .unwrap_or_default()
+    }
+
     pub fn schema_report(&self) -> SchemaReport {
         let mut violations = Vec::new();
         violations.extend(self.schema_vault_violations.clone());
@@ -386,6 +814,20 @@ impl VaultIndex {
                             referenced.insert(target);
                         }
                     }
+                    for key in &schema.vault.attachment_keys {
+                        let Some(value) = crate::fields::normalize_field_key(key)
+                            .and_then(|k| note.fields.get(&k))
+                        else {
+                            continue;
+                        };
+                        for reference in value.strings() {
+                            if let Some(target) =
+                                resolve_field_attachment(&resolver, reference, source)
+                            {
+                                referenced.insert(target);
+                            }
+                        }
+                    }
                 }
 
                 for file in self.files.values() {
@@ -450,6 +892,20 @@ impl VaultIndex {
         q.execute(self)
     }
 
+    /// Runs `q` and buckets the hits by status, keeping each bucket in query order.
+    ///
+    /// Statuses without matching tasks are absent from the map.
+    pub fn query_tasks_by_status(
+        &self,
+        q: &crate::TaskQuery,
+    ) -> BTreeMap<TaskStatus, Vec<crate::TaskHit>> {
+        let mut out: BTreeMap<TaskStatus, Vec<crate::TaskHit>> = BTreeMap::new();
+        for hit in q.execute(self) {
+            out.entry(hit.status).or_default().push(hit);
+        }
+        out
+    }
+
     pub(crate) fn notes_iter_paths(&self) -> impl Iterator<Item = &VaultPath> {
         self.notes.keys()
     }
@@ -479,9 +935,33 @@ impl VaultIndex {
         })
     }
 
+    /// Notes whose frontmatter and inline fields disagree on a key, as `(path, key)` pairs.
+    pub fn field_source_conflicts(&self) -> Vec<(VaultPath, String)> {
+        let mut out: Vec<(VaultPath, String)> = self
+            .notes
+            .iter()
+            .flat_map(|(path, note)| {
+                note.field_conflicts
+                    .iter()
+                    .map(move |k| (path.clone(), k.clone()))
+            })
+            .collect();
+        out.sort();
+        out
+    }
+
     pub fn frontmatter_report(&self) -> FrontmatterReport {
+        self.frontmatter_report_with_prefix("")
+    }
+
+    /// Like [`VaultIndex::frontmatter_report`], counting only notes whose path starts
+    /// with `prefix`.
+    pub fn frontmatter_report_with_prefix(&self, prefix: &str) -> FrontmatterReport {
         let mut r = FrontmatterReport::default();
-        for note in self.notes.values() {
+        for (path, note) in &self.notes {
+            if !path.as_path().to_string_lossy().starts_with(prefix) {
+                continue;
+            }
             match &note.frontmatter {
                 FrontmatterStatus::None => r.none += 1,
                 FrontmatterStatus::Valid => r.valid += 1,
@@ -607,6 +1087,43 @@ impl VaultIndex {
         hits
     }
 
+    /// Files whose relative path starts with `prefix`, in path order (score is always 0).
+    pub fn search_filenames_prefix(&self, prefix: &str, limit: usize) -> Vec<SearchHit> {
+        let q = prefix.trim();
+        if q.is_empty() {
+            return Vec::new();
+        }
+        self.filename_hits(limit, |p| p.as_str_lossy().starts_with(q))
+    }
+
+    /// Files whose full relative path or file stem equals `query`, in path order (score is
+    /// always 0).
+    pub fn search_filenames_exact(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let q = query.trim();
+        if q.is_empty() {
+            return Vec::new();
+        }
+        self.filename_hits(limit, |p| {
+            p.as_str_lossy() == q
+                || p.as_path()
+                    .file_stem()
+                    .is_some_and(|stem| stem.to_string_lossy() == q)
+        })
+    }
+
+    fn filename_hits(&self, limit: usize, pred: impl Fn(&VaultPath) -> bool) -> Vec<SearchHit> {
+        let mut paths: Vec<&VaultPath> = self.files.keys().filter(|p| pred(p)).collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .take(limit)
+            .map(|p| SearchHit {
+                path: p.clone(),
+                score: 0,
+            })
+            .collect()
+    }
+
     /// Fuzzy-search note content by scanning non-empty lines and taking the best match per note.
     ///
     /// This reads note files from disk and can be expensive; prefer calling it from a
@@ -617,23 +1134,62 @@ impl VaultIndex {
         query: &str,
         limit: usize,
     ) -> Result<Vec<ContentSearchHit>> {
-        let q = query.trim();
-        if q.is_empty() || limit == 0 {
+        if limit == 0 {
             return Ok(Vec::new());
         }
 
+        let mut hits: Vec<ContentSearchHit> = Vec::new();
+        self.search_content_fuzzy_each(vault, query, |hit| {
+            hits.push(hit);
+            ControlFlow::Continue(())
+        })?;
+
+        hits.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.path.cmp(&b.path))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Streaming form of [`VaultIndex::search_content_fuzzy`]: calls `on_hit` with each
+    /// file's best line as soon as that file is scanned, in path order and unsorted by
+    /// score. Returning [`ControlFlow::Break`] stops the scan.
+    pub fn search_content_fuzzy_each(
+        &self,
+        vault: &Vault,
+        query: &str,
+        mut on_hit: impl FnMut(ContentSearchHit) -> ControlFlow<()>,
+    ) -> Result<()> {
+        let q = query.trim();
+        if q.is_empty() {
+            return Ok(());
+        }
+
         let pattern = Pattern::parse(q, CaseMatching::Smart, Normalization::Smart);
         let mut matcher = Matcher::new(nucleo::Config::DEFAULT);
         let mut utf32_buf = Vec::new();
-        let mut hits: Vec<ContentSearchHit> = Vec::new();
-
-        for (path, file) in &self.files {
-            if !matches!(file.kind, FileKind::Markdown | FileKind::Canvas) {
-                continue;
-            }
 
+        let text_exts = &vault.config().text_attachment_extensions;
+        let mut files: Vec<(&VaultPath, &FileMeta)> = self.files.iter().collect();
+        files.sort_by(|a, b| a.0.cmp(b.0));
+        for (path, file) in files {
             let abs = vault.to_abs(path);
-            let text = std::fs::read_to_string(&abs).map_err(|e| Error::io(&abs, e))?;
+            let text = if matches!(file.kind, FileKind::Markdown | FileKind::Canvas) {
+                std::fs::read_to_string(&abs).map_err(|e| Error::io(&abs, e))?
+            } else {
+                let ext = lowercase_extension(path.as_path());
+                if !text_exts.iter().any(|e| e.eq_ignore_ascii_case(&ext)) {
+                    continue;
+                }
+                // A configured extension can still hold non-UTF-8 data; skip such files.
+                match std::fs::read_to_string(&abs) {
+                    Ok(text) => text,
+                    Err(_) => continue,
+                }
+            };
             let mut best: Option<(u32, u32, String)> = None;
             for (ix, line) in text.lines().enumerate() {
                 let lt = line.trim();
@@ -654,23 +1210,18 @@ impl VaultIndex {
             }
 
             if let Some((score, line_no, line_text)) = best {
-                hits.push(ContentSearchHit {
+                let hit = ContentSearchHit {
                     path: path.clone(),
                     score,
                     line: line_no,
                     line_text,
-                });
+                };
+                if on_hit(hit).is_break() {
+                    break;
+                }
             }
         }
-
-        hits.sort_by(|a, b| {
-            b.score
-                .cmp(&a.score)
-                .then_with(|| a.path.cmp(&b.path))
-                .then_with(|| a.line.cmp(&b.line))
-        });
-        hits.truncate(limit);
-        Ok(hits)
+        Ok(())
     }
 
     fn reconcile_tag_index(
@@ -729,9 +1280,118 @@ impl VaultIndex {
     }
 }
 
+/// Fields, aliases and frontmatter status derived from a parsed note.
+pub(crate) struct NoteFields {
+    pub fields: FieldMap,
+    pub aliases: BTreeSet<String>,
+    pub frontmatter: FrontmatterStatus,
+    pub field_conflicts: BTreeSet<String>,
+}
+
+/// Merges frontmatter and inline fields according to `policy`.
+pub(crate) fn note_fields(parsed: &ParsedNote, policy: FieldMergePolicy) -> NoteFields {
+    let mut fields = FieldMap::new();
+    let mut aliases = BTreeSet::new();
+    let frontmatter = match &parsed.frontmatter {
+        FrontmatterParse::None => FrontmatterStatus::None,
+        FrontmatterParse::Valid(fm) => {
+            if let Ok(fm_fields) = extract_top_level_frontmatter_fields(fm) {
+                for (k, v) in fm_fields {
+                    merge_field(&mut fields, k, v);
+                }
+            }
+            aliases = extract_frontmatter_aliases(fm);
+            FrontmatterStatus::Valid
+        }
+        FrontmatterParse::Broken { error } => FrontmatterStatus::Broken {
+            error: error.clone(),
+        },
+    };
+
+    let frontmatter_fields = fields.clone();
+    let mut overridden: BTreeSet<String> = BTreeSet::new();
+    let mut field_conflicts = BTreeSet::new();
+    for (k_raw, v_raw) in &parsed.inline_fields {
+        let Some(k) = normalize_field_key(k_raw) else {
+            continue;
+        };
+        let v = inline_value_to_field_value(v_raw);
+        if let Some(fm_value) = frontmatter_fields.get(&k) {
+            if fm_value != &v {
+                field_conflicts.insert(k.clone());
+            }
+            match policy {
+                FieldMergePolicy::Append => {}
+                FieldMergePolicy::FrontmatterWins => continue,
+                FieldMergePolicy::InlineOverrides => {
+                    if overridden.insert(k.clone()) {
+                        fields.insert(k, v);
+                        continue;
+                    }
+                }
+            }
+        }
+        merge_field(&mut fields, k, v);
+    }
+
+    NoteFields {
+        fields,
+        aliases,
+        frontmatter,
+        field_conflicts,
+    }
+}
+
+fn content_hash(content: &str) -> Option<String> {
+    Some(hash_text(content))
+}
+
+/// Hex-encoded SHA-256 of `text`.
+pub(crate) fn hash_text(text: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// Resolves a frontmatter attachment value: either link syntax (`"[[cover.png]]"`) or a bare
+/// file name/path (`cover.png`).
+fn resolve_field_attachment(
+    resolver: &crate::LinkResolver,
+    value: &str,
+    source: &VaultPath,
+) -> Option<VaultPath> {
+    let (_, links) = crate::parse::extract_links_from_line(value, 0);
+    let resolution = match links
+        .iter()
+        .find(|l| matches!(l.target, LinkTarget::Internal { .. }))
+    {
+        Some(link) => resolver.resolve_link_target(&link.target, source),
+        None => {
+            let reference = value.trim();
+            if reference.is_empty() {
+                return None;
+            }
+            resolver.resolve_internal(reference, source)
+        }
+    };
+    match resolution {
+        crate::ResolveResult::Resolved(target) => Some(target),
+        _ => None,
+    }
+}
+
+/// ASCII-lowercased extension of `rel`, or `""` when it has none.
+///
+/// Every extension comparison (file kind, scope kind, scope `extensions`) goes through
+/// this so `NOTE.MD` is treated the same as `note.md`.
+pub(crate) fn lowercase_extension(rel: &Path) -> String {
+    rel.extension()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
 fn file_kind_from_path(vault: &Vault, rel: &Path) -> FileKind {
-    let ext = rel.extension().and_then(|s| s.to_str()).unwrap_or("");
-    let ext = ext.to_lowercase();
+    let ext = lowercase_extension(rel);
 
     if vault
         .config()
@@ -781,9 +1441,13 @@ fn extract_frontmatter_aliases(fm: &serde_yaml::Value) -> BTreeSet<String> {
                 }
             }
             serde_yaml::Value::String(s) => {
-                let s = s.trim();
-                if !s.is_empty() {
-                    out.insert(s.to_lowercase());
+                // A single string may hold several aliases: comma-separated inline,
+                // or one per line in a block scalar (`aliases: |`).
+                for part in s.split([',', '\n']) {
+                    let part = part.trim();
+                    if !part.is_empty() {
+                        out.insert(part.to_lowercase());
+                    }
                 }
             }
             _ => {}
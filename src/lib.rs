@@ -1,15 +1,21 @@
 mod config;
+mod date;
+mod duplicates;
 #[cfg(feature = "similarity")]
 mod embeddings;
 mod error;
+mod field_schema;
 mod fields;
 mod graph;
 mod index;
+#[cfg(feature = "language")]
+mod language;
 mod link_health;
 mod link_resolve;
 mod links;
 mod mentions;
 mod parse;
+mod predicates;
 mod query;
 mod schema;
 mod service;
@@ -21,32 +27,46 @@ mod vault;
 #[cfg(feature = "web-ui")]
 pub mod web_ui;
 
-pub use crate::config::VaultConfig;
+pub use crate::config::{FieldMergePolicy, TagCharset, VaultConfig};
+pub use crate::date::Date;
+pub use crate::duplicates::{DuplicateGroup, MergePlan, RenameSuggestion, TagCluster, TagUsage};
 pub use crate::error::{Error, Result};
+pub use crate::field_schema::{FieldSchemaDraft, FieldValueKind, InferredField};
 pub use crate::fields::{FieldMap, FieldValue};
-pub use crate::graph::{GraphIndex, ResolvedInternalLink};
+pub use crate::graph::{
+    DirectedStats, GraphComponent, GraphExport, GraphExportEdge, GraphExportNode, GraphIndex,
+    NoteCounts, NoteSummary, ResolvedInternalLink,
+};
 pub use crate::index::{
-    ContentSearchHit, FileKind, FileMeta, FrontmatterReport, FrontmatterStatus, IndexDelta,
-    NoteMeta, SearchHit, Tag, Task, TaskStatus, VaultIndex,
+    BuildMetrics, ContentSearchHit, FileKind, FileMeta, FrontmatterReport, FrontmatterStatus,
+    IndexDelta, NoteMeta, SearchHit, Tag, TagIndexDiscrepancy, Task, TaskPriority, TaskStatus,
+    VaultIndex,
 };
 pub use crate::link_resolve::{LinkResolver, ResolveResult};
 pub use crate::links::{
-    Backlink, BacklinksIndex, Link, LinkHealthReport, LinkIssue, LinkIssueKind, LinkIssueReason,
-    LinkKind, LinkLocation, LinkTarget, Subpath,
+    Backlink, BacklinksIndex, ExternalLink, ExternalLinkSource, HeadingUsage, Link,
+    LinkHealthReport, LinkIssue, LinkIssueKind, LinkIssueReason, LinkKind, LinkLocation,
+    LinkTarget, Subpath, SubpathUsage,
+};
+pub use crate::mentions::{LinkCandidate, MentionSuggestion, UnlinkedMention};
+pub use crate::parse::{ParsedNotePublic, parse_note};
+pub use crate::predicates::{PredicateEdge, PredicateEdges, PredicateReport};
+pub use crate::query::{
+    CmpOp, FieldPredicate, Query, QueryHit, SortDir, SortKey, TagSource, TaskHit, TaskQuery,
+    TaskSortKey,
 };
-pub use crate::mentions::UnlinkedMention;
-pub use crate::query::{CmpOp, Query, QueryHit, SortDir, SortKey, TaskHit, TaskQuery};
 pub use crate::schema::{
     InheritKind, LayoutRule, LayoutRuleEntry, PredicateDef, Schema, SchemaReport, SchemaSeverity,
     SchemaSource, SchemaStatus, SchemaViolation, SchemaViolationDetail, SchemaViolationRecord,
     ScopeDef, ScopeKind, ScopeNoteType, ScopeNotes, ScopeRequireAny, ScopeResolution,
-    UnmatchedBehavior, VaultSchema,
+    ScopeTimestamps, TypeNormalization, UnmatchedBehavior, VaultSchema,
 };
 pub use crate::service::{ReindexCause, VaultEvent, VaultService, WatchKind};
 #[cfg(feature = "similarity")]
 pub use crate::similarity::{
-    NoteSimilarityHit, NoteSimilarityReport, SemanticSearchHit, SimilaritySettings,
+    NoteSimilarityHit, NoteSimilarityReport, Reranker, SemanticSearchHit, SimilaritySettings,
+    TokenOverlapReranker, rerank_note_hits,
 };
 #[cfg(feature = "sqlite")]
-pub use crate::sqlite::SqliteIndexStore;
+pub use crate::sqlite::{IndexSyncStats, SqliteIndexStore};
 pub use crate::vault::{Vault, VaultPath};
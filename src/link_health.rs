@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::link_resolve::{ResolveResult, Resolver};
+use crate::parse::CodeBlocks;
 use crate::{
     Error, FileKind, LinkHealthReport, LinkIssue, LinkIssueReason, LinkTarget, Subpath, Vault,
     VaultIndex, VaultPath,
@@ -27,6 +28,7 @@ pub(crate) fn link_health_report(
                 ResolveResult::Missing => {
                     report.broken.push(LinkIssue {
                         source: source_path.clone(),
+                        source_title: note.title.clone(),
                         link: link.clone(),
                         reason: LinkIssueReason::MissingTarget,
                     });
@@ -35,6 +37,7 @@ pub(crate) fn link_health_report(
                 ResolveResult::Ambiguous(candidates) => {
                     report.broken.push(LinkIssue {
                         source: source_path.clone(),
+                        source_title: note.title.clone(),
                         link: link.clone(),
                         reason: LinkIssueReason::AmbiguousTarget { candidates },
                     });
@@ -49,6 +52,7 @@ pub(crate) fn link_health_report(
                     SubpathCheck::MissingHeading(h) => {
                         report.broken.push(LinkIssue {
                             source: source_path.clone(),
+                            source_title: note.title.clone(),
                             link: link.clone(),
                             reason: LinkIssueReason::MissingHeading { heading: h },
                         });
@@ -57,6 +61,7 @@ pub(crate) fn link_health_report(
                     SubpathCheck::MissingBlock(b) => {
                         report.broken.push(LinkIssue {
                             source: source_path.clone(),
+                            source_title: note.title.clone(),
                             link: link.clone(),
                             reason: LinkIssueReason::MissingBlock { block: b },
                         });
@@ -102,7 +107,8 @@ fn validate_subpath(
     if !cache.contains_key(target) {
         let abs = vault.to_abs(target);
         let text = std::fs::read_to_string(&abs).map_err(|e| Error::io(&abs, e))?;
-        let (headings, heading_slugs, blocks) = index_targets(&text);
+        let (headings, heading_slugs, blocks) =
+            index_targets(&text, vault.config().indented_code_blocks);
         cache.insert(
             target.clone(),
             TargetCache {
@@ -141,21 +147,20 @@ fn validate_subpath(
     }
 }
 
-fn index_targets(text: &str) -> (HashSet<String>, HashSet<String>, HashSet<String>) {
+fn index_targets(
+    text: &str,
+    indented_code: bool,
+) -> (HashSet<String>, HashSet<String>, HashSet<String>) {
     let mut headings = HashSet::new();
     let mut heading_slugs = HashSet::new();
     let mut blocks = HashSet::new();
 
-    let mut in_fenced = false;
+    let mut code = CodeBlocks::new(indented_code);
     for line in text.lines() {
-        let t = line.trim_start();
-        if t.starts_with("```") {
-            in_fenced = !in_fenced;
-            continue;
-        }
-        if in_fenced {
+        if code.skip(line) {
             continue;
         }
+        let t = line.trim_start();
 
         if let Some(h) = parse_heading(t) {
             let hl = h.to_lowercase();
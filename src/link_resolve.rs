@@ -1,6 +1,6 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use crate::{FileKind, LinkTarget, VaultIndex, VaultPath};
+use crate::{FileKind, Link, LinkTarget, Subpath, VaultIndex, VaultPath};
 
 #[derive(Debug, Clone)]
 pub(crate) struct Resolver {
@@ -10,7 +10,8 @@ pub(crate) struct Resolver {
     by_filename_lower: HashMap<String, Vec<VaultPath>>,
     by_stem: HashMap<String, Vec<VaultPath>>,
     by_stem_lower: HashMap<String, Vec<VaultPath>>,
-    by_alias: HashMap<String, Vec<VaultPath>>,
+    by_alias: BTreeMap<String, Vec<VaultPath>>,
+    titles: HashMap<VaultPath, String>,
     note_exts: Vec<String>,
 }
 
@@ -35,7 +36,7 @@ impl Resolver {
         let mut by_filename_lower: HashMap<String, Vec<VaultPath>> = HashMap::new();
         let mut by_stem: HashMap<String, Vec<VaultPath>> = HashMap::new();
         let mut by_stem_lower: HashMap<String, Vec<VaultPath>> = HashMap::new();
-        let mut by_alias: HashMap<String, Vec<VaultPath>> = HashMap::new();
+        let mut titles: HashMap<VaultPath, String> = HashMap::new();
         let mut note_exts: BTreeSet<String> = BTreeSet::new();
 
         for f in index.all_files() {
@@ -65,19 +66,15 @@ impl Resolver {
                         .or_default()
                         .push(f.path.clone());
                 }
-                if let Some(ext) = f.path.as_path().extension().and_then(|s| s.to_str()) {
-                    note_exts.insert(ext.to_lowercase());
+                let ext = crate::index::lowercase_extension(f.path.as_path());
+                if !ext.is_empty() {
+                    note_exts.insert(ext);
                 }
             }
         }
 
         for (path, note) in index.notes_iter() {
-            for a in &note.aliases {
-                by_alias
-                    .entry(a.to_lowercase())
-                    .or_default()
-                    .push(path.clone());
-            }
+            titles.insert(path.clone(), note.title.clone());
         }
 
         Self {
@@ -87,7 +84,8 @@ impl Resolver {
             by_filename_lower,
             by_stem,
             by_stem_lower,
-            by_alias,
+            by_alias: index.aliases_index(),
+            titles,
             note_exts: note_exts.into_iter().collect(),
         }
     }
@@ -114,32 +112,19 @@ impl Resolver {
             return ResolveResult::Missing;
         }
 
-        // Path-ish: contains a slash.
+        // Path-ish: contains a slash. Try the vault-relative reading first, then the
+        // source-relative one (`./` and `../` are only ever source-relative).
         if r.contains('/') {
-            if let Some(p) = self.by_rel.get(r) {
-                return ResolveResult::Resolved(p.clone());
-            }
-            if let Some(p) = self.by_rel_lower.get(&r.to_lowercase()) {
-                return ResolveResult::Resolved(p.clone());
-            }
-
-            if !has_extension(r) {
-                let mut candidates = Vec::new();
-                for ext in &self.note_exts {
-                    let cand = format!("{r}.{ext}");
-                    if let Some(p) = self.by_rel.get(&cand) {
-                        candidates.push(p.clone());
-                    } else if let Some(p) = self.by_rel_lower.get(&cand.to_lowercase()) {
-                        candidates.push(p.clone());
-                    }
+            for cand in path_candidates(r, source) {
+                if let Some(res) = self.resolve_rel_path(&cand) {
+                    return res;
                 }
-                return pick(candidates);
             }
-
             return ResolveResult::Missing;
         }
 
-        // If reference includes extension, treat it as a filename.
+        // If reference includes extension, treat it as a filename. A miss falls through:
+        // the "extension" may be part of a dotted stem such as `v1.2`.
         if has_extension(r) {
             if let Some(v) = self.by_filename.get(r) {
                 return pick_prefer_source(v.clone(), source);
@@ -147,7 +132,6 @@ impl Resolver {
             if let Some(v) = self.by_filename_lower.get(&r.to_lowercase()) {
                 return pick_prefer_source(v.clone(), source);
             }
-            return ResolveResult::Missing;
         }
 
         // Otherwise: resolve by note stem or alias.
@@ -177,6 +161,32 @@ impl Resolver {
     }
 }
 
+impl Resolver {
+    fn resolve_rel_path(&self, r: &str) -> Option<ResolveResult> {
+        if let Some(p) = self.by_rel.get(r) {
+            return Some(ResolveResult::Resolved(p.clone()));
+        }
+        if let Some(p) = self.by_rel_lower.get(&r.to_lowercase()) {
+            return Some(ResolveResult::Resolved(p.clone()));
+        }
+
+        // Also tried when `r` looks like it has an extension, for dotted stems.
+        let mut candidates = Vec::new();
+        for ext in &self.note_exts {
+            let cand = format!("{r}.{ext}");
+            if let Some(p) = self.by_rel.get(&cand) {
+                candidates.push(p.clone());
+            } else if let Some(p) = self.by_rel_lower.get(&cand.to_lowercase()) {
+                candidates.push(p.clone());
+            }
+        }
+        if candidates.is_empty() {
+            return None;
+        }
+        Some(pick(candidates))
+    }
+}
+
 impl LinkResolver {
     pub fn new(index: &VaultIndex) -> Self {
         Self {
@@ -191,6 +201,44 @@ impl LinkResolver {
     pub fn resolve_link_target(&self, target: &LinkTarget, source: &VaultPath) -> ResolveResult {
         self.inner.resolve_link_target(target, source)
     }
+
+    /// Text Obsidian would show for `link` written in `source`.
+    ///
+    /// Precedence: the explicit display text, else the resolved note's title, else the
+    /// raw reference. Without explicit display text a heading or block subpath is
+    /// appended as `Title > Section`.
+    pub fn display_for(&self, link: &Link, source: &VaultPath) -> String {
+        if let Some(display) = &link.display {
+            return display.clone();
+        }
+
+        let base = match &link.target {
+            LinkTarget::Internal { reference } => {
+                match self.inner.resolve_internal_with_source(reference, source) {
+                    ResolveResult::Resolved(path) => self
+                        .inner
+                        .titles
+                        .get(&path)
+                        .cloned()
+                        .unwrap_or_else(|| reference.clone()),
+                    ResolveResult::Ambiguous(_) | ResolveResult::Missing => reference.clone(),
+                }
+            }
+            LinkTarget::ExternalUrl(url) => url.clone(),
+            LinkTarget::ObsidianUri { raw } => raw.clone(),
+        };
+
+        let section = match &link.subpath {
+            Some(Subpath::Heading(h)) => h.clone(),
+            Some(Subpath::Block(b)) => format!("^{b}"),
+            None => return base,
+        };
+        if base.is_empty() {
+            section
+        } else {
+            format!("{base} > {section}")
+        }
+    }
 }
 
 fn pick(mut candidates: Vec<VaultPath>) -> ResolveResult {
@@ -264,8 +312,53 @@ fn pick_shortest_or_ambiguous(mut candidates: Vec<VaultPath>) -> ResolveResult {
 }
 
 fn has_extension(path: &str) -> bool {
-    path.rsplit_once('.')
-        .is_some_and(|(_, ext)| !ext.is_empty())
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.rsplit_once('.')
+        .is_some_and(|(stem, ext)| !stem.is_empty() && !ext.is_empty())
+}
+
+/// Vault-relative spellings a path-ish reference may stand for, in lookup order.
+fn path_candidates(r: &str, source: &VaultPath) -> Vec<String> {
+    if let Some(rest) = r.strip_prefix('/') {
+        return vec![rest.to_string()];
+    }
+
+    let source_dir = source
+        .as_path()
+        .parent()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+    let relative = join_relative(&source_dir, r);
+
+    if r.starts_with("./") || r.starts_with("../") {
+        return relative.into_iter().collect();
+    }
+
+    let mut out = vec![r.to_string()];
+    if let Some(rel) = relative
+        && rel != r
+    {
+        out.push(rel);
+    }
+    out
+}
+
+/// Lexically joins `r` onto `base`, folding `.`/`..`; `None` if it climbs out of the vault.
+fn join_relative(base: &str, r: &str) -> Option<String> {
+    let mut parts: Vec<&str> = base.split('/').filter(|s| !s.is_empty()).collect();
+    for seg in r.split('/') {
+        match seg {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            _ => parts.push(seg),
+        }
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    Some(parts.join("/"))
 }
 
 fn percent_decode(s: &str) -> Option<String> {
@@ -73,6 +73,8 @@ pub enum LinkIssueKind {
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct LinkIssue {
     pub source: crate::VaultPath,
+    /// Title of the source note.
+    pub source_title: String,
     pub link: Link,
     pub reason: LinkIssueReason,
 }
@@ -84,6 +86,39 @@ pub struct LinkHealthReport {
     pub broken: Vec<LinkIssue>,
 }
 
+/// A unique external URL (including `mailto:`) and every place it is referenced.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ExternalLink {
+    pub url: String,
+    pub count: usize,
+    pub sources: Vec<ExternalLinkSource>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ExternalLinkSource {
+    pub source: crate::VaultPath,
+    pub location: LinkLocation,
+}
+
+/// Tally of how internal link occurrences address their targets.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct SubpathUsage {
+    /// Occurrences without a subpath.
+    pub whole_note: usize,
+    pub heading: usize,
+    pub block: usize,
+    /// Most referenced `(target, heading)` pairs, most used first.
+    pub top_headings: Vec<HeadingUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct HeadingUsage {
+    /// Link reference as written, without the subpath.
+    pub target: String,
+    pub heading: String,
+    pub count: usize,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct Backlink {
     pub source: crate::VaultPath,
@@ -105,7 +140,33 @@ impl BacklinksIndex {
             .unwrap_or(&[])
     }
 
+    /// Linked-to paths in unspecified order; see [`Self::targets_sorted`].
     pub fn targets(&self) -> impl Iterator<Item = &crate::VaultPath> {
         self.inbound.keys()
     }
+
+    /// Linked-to paths sorted by path, for output that must be stable across runs.
+    pub fn targets_sorted(&self) -> Vec<&crate::VaultPath> {
+        let mut targets: Vec<_> = self.inbound.keys().collect();
+        targets.sort();
+        targets
+    }
+
+    /// Markdown notes that no other note resolves a link to, sorted by path.
+    ///
+    /// Self-links do not count; whether the note links out is irrelevant.
+    pub fn unreferenced(&self, index: &crate::VaultIndex) -> Vec<crate::VaultPath> {
+        let mut out: Vec<crate::VaultPath> = index
+            .notes_iter_paths()
+            .filter(|p| {
+                index
+                    .file(p)
+                    .is_some_and(|f| f.kind == crate::FileKind::Markdown)
+            })
+            .filter(|p| self.backlinks(p).iter().all(|b| &b.source == *p))
+            .cloned()
+            .collect();
+        out.sort();
+        out
+    }
 }
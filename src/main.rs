@@ -1,21 +1,20 @@
 use std::collections::BTreeMap;
 use std::fs;
+use std::io::Write;
 #[cfg(feature = "web-ui")]
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 #[cfg(feature = "web-ui")]
 use std::sync::Once;
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use oxidian::{
-    FileKind, InheritKind, LayoutRule, LayoutRuleEntry, Link, LinkIssueKind, LinkIssueReason,
-    LinkKind, PredicateDef, Query, Schema, SchemaSeverity, ScopeDef, SortDir, Tag, TaskQuery,
-    TaskStatus, UnmatchedBehavior, Vault, VaultPath, VaultSchema, VaultService,
+    FieldPredicate, FileKind, InheritKind, LayoutRule, LayoutRuleEntry, Link, LinkIssueKind,
+    LinkIssueReason, LinkKind, PredicateDef, Query, Schema, SchemaSeverity, ScopeDef, SortDir, Tag,
+    TagSource, TaskPriority, TaskQuery, TaskSortKey, TaskStatus, UnmatchedBehavior, Vault,
+    VaultConfig, VaultPath, VaultSchema, VaultService,
 };
 
-#[cfg(feature = "similarity")]
-use oxidian::VaultConfig;
-
 // ---------------------------------------------------------------------------
 // Output helpers
 // ---------------------------------------------------------------------------
@@ -24,6 +23,10 @@ use oxidian::VaultConfig;
 enum OutputFormat {
     Text,
     Json,
+    Csv,
+    /// One JSON object per line, flushed as written. `tasks`, `search` and `check links`
+    /// print one line per hit; other commands print their JSON envelope as a single line.
+    Ndjson,
 }
 
 /// Unified envelope for JSON output.
@@ -54,6 +57,22 @@ fn emit_json<T: serde::Serialize>(data: &T) {
     );
 }
 
+/// Prints `item` as one NDJSON line and flushes, so a piped reader sees it immediately.
+fn emit_ndjson_line<T: serde::Serialize>(item: &T) -> anyhow::Result<()> {
+    let mut out = std::io::stdout().lock();
+    serde_json::to_writer(&mut out, item)?;
+    out.write_all(b"\n")?;
+    out.flush()?;
+    Ok(())
+}
+
+fn emit_ndjson<T: serde::Serialize>(items: impl IntoIterator<Item = T>) -> anyhow::Result<()> {
+    for item in items {
+        emit_ndjson_line(&item)?;
+    }
+    Ok(())
+}
+
 fn emit_json_error(code: &str, message: &str) {
     let envelope = JsonEnvelope::<()> {
         ok: false,
@@ -69,6 +88,91 @@ fn emit_json_error(code: &str, message: &str) {
     );
 }
 
+/// Columns of `-o csv` task rows, including fields JSON output omits when unset.
+const TASK_CSV_HEADER: &[&str] = &[
+    "path", "line", "status", "text", "due", "done", "priority", "tags",
+];
+
+/// Columns of `-o csv` link occurrences: every flattened target, subpath and resolution
+/// variant gets its own column.
+const LINK_CSV_HEADER: &[&str] = &[
+    "kind",
+    "embed",
+    "display",
+    "target.internal.reference",
+    "target.external_url",
+    "target.obsidian_uri.raw",
+    "subpath.heading",
+    "subpath.block",
+    "location.line",
+    "location.column",
+    "raw",
+    "resolution",
+    "resolution.resolved",
+    "resolution.ambiguous",
+];
+
+/// Prints `rows` as CSV under a fixed `header`, so every row (and an empty result) has
+/// the same columns. Cells come from the JSON form: nested objects become dotted
+/// columns, arrays stay JSON-encoded in one cell, and absent fields are left empty.
+fn emit_csv<T: serde::Serialize>(header: &[&str], rows: impl IntoIterator<Item = T>) {
+    let line = |cells: Vec<&str>| {
+        cells
+            .into_iter()
+            .map(csv_field)
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    println!("{}", line(header.to_vec()));
+    for row in rows {
+        let mut cells = Vec::new();
+        let value = serde_json::to_value(&row).expect("json serialization");
+        flatten_csv_cells(String::new(), value, &mut cells);
+        let cells = header
+            .iter()
+            .map(|h| {
+                cells
+                    .iter()
+                    .find(|(k, _)| k == h)
+                    .map_or("", |(_, v)| v.as_str())
+            })
+            .collect();
+        println!("{}", line(cells));
+    }
+}
+
+fn flatten_csv_cells(key: String, value: serde_json::Value, out: &mut Vec<(String, String)>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (k, v) in map {
+                let key = if key.is_empty() {
+                    k
+                } else {
+                    format!("{key}.{k}")
+                };
+                flatten_csv_cells(key, v, out);
+            }
+        }
+        value => {
+            let key = if key.is_empty() { "value".into() } else { key };
+            let cell = match value {
+                serde_json::Value::Null => String::new(),
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            };
+            out.push((key, cell));
+        }
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
 fn format_schema_status(status: &oxidian::SchemaStatus) -> String {
     match status {
         oxidian::SchemaStatus::Disabled => "disabled".to_string(),
@@ -77,6 +181,26 @@ fn format_schema_status(status: &oxidian::SchemaStatus) -> String {
     }
 }
 
+/// Schema errors, counting an unloadable schema as one.
+fn schema_error_count(report: &oxidian::SchemaReport) -> usize {
+    report.errors + usize::from(matches!(report.status, oxidian::SchemaStatus::Error { .. }))
+}
+
+/// Whether counts reach the `--fail-on` threshold; without the flag nothing fails.
+fn fails(fail_on: Option<SchemaSeverityArg>, errors: usize, warnings: usize) -> bool {
+    match fail_on.map(SchemaSeverity::from) {
+        Some(SchemaSeverity::Error) => errors > 0,
+        Some(SchemaSeverity::Warn) => errors + warnings > 0,
+        None => false,
+    }
+}
+
+/// Flushes the report already printed and exits with status 1.
+fn exit_failed() -> anyhow::Result<()> {
+    std::io::stdout().flush()?;
+    std::process::exit(1);
+}
+
 /// Emit a progress message to stderr, unless `--quiet` is set.
 fn progress(quiet: bool, msg: &str) {
     if !quiet {
@@ -128,6 +252,105 @@ impl From<StatusArg> for TaskStatus {
     }
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum TaskSortArg {
+    Path,
+    Line,
+    Status,
+    Due,
+    Done,
+    Priority,
+}
+
+impl From<TaskSortArg> for TaskSortKey {
+    fn from(value: TaskSortArg) -> Self {
+        match value {
+            TaskSortArg::Path => TaskSortKey::Path,
+            TaskSortArg::Line => TaskSortKey::Line,
+            TaskSortArg::Status => TaskSortKey::Status,
+            TaskSortArg::Due => TaskSortKey::Due,
+            TaskSortArg::Done => TaskSortKey::Done,
+            TaskSortArg::Priority => TaskSortKey::Priority,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum PriorityArg {
+    Highest,
+    High,
+    Medium,
+    None,
+    Low,
+    Lowest,
+}
+
+impl From<PriorityArg> for TaskPriority {
+    fn from(value: PriorityArg) -> Self {
+        match value {
+            PriorityArg::Highest => TaskPriority::Highest,
+            PriorityArg::High => TaskPriority::High,
+            PriorityArg::Medium => TaskPriority::Medium,
+            PriorityArg::None => TaskPriority::None,
+            PriorityArg::Low => TaskPriority::Low,
+            PriorityArg::Lowest => TaskPriority::Lowest,
+        }
+    }
+}
+
+/// A cutoff date given either as `YYYY-MM-DD` or as a number of days before today.
+#[derive(Debug, Clone, Copy)]
+struct SinceArg(oxidian::Date);
+
+impl std::str::FromStr for SinceArg {
+    type Err = oxidian::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().parse::<u32>() {
+            Ok(days) => Ok(Self(oxidian::Date::today().add_days(-i64::from(days)))),
+            Err(_) => s.parse().map(Self),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum TaskGroupArg {
+    Status,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum NoteSortArg {
+    Path,
+    Title,
+    Tags,
+    Links,
+    Backlinks,
+    Tasks,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum TagSourceArg {
+    Any,
+    Frontmatter,
+    Inline,
+}
+
+impl From<TagSourceArg> for TagSource {
+    fn from(value: TagSourceArg) -> Self {
+        match value {
+            TagSourceArg::Any => TagSource::Any,
+            TagSourceArg::Frontmatter => TagSource::Frontmatter,
+            TagSourceArg::Inline => TagSource::Inline,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum RerankArg {
+    /// Jaccard overlap of word tokens.
+    TokenOverlap,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum SchemaSeverityArg {
     Warn,
@@ -143,6 +366,12 @@ impl From<SchemaSeverityArg> for SchemaSeverity {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum HealthSignalArg {
+    Schema,
+    Links,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum SearchMode {
     Files,
@@ -150,6 +379,13 @@ enum SearchMode {
     Semantic,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum FileMatchArg {
+    Fuzzy,
+    Prefix,
+    Exact,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum SchemaTemplate {
     Para,
@@ -184,93 +420,31 @@ struct Cli {
 enum Command {
     // ── Querying / Reading ──────────────────────────────────
     /// Search notes by filename, content, or embeddings.
-    Search {
-        /// Query string.
-        query: String,
-
-        /// Search mode.
-        #[arg(long, value_enum, default_value = "files")]
-        mode: SearchMode,
-
-        /// Maximum number of results.
-        #[arg(long, default_value_t = 20)]
-        limit: usize,
-
-        /// Minimum similarity score (semantic mode only).
-        #[arg(long)]
-        min_score: Option<f32>,
-    },
+    Search(SearchArgs),
 
     /// Dataview-like querying of notes.
-    Query {
-        /// Limit results to paths with this prefix.
-        #[arg(long)]
-        prefix: Option<String>,
-
-        /// Limit results to notes with this tag.
-        #[arg(long)]
-        tag: Option<String>,
-
-        /// Require that a field exists (repeatable).
-        #[arg(long)]
-        exists: Vec<String>,
-
-        /// Field equals (repeatable): key=value.
-        #[arg(long)]
-        eq: Vec<String>,
-
-        /// Field contains substring (repeatable): key=value.
-        #[arg(long)]
-        contains: Vec<String>,
-
-        /// Field numeric greater-than (repeatable): key=value.
-        #[arg(long)]
-        gt: Vec<String>,
-
-        /// Sort by field name.
-        #[arg(long)]
-        sort: Option<String>,
-
-        /// Sort descending.
-        #[arg(long)]
-        desc: bool,
-
-        /// Maximum number of results.
-        #[arg(long, default_value_t = 50)]
-        limit: usize,
-    },
+    Query(Box<QueryArgs>),
 
     /// List tags with file counts.
     Tags {
         /// How many tags to print.
         #[arg(long, default_value_t = 50)]
         top: usize,
-    },
 
-    /// List indexed tasks.
-    Tasks {
-        /// Optional path prefix.
+        /// Cluster near-duplicate tags (e.g. #m-l and #ml) and suggest a canonical tag for each.
         #[arg(long)]
-        prefix: Option<String>,
-
-        /// Filter by status.
-        #[arg(long, value_enum)]
-        status: Option<StatusArg>,
-
-        /// Filter by substring on task text.
-        #[arg(long)]
-        contains: Option<String>,
-
-        /// Maximum number of tasks to print.
-        #[arg(long, default_value_t = 100)]
-        limit: usize,
+        rename_preview: bool,
     },
 
+    /// List indexed tasks.
+    Tasks(TaskArgs),
+
     // ── Per-note inspection ─────────────────────────────────
     /// Show outgoing links for a note.
     Links {
         /// Note path (relative to vault).
-        note: PathBuf,
+        #[arg(required_unless_present_any = ["external", "subpath_usage", "orphaned_targets"])]
+        note: Option<PathBuf>,
 
         /// Filter by link kind.
         #[arg(long, value_enum)]
@@ -279,12 +453,40 @@ enum Command {
         /// Only show embed links (e.g. ![[..]] or ![](..)).
         #[arg(long)]
         only_embeds: bool,
+
+        /// Annotate each internal occurrence with its resolution (resolved/missing/ambiguous).
+        #[arg(long)]
+        resolve: bool,
+
+        /// List every external URL in the vault with its sources, most used first.
+        #[arg(long, conflicts_with_all = ["note", "kind", "only_embeds", "resolve"])]
+        external: bool,
+
+        /// Tally internal occurrences by heading, block or whole-note target across the vault.
+        #[arg(long, conflicts_with_all = ["note", "kind", "only_embeds", "resolve", "external"])]
+        subpath_usage: bool,
+
+        /// List Markdown notes that no other note links to.
+        #[arg(long, conflicts_with_all = ["note", "kind", "only_embeds", "resolve", "external", "subpath_usage"])]
+        orphaned_targets: bool,
+
+        /// With --orphaned-targets, skip notes carrying this tag, e.g. MOCs (repeatable).
+        #[arg(long, requires = "orphaned_targets")]
+        exclude_tag: Vec<String>,
     },
 
     /// Show inbound links (backlinks) to a note.
     Backlinks {
-        /// Target note path or name.
+        /// Target note path, name, or alias.
         note: String,
+
+        /// Also list plain-text (unlinked) mentions of the target.
+        #[arg(long)]
+        include_mentions: bool,
+
+        /// List every ambiguous internal link occurrence with its candidates.
+        #[arg(long)]
+        show_ambiguous: bool,
     },
 
     /// Find plain-text (unlinked) mentions of a note.
@@ -295,6 +497,10 @@ enum Command {
         /// Maximum number of results.
         #[arg(long, default_value_t = 100)]
         limit: usize,
+
+        /// Print each mention's column span and `[[...]]` replacement (dry run).
+        #[arg(long)]
+        write_suggestions: bool,
     },
 
     /// Find similar notes (embedding neighbors).
@@ -309,6 +515,10 @@ enum Command {
         /// Maximum neighbors.
         #[arg(long)]
         top_k: Option<usize>,
+
+        /// Rescore the KNN candidates with a second-stage method.
+        #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "token-overlap")]
+        rerank: Option<RerankArg>,
     },
 
     // ── Vault-wide inspection ───────────────────────────────
@@ -317,6 +527,18 @@ enum Command {
         /// Optional tag to query for matching files.
         #[arg(long)]
         tag: Option<String>,
+
+        /// Like --tag, but also matches files under nested tags.
+        #[arg(long, conflicts_with = "tag")]
+        tag_prefix: Option<String>,
+
+        /// List files of unrecognized kind, grouped by extension.
+        #[arg(long)]
+        other: bool,
+
+        /// Report how long each index build phase took.
+        #[arg(long)]
+        metrics: bool,
     },
 
     /// Graph summary and outgoing links.
@@ -324,6 +546,48 @@ enum Command {
         /// Source note path to show outgoing internal links.
         #[arg(long)]
         note: Option<PathBuf>,
+
+        /// List connected components of linked notes, largest first.
+        #[arg(long)]
+        isolated_components: bool,
+
+        /// List notes that are linked to but link nowhere themselves.
+        #[arg(long)]
+        dead_ends: bool,
+
+        /// Show reciprocal link pairs and the in/out-degree distribution.
+        #[arg(long)]
+        directed_stats: bool,
+
+        /// Export the plain node/edge graph instead of the summary.
+        #[arg(long, conflicts_with_all = ["note", "isolated_components", "dead_ends", "directed_stats"])]
+        export: bool,
+
+        /// Print the graph as GEXF (for Gephi) instead of the summary; ignores `-o`.
+        #[arg(long, conflicts_with_all = ["note", "isolated_components", "dead_ends", "directed_stats", "export"])]
+        export_gexf: bool,
+    },
+
+    /// List schema predicate fields (e.g. `depends_on:: [[X]]`) as resolved edges.
+    Predicates {
+        /// Only show edges whose target is missing or ambiguous.
+        #[arg(long)]
+        unresolved: bool,
+    },
+
+    /// One row per note with tag, link, backlink and task counts.
+    Notes {
+        /// Sort key (default: path).
+        #[arg(long, value_enum)]
+        sort: Option<NoteSortArg>,
+
+        /// Sort descending.
+        #[arg(long, requires = "sort")]
+        desc: bool,
+
+        /// List notes sharing tags with this note that it does not link to yet.
+        #[arg(long, value_name = "NOTE", conflicts_with = "sort")]
+        no_links_from: Option<PathBuf>,
     },
 
     // ── Auditing / Linting ──────────────────────────────────
@@ -335,13 +599,21 @@ enum Command {
 
     // ── Infrastructure ──────────────────────────────────────
     /// Stream vault change events.
-    Watch,
+    Watch {
+        /// Rewrite this file with index stats (JSON) after every applied change batch.
+        #[arg(long)]
+        snapshot_path: Option<PathBuf>,
+    },
 
     /// Persist the index to SQLite and incrementally update.
     Persist {
         /// Optional SQLite DB path.
         #[arg(long)]
         db: Option<PathBuf>,
+
+        /// Rewrite every row instead of syncing only files whose mtime/size changed.
+        #[arg(long)]
+        full: bool,
     },
 
     /// Schema utilities.
@@ -360,6 +632,206 @@ enum Command {
     },
 }
 
+#[derive(Debug, Args)]
+struct SearchArgs {
+    /// Query string.
+    query: String,
+
+    /// Search mode.
+    #[arg(long, value_enum, default_value = "files")]
+    mode: SearchMode,
+
+    /// How files mode matches paths: fuzzy, path prefix, or exact path/stem.
+    #[arg(long = "match", value_enum, default_value = "fuzzy")]
+    file_match: FileMatchArg,
+
+    /// Maximum number of results.
+    #[arg(long, default_value_t = 20)]
+    limit: usize,
+
+    /// Minimum similarity score (semantic mode only).
+    #[arg(long)]
+    min_score: Option<f32>,
+}
+
+#[derive(Debug, Args)]
+struct QueryArgs {
+    /// Limit results to paths with this prefix.
+    #[arg(long)]
+    prefix: Option<String>,
+
+    /// Limit results to notes with this tag.
+    #[arg(long)]
+    tag: Option<String>,
+
+    /// Limit results to notes with this tag or any tag nested under it.
+    #[arg(long, conflicts_with = "tag")]
+    tag_prefix: Option<String>,
+
+    /// Where --tag must appear: frontmatter, inline body, or either.
+    #[arg(long, value_enum, requires = "tag", default_value = "any")]
+    tag_source: TagSourceArg,
+
+    /// Drop notes carrying this tag (repeatable).
+    #[arg(long)]
+    exclude_tag: Vec<String>,
+
+    /// Require that a field exists (repeatable).
+    #[arg(long)]
+    exists: Vec<String>,
+
+    /// Require that a field is absent; an empty value counts as present (repeatable).
+    #[arg(long)]
+    not_exists: Vec<String>,
+
+    /// Require at least one of these fields to exist (repeatable).
+    #[arg(long)]
+    exists_any: Vec<String>,
+
+    /// Require all of these fields to be missing (repeatable).
+    #[arg(long)]
+    missing: Vec<String>,
+
+    /// Field equals (repeatable): key=value.
+    #[arg(long)]
+    eq: Vec<String>,
+
+    /// Field does not equal, or is missing (repeatable): key=value.
+    #[arg(long)]
+    ne: Vec<String>,
+
+    /// Field contains substring (repeatable): key=value.
+    #[arg(long)]
+    contains: Vec<String>,
+
+    /// Field equals, OR'd together into one group (repeatable): key=value.
+    #[arg(long = "or")]
+    or: Vec<String>,
+
+    /// Field numeric greater-than (repeatable): key=value.
+    #[arg(long)]
+    gt: Vec<String>,
+
+    /// Field numeric greater-than-or-equal (repeatable): key=value.
+    #[arg(long)]
+    gte: Vec<String>,
+
+    /// Field numeric less-than (repeatable): key=value.
+    #[arg(long)]
+    lt: Vec<String>,
+
+    /// Field numeric less-than-or-equal (repeatable): key=value.
+    #[arg(long)]
+    lte: Vec<String>,
+
+    /// Only notes with at least this many tags.
+    #[arg(long)]
+    min_tags: Option<usize>,
+
+    /// Only notes with at most this many tags.
+    #[arg(long)]
+    max_tags: Option<usize>,
+
+    /// Only notes detected as this language (ISO 639-1, e.g. `en`); needs the `language` feature.
+    #[arg(long)]
+    lang: Option<String>,
+
+    /// Sort by field name.
+    #[arg(long)]
+    sort: Option<String>,
+
+    /// Sort by file modification time.
+    #[arg(long, conflicts_with_all = ["sort", "sort_size"])]
+    sort_mtime: bool,
+
+    /// Sort by file size.
+    #[arg(long, conflicts_with = "sort")]
+    sort_size: bool,
+
+    /// Sort descending.
+    #[arg(long)]
+    desc: bool,
+
+    /// Maximum number of results.
+    #[arg(long, default_value_t = 50)]
+    limit: usize,
+
+    /// Print only the number of matches (ignores --limit).
+    #[arg(long)]
+    count: bool,
+}
+
+#[derive(Debug, Args)]
+struct TaskArgs {
+    /// Optional path prefix.
+    #[arg(long, conflicts_with = "path")]
+    prefix: Option<String>,
+
+    /// Only list tasks of this exact note (relative to vault).
+    #[arg(long)]
+    path: Option<PathBuf>,
+
+    /// Filter by status.
+    #[arg(long, value_enum)]
+    status: Option<StatusArg>,
+
+    /// Exclude tasks with this status (repeatable).
+    #[arg(long, value_enum)]
+    exclude_status: Vec<StatusArg>,
+
+    /// Filter by substring on task text.
+    #[arg(long)]
+    contains: Option<String>,
+
+    /// Filter by regex on task text (combined with --contains).
+    #[arg(long)]
+    contains_regex: Option<String>,
+
+    /// Only open tasks due between today and today+N days; sorts by due date.
+    #[arg(long, value_name = "DAYS")]
+    due_within: Option<u32>,
+
+    /// Only tasks due strictly before this date (YYYY-MM-DD).
+    #[arg(long, value_name = "DATE")]
+    due_before: Option<oxidian::Date>,
+
+    /// Only tasks due strictly after this date (YYYY-MM-DD).
+    #[arg(long, value_name = "DATE")]
+    due_after: Option<oxidian::Date>,
+
+    /// Only tasks at this priority or above (unmarked tasks rank as none, below medium).
+    #[arg(long, value_enum)]
+    min_priority: Option<PriorityArg>,
+
+    /// Only done tasks completed (✅ date) on or after this date or N days ago; newest first.
+    #[arg(long, value_name = "DAYS|DATE")]
+    completed_since: Option<SinceArg>,
+
+    /// Only tasks whose own line carries this tag.
+    #[arg(long)]
+    tag: Option<String>,
+
+    /// Sort key (default: path, then line).
+    #[arg(long, value_enum)]
+    sort: Option<TaskSortArg>,
+
+    /// Sort descending.
+    #[arg(long, requires = "sort")]
+    desc: bool,
+
+    /// Maximum number of tasks to print.
+    #[arg(long, default_value_t = 100)]
+    limit: usize,
+
+    /// Print only the number of matching tasks (ignores --limit).
+    #[arg(long)]
+    count: bool,
+
+    /// Bucket matching tasks; JSON output becomes an object keyed by status.
+    #[arg(long, value_enum, conflicts_with = "count")]
+    group_by: Option<TaskGroupArg>,
+}
+
 #[derive(Debug, Subcommand)]
 enum CheckCommand {
     /// Audit internal links for missing/ambiguous targets.
@@ -375,12 +847,28 @@ enum CheckCommand {
         /// Exclude issues with these reasons (repeatable).
         #[arg(long, conflicts_with = "reason")]
         exclude_reason: Vec<LinkIssueKind>,
+
+        /// Exit with status 1 when any selected broken link remains (links count as errors).
+        #[arg(long, value_enum)]
+        fail_on: Option<SchemaSeverityArg>,
     },
     /// Audit frontmatter across the vault.
     Frontmatter {
+        /// Only audit notes whose path starts with this prefix.
+        #[arg(long, conflicts_with = "field_report")]
+        prefix: Option<String>,
+
         /// Maximum number of issues to print.
         #[arg(long, default_value_t = 100)]
         limit: usize,
+
+        /// Also list keys set in both frontmatter and inline fields with differing values.
+        #[arg(long)]
+        field_conflicts: bool,
+
+        /// Print a commented report of field kinds and values inferred from notes instead.
+        #[arg(long, conflicts_with = "field_conflicts")]
+        field_report: bool,
     },
     /// Validate vault against its schema.
     Schema {
@@ -388,9 +876,46 @@ enum CheckCommand {
         #[arg(long, value_enum)]
         severity: Option<SchemaSeverityArg>,
 
+        /// Only show violations with this code (repeatable).
+        #[arg(long = "only-code")]
+        only_codes: Vec<String>,
+
+        /// Hide violations with this code (repeatable).
+        #[arg(long = "exclude-code")]
+        exclude_codes: Vec<String>,
+
         /// Maximum number of violations to print.
         #[arg(long, default_value_t = 100)]
         limit: usize,
+
+        /// Exit with status 1 when the schema has errors (or, with `warn`, warnings too).
+        #[arg(long, value_enum)]
+        fail_on: Option<SchemaSeverityArg>,
+    },
+    /// Summarize schema violations and broken links; exits non-zero past --fail-on.
+    Health {
+        /// Exit with status 1 when a counted signal reaches this severity.
+        #[arg(long, value_enum)]
+        fail_on: Option<SchemaSeverityArg>,
+
+        /// Signals counted towards --fail-on (repeatable; default: all). Broken links
+        /// and an unloadable schema count as errors.
+        #[arg(long, value_enum)]
+        signal: Vec<HealthSignalArg>,
+    },
+    /// Find notes that share a basename (ambiguous `[[name]]` links).
+    Duplicates {
+        /// Suggest disambiguated names and list inbound links each rename would touch.
+        #[arg(long)]
+        merge_plan: bool,
+
+        /// Group notes with byte-identical content instead of shared basenames.
+        #[arg(long, conflicts_with = "merge_plan")]
+        exact: bool,
+
+        /// Maximum number of duplicate groups to print.
+        #[arg(long, default_value_t = 100)]
+        limit: usize,
     },
     /// Full similarity report across the vault.
     Similarity {
@@ -401,6 +926,10 @@ enum CheckCommand {
         /// Maximum neighbors per note.
         #[arg(long)]
         top_k: Option<usize>,
+
+        /// Only report pairs whose notes share at least this many tags.
+        #[arg(long, default_value_t = 0)]
+        min_shared_tags: usize,
     },
 }
 
@@ -412,25 +941,90 @@ enum SchemaCommand {
         #[arg(long, value_enum, default_value = "para")]
         template: SchemaTemplate,
 
+        /// Scaffold one scope per top-level vault folder instead of using a template.
+        #[arg(long, conflicts_with = "template")]
+        from_vault: bool,
+
         /// Overwrite existing schema file.
         #[arg(long)]
         force: bool,
     },
+    /// Statically check the schema for overlapping scopes and rules that never match.
+    Lint,
 }
 
 // ---------------------------------------------------------------------------
 // JSON-serializable output structs
 // ---------------------------------------------------------------------------
 
+#[derive(serde::Serialize)]
+struct CountOutput {
+    count: usize,
+}
+
 #[derive(serde::Serialize)]
 struct StatsOutput {
     files: usize,
     notes: usize,
     tags: usize,
+    by_kind: BTreeMap<FileKind, usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tag_filter: Option<String>,
+    /// `tag_filter` also matched nested tags.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    tag_prefix: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tagged_files: Option<Vec<TaggedFileOutput>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    other_files: Option<BTreeMap<String, OtherFilesOutput>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metrics: Option<BuildMetricsOutput>,
+}
+
+/// Build phase timings in milliseconds.
+#[derive(serde::Serialize)]
+struct BuildMetricsOutput {
+    files: usize,
+    walk_ms: f64,
+    parse_ms: f64,
+    schema_ms: f64,
+    reconcile_ms: f64,
+    total_ms: f64,
+}
+
+impl From<oxidian::BuildMetrics> for BuildMetricsOutput {
+    fn from(m: oxidian::BuildMetrics) -> Self {
+        let ms = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+        Self {
+            files: m.files,
+            walk_ms: ms(m.walk),
+            parse_ms: ms(m.parse),
+            schema_ms: ms(m.schema),
+            reconcile_ms: ms(m.reconcile),
+            total_ms: ms(m.total()),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct OtherFilesOutput {
+    count: usize,
+    paths: Vec<VaultPath>,
+}
+
+#[derive(serde::Serialize)]
+struct TaggedFileOutput {
+    path: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    tagged_files: Option<Vec<String>>,
+    title: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct WatchSnapshot {
+    files: usize,
+    notes: usize,
+    tags: usize,
+    tasks: usize,
 }
 
 #[derive(serde::Serialize)]
@@ -444,7 +1038,33 @@ struct LinksOutput {
     note: String,
     unique_targets: usize,
     occurrences: usize,
-    links: Vec<oxidian::Link>,
+    links: Vec<LinkOccurrenceOutput>,
+}
+
+#[derive(serde::Serialize)]
+struct LinkOccurrenceOutput {
+    #[serde(flatten)]
+    link: oxidian::Link,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolution: Option<oxidian::ResolveResult>,
+}
+
+#[derive(serde::Serialize)]
+struct ExternalLinksOutput {
+    unique_urls: usize,
+    occurrences: usize,
+    urls: Vec<oxidian::ExternalLink>,
+}
+
+#[derive(serde::Serialize)]
+struct OrphanedTargetsOutput {
+    count: usize,
+    notes: Vec<OrphanedTargetOutput>,
+}
+
+#[derive(serde::Serialize)]
+struct OrphanedTargetOutput {
+    path: VaultPath,
 }
 
 #[derive(serde::Serialize)]
@@ -454,6 +1074,10 @@ struct BacklinksOutput {
     backlinks: Vec<oxidian::Backlink>,
     unresolved_internal_occurrences: usize,
     ambiguous_internal_occurrences: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mentions: Option<Vec<oxidian::UnlinkedMention>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ambiguous: Option<Vec<oxidian::ResolvedInternalLink>>,
 }
 
 #[derive(serde::Serialize)]
@@ -462,6 +1086,12 @@ struct MentionsOutput {
     mentions: Vec<oxidian::UnlinkedMention>,
 }
 
+#[derive(serde::Serialize)]
+struct MentionSuggestionsOutput {
+    count: usize,
+    suggestions: Vec<oxidian::MentionSuggestion>,
+}
+
 #[derive(serde::Serialize)]
 struct GraphOutput {
     unresolved_internal_occurrences: usize,
@@ -471,6 +1101,12 @@ struct GraphOutput {
     source: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     outgoing: Option<Vec<oxidian::ResolvedInternalLink>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    components: Option<Vec<oxidian::GraphComponent>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dead_ends: Option<Vec<VaultPath>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    directed_stats: Option<oxidian::DirectedStats>,
 }
 
 #[derive(serde::Serialize)]
@@ -488,6 +1124,14 @@ struct FrontmatterOutput {
     notes_with_frontmatter_broken: usize,
     missing: Vec<String>,
     broken: Vec<FrontmatterBroken>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field_conflicts: Option<Vec<FieldConflict>>,
+}
+
+#[derive(serde::Serialize)]
+struct FieldConflict {
+    path: String,
+    key: String,
 }
 
 #[derive(serde::Serialize)]
@@ -505,12 +1149,43 @@ struct SchemaCheckOutput {
     violations: Vec<oxidian::SchemaViolationRecord>,
 }
 
+#[derive(serde::Serialize)]
+struct HealthOutput {
+    schema_status: oxidian::SchemaStatus,
+    schema_errors: usize,
+    schema_warnings: usize,
+    internal_occurrences: usize,
+    broken_links: usize,
+    /// Whether a counted signal reached `--fail-on`; always false without it.
+    failed: bool,
+}
+
+#[derive(serde::Serialize)]
+struct FieldReportOutput {
+    fields: Vec<oxidian::InferredField>,
+    field_report: String,
+}
+
+#[derive(serde::Serialize)]
+struct DuplicatesOutput {
+    groups: usize,
+    duplicates: Vec<oxidian::DuplicateGroup>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merge_plan: Option<Vec<oxidian::MergePlan>>,
+}
+
 #[derive(serde::Serialize)]
 struct SchemaInitOutput {
     path: String,
     template: String,
 }
 
+#[derive(serde::Serialize)]
+struct SchemaLintOutput {
+    count: usize,
+    lints: Vec<oxidian::SchemaViolation>,
+}
+
 #[cfg(feature = "sqlite")]
 #[derive(serde::Serialize)]
 struct PersistOutput {
@@ -519,6 +1194,8 @@ struct PersistOutput {
     tags: usize,
     tasks: usize,
     links: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sync: Option<oxidian::IndexSyncStats>,
 }
 
 // ---------------------------------------------------------------------------
@@ -534,7 +1211,7 @@ async fn main() -> anyhow::Result<()> {
     match result {
         Ok(()) => Ok(()),
         Err(e) => {
-            if matches!(fmt, OutputFormat::Json) {
+            if matches!(fmt, OutputFormat::Json | OutputFormat::Ndjson) {
                 emit_json_error("error", &e.to_string());
                 std::process::exit(1);
             }
@@ -546,54 +1223,102 @@ async fn main() -> anyhow::Result<()> {
 async fn run(cli: Cli) -> anyhow::Result<()> {
     let fmt = cli.output;
     let quiet = cli.quiet;
+    let tabular = matches!(
+        cli.command,
+        Command::Query(_) | Command::Tags { .. } | Command::Tasks(_) | Command::Links { .. }
+    );
+    if matches!(fmt, OutputFormat::Csv) && !tabular {
+        anyhow::bail!("-o csv is only supported by query, tags, tasks and links");
+    }
 
     match cli.command {
-        Command::Search {
-            query,
-            mode,
-            limit,
-            min_score,
-        } => handle_search(cli.vault, fmt, quiet, query, mode, limit, min_score).await?,
-        Command::Query {
-            prefix,
-            tag,
-            exists,
-            eq,
-            contains,
-            gt,
-            sort,
-            desc,
-            limit,
-        } => {
-            handle_query(
-                cli.vault, fmt, prefix, tag, exists, eq, contains, gt, sort, desc, limit,
-            )
-            .await?
-        }
-        Command::Tags { top } => handle_tags(cli.vault, fmt, top).await?,
-        Command::Tasks {
-            prefix,
-            status,
-            contains,
-            limit,
-        } => handle_tasks(cli.vault, fmt, prefix, status, contains, limit).await?,
+        Command::Search(args) => handle_search(cli.vault, fmt, quiet, args).await?,
+        Command::Query(args) => handle_query(cli.vault, fmt, *args).await?,
+        Command::Tags {
+            top,
+            rename_preview,
+        } => handle_tags(cli.vault, fmt, top, rename_preview).await?,
+        Command::Tasks(args) => handle_tasks(cli.vault, fmt, args).await?,
         Command::Links {
             note,
             kind,
             only_embeds,
-        } => handle_links(cli.vault, fmt, note, kind, only_embeds).await?,
-        Command::Backlinks { note } => handle_backlinks(cli.vault, fmt, note).await?,
-        Command::Mentions { note, limit } => handle_mentions(cli.vault, fmt, note, limit).await?,
+            resolve,
+            external,
+            subpath_usage,
+            orphaned_targets,
+            exclude_tag,
+        } => {
+            if external {
+                handle_external_links(cli.vault, fmt).await?
+            } else if subpath_usage {
+                handle_subpath_usage(cli.vault, fmt).await?
+            } else if orphaned_targets {
+                handle_orphaned_targets(cli.vault, fmt, exclude_tag).await?
+            } else {
+                let Some(note) = note else {
+                    anyhow::bail!("a note path is required");
+                };
+                handle_links(cli.vault, fmt, note, kind, only_embeds, resolve).await?
+            }
+        }
+        Command::Backlinks {
+            note,
+            include_mentions,
+            show_ambiguous,
+        } => handle_backlinks(cli.vault, fmt, note, include_mentions, show_ambiguous).await?,
+        Command::Mentions {
+            note,
+            limit,
+            write_suggestions,
+        } => handle_mentions(cli.vault, fmt, note, limit, write_suggestions).await?,
         Command::Neighbors {
             note,
             min_score,
             top_k,
-        } => handle_neighbors(cli.vault, fmt, quiet, note, min_score, top_k).await?,
-        Command::Stats { tag } => handle_stats(cli.vault, fmt, tag).await?,
-        Command::Graph { note } => handle_graph(cli.vault, fmt, note).await?,
+            rerank,
+        } => handle_neighbors(cli.vault, fmt, quiet, note, min_score, top_k, rerank).await?,
+        Command::Stats {
+            tag,
+            tag_prefix,
+            other,
+            metrics,
+        } => handle_stats(cli.vault, fmt, tag, tag_prefix, other, metrics).await?,
+        Command::Graph {
+            note,
+            isolated_components,
+            dead_ends,
+            directed_stats,
+            export,
+            export_gexf,
+        } => {
+            if export {
+                handle_graph_export(cli.vault, fmt).await?
+            } else if export_gexf {
+                handle_graph_gexf(cli.vault).await?
+            } else {
+                handle_graph(
+                    cli.vault,
+                    fmt,
+                    note,
+                    isolated_components,
+                    dead_ends,
+                    directed_stats,
+                )
+                .await?
+            }
+        }
+        Command::Predicates { unresolved } => handle_predicates(cli.vault, fmt, unresolved).await?,
+        Command::Notes {
+            sort,
+            desc,
+            no_links_from,
+        } => handle_notes(cli.vault, fmt, sort, desc, no_links_from).await?,
         Command::Check { command } => handle_check(cli.vault, fmt, quiet, command).await?,
-        Command::Watch => handle_watch(cli.vault, fmt, quiet).await?,
-        Command::Persist { db } => handle_persist(cli.vault, fmt, quiet, db).await?,
+        Command::Watch { snapshot_path } => {
+            handle_watch(cli.vault, fmt, quiet, snapshot_path).await?
+        }
+        Command::Persist { db, full } => handle_persist(cli.vault, fmt, quiet, db, full).await?,
         Command::Schema { command } => handle_schema(cli.vault, fmt, command).await?,
         #[cfg(feature = "web-ui")]
         Command::WebUi { bind } => handle_web_ui(cli.vault, bind).await?,
@@ -611,8 +1336,15 @@ fn require_vault(vault: Option<PathBuf>) -> anyhow::Result<PathBuf> {
 }
 
 async fn open_service(vault: Option<PathBuf>) -> anyhow::Result<VaultService> {
+    open_service_with_config(vault, VaultConfig::default()).await
+}
+
+async fn open_service_with_config(
+    vault: Option<PathBuf>,
+    cfg: VaultConfig,
+) -> anyhow::Result<VaultService> {
     let vault_path = require_vault(vault)?;
-    let vault = Vault::open(&vault_path)?;
+    let vault = Vault::with_config(&vault_path, cfg)?;
     let service = VaultService::new(vault)?;
     service.build_index().await?;
     Ok(service)
@@ -651,6 +1383,15 @@ fn normalize_tag_for_query(raw: &str) -> anyhow::Result<String> {
     Ok(s.to_lowercase())
 }
 
+fn emit_count(fmt: OutputFormat, count: usize) -> anyhow::Result<()> {
+    match fmt {
+        OutputFormat::Json | OutputFormat::Ndjson => emit_json(&CountOutput { count }),
+        OutputFormat::Csv => emit_csv(&["count"], [CountOutput { count }]),
+        OutputFormat::Text => println!("{count}"),
+    }
+    Ok(())
+}
+
 fn print_occ(l: &Link) {
     println!(
         "- {:?}\tembed={}\t{}:{}\ttarget={:?}\tsubpath={:?}\tdisplay={:?}\traw={:?}",
@@ -666,9 +1407,16 @@ async fn handle_stats(
     vault: Option<PathBuf>,
     fmt: OutputFormat,
     tag: Option<String>,
+    tag_prefix: Option<String>,
+    other: bool,
+    metrics: bool,
 ) -> anyhow::Result<()> {
     let service = open_service(vault).await?;
     let snapshot = service.index_snapshot();
+    let metrics: Option<BuildMetricsOutput> = metrics
+        .then(|| snapshot.build_metrics())
+        .flatten()
+        .map(Into::into);
 
     let file_count = snapshot.all_files().count();
     let note_count = snapshot
@@ -676,50 +1424,127 @@ async fn handle_stats(
         .filter(|f| matches!(f.kind, FileKind::Markdown | FileKind::Canvas))
         .count();
     let tag_count = snapshot.all_tags().count();
+    let by_kind = snapshot.note_count_by_kind();
 
-    let (tag_filter, tagged_files) = if let Some(ref raw_tag) = tag {
+    let nested = tag_prefix.is_some();
+    let (tag_filter, tagged_files) = if let Some(ref raw_tag) = tag.or(tag_prefix) {
         let t = normalize_tag_for_query(raw_tag)?;
-        let files: Vec<String> = snapshot
-            .files_with_tag(&Tag(t.clone()))
-            .map(|p| p.as_str_lossy())
+        let paths: Vec<&VaultPath> = if nested {
+            snapshot.files_with_tag_prefix(&Tag(t.clone()))
+        } else {
+            snapshot.files_with_tag(&Tag(t.clone())).collect()
+        };
+        let files: Vec<TaggedFileOutput> = paths
+            .into_iter()
+            .map(|p| TaggedFileOutput {
+                path: p.as_str_lossy(),
+                title: snapshot.note(p).map(|n| n.title.clone()),
+            })
             .collect();
         (Some(t), Some(files))
     } else {
         (None, None)
     };
+    let other_files: Option<BTreeMap<String, OtherFilesOutput>> = other.then(|| {
+        snapshot
+            .other_files_by_extension()
+            .into_iter()
+            .map(|(ext, paths)| {
+                let out = OtherFilesOutput {
+                    count: paths.len(),
+                    paths,
+                };
+                (ext, out)
+            })
+            .collect()
+    });
 
     match fmt {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Ndjson => {
             emit_json(&StatsOutput {
                 files: file_count,
                 notes: note_count,
                 tags: tag_count,
+                by_kind,
                 tag_filter,
+                tag_prefix: nested,
                 tagged_files,
+                other_files,
+                metrics,
             });
         }
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::Csv => {
             println!("stats");
             println!("  files: {file_count}");
             println!("  notes: {note_count}");
             println!("  tags: {tag_count}");
+            println!("  by_kind:");
+            for (kind, n) in &by_kind {
+                println!("    {kind}: {n}");
+            }
 
             if let (Some(tag_name), Some(files)) = (&tag_filter, &tagged_files) {
-                println!("\nfiles with tag #{tag_name}:");
-                for p in files {
-                    println!("- {p}");
+                let scope = if nested { " (and nested tags)" } else { "" };
+                println!("\nfiles with tag #{tag_name}{scope}:");
+                for f in files {
+                    match &f.title {
+                        Some(title) => println!("- {}\t{title}", f.path),
+                        None => println!("- {}", f.path),
+                    }
                 }
             }
+
+            if let Some(groups) = &other_files {
+                println!("\nother files by extension:");
+                for (ext, group) in groups {
+                    let label = if ext.is_empty() { "(none)" } else { ext };
+                    println!("  {label}: {}", group.count);
+                    for p in &group.paths {
+                        println!("    - {}", p.as_str_lossy());
+                    }
+                }
+            }
+
+            if let Some(m) = &metrics {
+                println!("\nbuild metrics ({} files):", m.files);
+                println!("  walk: {:.2} ms", m.walk_ms);
+                println!("  parse: {:.2} ms", m.parse_ms);
+                println!("  schema: {:.2} ms", m.schema_ms);
+                println!("  reconcile: {:.2} ms", m.reconcile_ms);
+                println!("  total: {:.2} ms", m.total_ms);
+            }
         }
     }
 
     Ok(())
 }
 
-async fn handle_tags(vault: Option<PathBuf>, fmt: OutputFormat, top: usize) -> anyhow::Result<()> {
+async fn handle_tags(
+    vault: Option<PathBuf>,
+    fmt: OutputFormat,
+    top: usize,
+    rename_preview: bool,
+) -> anyhow::Result<()> {
     let service = open_service(vault).await?;
     let snapshot = service.index_snapshot();
 
+    if rename_preview {
+        let clusters = snapshot.near_duplicate_tags();
+        match fmt {
+            OutputFormat::Json | OutputFormat::Ndjson => emit_json(&clusters),
+            OutputFormat::Csv => emit_csv(&["canonical", "tags"], &clusters),
+            OutputFormat::Text => {
+                for cluster in &clusters {
+                    println!("#{}", cluster.canonical.0);
+                    for usage in &cluster.tags {
+                        println!("  {}\t#{}", usage.files, usage.tag.0);
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+
     let mut rows: Vec<(Tag, usize)> = snapshot
         .all_tags()
         .cloned()
@@ -733,7 +1558,7 @@ async fn handle_tags(vault: Option<PathBuf>, fmt: OutputFormat, top: usize) -> a
     let rows: Vec<(Tag, usize)> = rows.into_iter().take(top).collect();
 
     match fmt {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Ndjson => {
             let items: Vec<TagCount> = rows
                 .iter()
                 .map(|(tag, count)| TagCount {
@@ -743,6 +1568,13 @@ async fn handle_tags(vault: Option<PathBuf>, fmt: OutputFormat, top: usize) -> a
                 .collect();
             emit_json(&items);
         }
+        OutputFormat::Csv => emit_csv(
+            &["tag", "count"],
+            rows.iter().map(|(tag, count)| TagCount {
+                tag: tag.0.clone(),
+                count: *count,
+            }),
+        ),
         OutputFormat::Text => {
             for (tag, n) in &rows {
                 println!("{n}\t#{tag}", tag = tag.0);
@@ -756,35 +1588,116 @@ async fn handle_tags(vault: Option<PathBuf>, fmt: OutputFormat, top: usize) -> a
 async fn handle_tasks(
     vault: Option<PathBuf>,
     fmt: OutputFormat,
-    prefix: Option<String>,
-    status: Option<StatusArg>,
-    contains: Option<String>,
-    limit: usize,
+    args: TaskArgs,
 ) -> anyhow::Result<()> {
+    let TaskArgs {
+        prefix,
+        path,
+        status,
+        exclude_status,
+        contains,
+        contains_regex,
+        due_within,
+        due_before,
+        due_after,
+        min_priority,
+        completed_since,
+        tag,
+        sort,
+        desc,
+        limit,
+        count,
+        group_by,
+    } = args;
     let service = open_service(vault).await?;
 
     let mut q = TaskQuery::all();
     if let Some(prefix) = prefix {
         q = q.from_path_prefix(prefix);
     }
+    if let Some(path) = path {
+        let path = VaultPath::try_from(path.as_path())?;
+        if service.with_index(|idx| idx.note(&path).is_none()) {
+            anyhow::bail!("note not found: {}", path.as_str_lossy());
+        }
+        q = q.from_path(path);
+    }
     if let Some(status) = status {
         q = q.status(status.into());
     }
+    for status in exclude_status {
+        q = q.exclude_status(status.into());
+    }
     if let Some(needle) = contains {
         q = q.contains_text(needle);
     }
+    if let Some(pattern) = contains_regex {
+        q = q.text_matches(&pattern)?;
+    }
+    if let Some(days) = due_within {
+        let today = oxidian::Date::today();
+        q = q
+            .due_between(today, today.add_days(i64::from(days)))
+            .exclude_status(TaskStatus::Done)
+            .exclude_status(TaskStatus::Cancelled);
+    }
+    if let Some(date) = due_before {
+        q = q.due_before(date);
+    }
+    if let Some(date) = due_after {
+        q = q.due_after(date);
+    }
+    if let Some(priority) = min_priority {
+        q = q.min_priority(priority.into());
+    }
+    if let Some(SinceArg(date)) = completed_since {
+        q = q.completed_since(date);
+    }
+    if let Some(tag) = tag {
+        q = q.with_tag(tag);
+    }
+    if let Some(sort) = sort {
+        let dir = if desc { SortDir::Desc } else { SortDir::Asc };
+        q = q.sort_by(sort.into(), dir);
+    } else if due_within.is_some() {
+        q = q.sort_by(TaskSortKey::Due, SortDir::Asc);
+    } else if completed_since.is_some() {
+        q = q.sort_by(TaskSortKey::Done, SortDir::Desc);
+    }
+    if count {
+        return emit_count(fmt, service.query_tasks(&q).len());
+    }
     q = q.limit(limit);
 
+    if let Some(TaskGroupArg::Status) = group_by {
+        let groups = service.query_tasks_by_status(&q);
+        match fmt {
+            OutputFormat::Json | OutputFormat::Ndjson => emit_json(&groups),
+            OutputFormat::Csv => emit_csv(TASK_CSV_HEADER, groups.values().flatten()),
+            OutputFormat::Text => {
+                for (status, hits) in &groups {
+                    println!("{status}: {}", hits.len());
+                    for hit in hits {
+                        println!("- {}:{}\t{}", hit.path.as_str_lossy(), hit.line, hit.text);
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+
     let hits: Vec<oxidian::TaskHit> = service.query_tasks(&q);
 
     match fmt {
         OutputFormat::Json => {
             emit_json(&hits);
         }
+        OutputFormat::Ndjson => emit_ndjson(&hits)?,
+        OutputFormat::Csv => emit_csv(TASK_CSV_HEADER, &hits),
         OutputFormat::Text => {
             for hit in &hits {
                 println!(
-                    "{:?}\t{}:{}\t{}",
+                    "{}\t{}:{}\t{}",
                     hit.status,
                     hit.path.as_str_lossy(),
                     hit.line,
@@ -803,6 +1716,7 @@ async fn handle_links(
     note: PathBuf,
     kind: Option<LinkKindArg>,
     only_embeds: bool,
+    resolve: bool,
 ) -> anyhow::Result<()> {
     let service = open_service(vault).await?;
     let snapshot = service.index_snapshot();
@@ -820,15 +1734,35 @@ async fn handle_links(
         .filter(|l| !only_embeds || l.embed)
         .collect();
 
+    let resolver = resolve.then(|| snapshot.link_resolver());
+    let resolution_of = |l: &Link| {
+        let resolver = resolver.as_ref()?;
+        matches!(l.target, oxidian::LinkTarget::Internal { .. })
+            .then(|| resolver.resolve_link_target(&l.target, &rel))
+    };
+
     match fmt {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Ndjson => {
             emit_json(&LinksOutput {
                 note: rel.as_str_lossy(),
                 unique_targets: note_meta.links.len(),
                 occurrences: filtered.len(),
-                links: filtered.into_iter().cloned().collect(),
+                links: filtered
+                    .iter()
+                    .map(|l| LinkOccurrenceOutput {
+                        link: (*l).clone(),
+                        resolution: resolution_of(l),
+                    })
+                    .collect(),
             });
         }
+        OutputFormat::Csv => emit_csv(
+            LINK_CSV_HEADER,
+            filtered.iter().map(|l| LinkOccurrenceOutput {
+                link: (*l).clone(),
+                resolution: resolution_of(l),
+            }),
+        ),
         OutputFormat::Text => {
             println!("note: {}", rel.as_str_lossy());
             println!("summary");
@@ -845,6 +1779,16 @@ async fn handle_links(
             println!("\noccurrences:");
             for l in &filtered {
                 print_occ(l);
+                match resolution_of(l) {
+                    Some(oxidian::ResolveResult::Resolved(p)) => {
+                        println!("    resolved\t{}", p.as_str_lossy());
+                    }
+                    Some(oxidian::ResolveResult::Missing) => println!("    missing"),
+                    Some(oxidian::ResolveResult::Ambiguous(cands)) => {
+                        println!("    ambiguous\tcandidates={cands:?}");
+                    }
+                    None => {}
+                }
             }
         }
     }
@@ -852,28 +1796,139 @@ async fn handle_links(
     Ok(())
 }
 
+async fn handle_external_links(vault: Option<PathBuf>, fmt: OutputFormat) -> anyhow::Result<()> {
+    let service = open_service(vault).await?;
+    let urls = service.with_index(|idx| idx.external_links());
+    let occurrences = urls.iter().map(|u| u.count).sum();
+
+    match fmt {
+        OutputFormat::Json | OutputFormat::Ndjson => {
+            emit_json(&ExternalLinksOutput {
+                unique_urls: urls.len(),
+                occurrences,
+                urls,
+            });
+        }
+        OutputFormat::Csv => emit_csv(&["url", "count", "sources"], &urls),
+        OutputFormat::Text => {
+            println!("summary");
+            println!("  unique_urls: {}", urls.len());
+            println!("  occurrences: {occurrences}");
+            for u in &urls {
+                println!("\n{}\t{}", u.count, u.url);
+                for src in &u.sources {
+                    println!(
+                        "- {}:{}:{}",
+                        src.source.as_str_lossy(),
+                        src.location.line,
+                        src.location.column
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Headings listed by `links --subpath-usage`.
+const SUBPATH_TOP_HEADINGS: usize = 10;
+
+async fn handle_subpath_usage(vault: Option<PathBuf>, fmt: OutputFormat) -> anyhow::Result<()> {
+    let service = open_service(vault).await?;
+    let usage = service.with_index(|idx| idx.subpath_usage(SUBPATH_TOP_HEADINGS));
+
+    match fmt {
+        OutputFormat::Json | OutputFormat::Ndjson => emit_json(&usage),
+        OutputFormat::Csv => emit_csv(&["target", "heading", "count"], &usage.top_headings),
+        OutputFormat::Text => {
+            println!("summary");
+            println!("  whole_note: {}", usage.whole_note);
+            println!("  heading: {}", usage.heading);
+            println!("  block: {}", usage.block);
+            if !usage.top_headings.is_empty() {
+                println!("\ntop_headings:");
+                for h in &usage.top_headings {
+                    println!("{}\t{}#{}", h.count, h.target, h.heading);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_orphaned_targets(
+    vault: Option<PathBuf>,
+    fmt: OutputFormat,
+    exclude_tag: Vec<String>,
+) -> anyhow::Result<()> {
+    let excluded: Vec<Tag> = exclude_tag
+        .iter()
+        .map(|t| normalize_tag_for_query(t).map(Tag))
+        .collect::<anyhow::Result<_>>()?;
+    let service = open_service(vault).await?;
+    let backlinks = service.build_backlinks()?;
+    let notes: Vec<OrphanedTargetOutput> = service.with_index(|idx| {
+        backlinks
+            .unreferenced(idx)
+            .into_iter()
+            .filter(|p| {
+                idx.note(p)
+                    .is_none_or(|n| !excluded.iter().any(|t| n.tags.contains(t)))
+            })
+            .map(|path| OrphanedTargetOutput { path })
+            .collect()
+    });
+
+    match fmt {
+        OutputFormat::Json | OutputFormat::Ndjson => emit_json(&OrphanedTargetsOutput {
+            count: notes.len(),
+            notes,
+        }),
+        OutputFormat::Csv => emit_csv(&["path"], &notes),
+        OutputFormat::Text => {
+            println!("orphaned_targets: {}", notes.len());
+            for n in &notes {
+                println!("- {}", n.path.as_str_lossy());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Cap on unlinked mentions appended by `backlinks --include-mentions`.
+const BACKLINK_MENTIONS_LIMIT: usize = 100;
+
 async fn handle_backlinks(
     vault: Option<PathBuf>,
     fmt: OutputFormat,
     note: String,
+    include_mentions: bool,
+    show_ambiguous: bool,
 ) -> anyhow::Result<()> {
     let service = open_service(vault).await?;
     let snapshot = service.index_snapshot();
-    let backlinks = service.build_backlinks()?;
+    let graph = service.build_graph()?;
+    let backlinks = &graph.backlinks;
 
     let target: VaultPath = if note.contains('/') || note.contains('.') {
         VaultPath::try_from(Path::new(&note))?
     } else {
         let needle = note.to_lowercase();
-        let mut matches = Vec::new();
+        let mut matches = snapshot.aliases_index().remove(&needle).unwrap_or_default();
         for f in snapshot.all_files() {
-            let Some(_note) = snapshot.note(&f.path) else {
-                continue;
-            };
-            let Some(stem) = f.path.as_path().file_stem().and_then(|s| s.to_str()) else {
+            if snapshot.note(&f.path).is_none() {
                 continue;
-            };
-            if stem.to_lowercase() == needle {
+            }
+            let stem_matches = f
+                .path
+                .as_path()
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|stem| stem.to_lowercase() == needle);
+            if stem_matches {
                 matches.push(f.path.clone());
             }
         }
@@ -893,18 +1948,31 @@ async fn handle_backlinks(
     };
 
     let items = backlinks.backlinks(&target);
+    let mentions = if include_mentions {
+        Some(
+            service
+                .unlinked_mentions(&target, BACKLINK_MENTIONS_LIMIT)
+                .await?,
+        )
+    } else {
+        None
+    };
+    let ambiguous: Option<Vec<oxidian::ResolvedInternalLink>> =
+        show_ambiguous.then(|| graph.ambiguous().cloned().collect());
 
     match fmt {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Ndjson => {
             emit_json(&BacklinksOutput {
                 target: target.as_str_lossy(),
                 count: items.len(),
                 backlinks: items.to_vec(),
                 unresolved_internal_occurrences: backlinks.unresolved,
                 ambiguous_internal_occurrences: backlinks.ambiguous,
+                mentions,
+                ambiguous,
             });
         }
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::Csv => {
             println!("target: {}", target.as_str_lossy());
             println!("summary");
             println!("  backlinks: {}", items.len());
@@ -923,6 +1991,35 @@ async fn handle_backlinks(
                 backlinks.unresolved
             );
             println!("  ambiguous_internal_occurrences: {}", backlinks.ambiguous);
+            if let Some(ambiguous) = &ambiguous {
+                for a in ambiguous {
+                    let oxidian::ResolveResult::Ambiguous(candidates) = &a.resolution else {
+                        continue;
+                    };
+                    let candidates: Vec<String> =
+                        candidates.iter().map(|c| c.as_str_lossy()).collect();
+                    println!(
+                        "- ambiguous {}:{}\traw={:?}\tcandidates={}",
+                        a.source.as_str_lossy(),
+                        a.link.location.line,
+                        a.link.raw,
+                        candidates.join(",")
+                    );
+                }
+            }
+
+            if let Some(mentions) = &mentions {
+                println!("  mentions: {}", mentions.len());
+                for m in mentions {
+                    println!(
+                        "- mention {}:{}\tterm={:?}\t{}",
+                        m.source.as_str_lossy(),
+                        m.line,
+                        m.term,
+                        m.line_text
+                    );
+                }
+            }
         }
     }
 
@@ -934,19 +2031,47 @@ async fn handle_mentions(
     fmt: OutputFormat,
     note: PathBuf,
     limit: usize,
+    write_suggestions: bool,
 ) -> anyhow::Result<()> {
     let service = open_service(vault).await?;
     let target = VaultPath::try_from(note.as_path())?;
+
+    if write_suggestions {
+        let suggestions = service.mention_suggestions(&target, limit).await?;
+        match fmt {
+            OutputFormat::Json | OutputFormat::Ndjson => emit_json(&MentionSuggestionsOutput {
+                count: suggestions.len(),
+                suggestions,
+            }),
+            OutputFormat::Text | OutputFormat::Csv => {
+                println!("summary");
+                println!("  suggestions: {}", suggestions.len());
+                for s in &suggestions {
+                    println!(
+                        "- {}:{}:{}-{}\t{:?}\t{}",
+                        s.source.as_str_lossy(),
+                        s.line,
+                        s.column,
+                        s.end_column,
+                        s.text,
+                        s.replacement
+                    );
+                }
+            }
+        }
+        return Ok(());
+    }
+
     let mentions = service.unlinked_mentions(&target, limit).await?;
 
     match fmt {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Ndjson => {
             emit_json(&MentionsOutput {
                 count: mentions.len(),
                 mentions,
             });
         }
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::Csv => {
             println!("summary");
             println!("  mentions: {}", mentions.len());
             for m in &mentions {
@@ -971,10 +2096,11 @@ async fn handle_neighbors(
     note: PathBuf,
     min_score: Option<f32>,
     top_k: Option<usize>,
+    rerank: Option<RerankArg>,
 ) -> anyhow::Result<()> {
     #[cfg(not(feature = "similarity"))]
     {
-        let _ = (vault, fmt, quiet, note, min_score, top_k);
+        let _ = (vault, fmt, quiet, note, min_score, top_k, rerank);
         anyhow::bail!("This command requires --features similarity");
     }
 
@@ -989,14 +2115,19 @@ async fn handle_neighbors(
             quiet,
             &format!("computing similarity for {}...", note_path.as_str_lossy()),
         );
-        let hits = service.note_similarity_for(&note_path)?;
+        let hits = match rerank {
+            Some(RerankArg::TokenOverlap) => {
+                service.note_similarity_for_reranked(&note_path, &oxidian::TokenOverlapReranker)?
+            }
+            None => service.note_similarity_for(&note_path)?,
+        };
         progress(quiet, &format!("done: {} hits", hits.len()));
 
         match fmt {
-            OutputFormat::Json => {
+            OutputFormat::Json | OutputFormat::Ndjson => {
                 emit_json(&hits);
             }
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Csv => {
                 for hit in &hits {
                     println!(
                         "{:.3}\t{}\t{}",
@@ -1012,14 +2143,176 @@ async fn handle_neighbors(
     }
 }
 
+async fn handle_graph_gexf(vault: Option<PathBuf>) -> anyhow::Result<()> {
+    let service = open_service(vault).await?;
+    let snapshot = service.index_snapshot();
+    print!("{}", service.build_graph()?.to_gexf(&snapshot));
+    Ok(())
+}
+
+async fn handle_graph_export(vault: Option<PathBuf>, fmt: OutputFormat) -> anyhow::Result<()> {
+    let service = open_service(vault).await?;
+    let snapshot = service.index_snapshot();
+    let export = service.build_graph()?.export(&snapshot);
+
+    match fmt {
+        OutputFormat::Json | OutputFormat::Ndjson => emit_json(&export),
+        OutputFormat::Text | OutputFormat::Csv => {
+            println!("nodes: {}", export.nodes.len());
+            for n in &export.nodes {
+                println!(
+                    "- {}\t{:?}\t{}",
+                    n.path.as_str_lossy(),
+                    n.kind,
+                    n.title.as_deref().unwrap_or("")
+                );
+            }
+            println!("\nedges: {}", export.edges.len());
+            for e in &export.edges {
+                println!(
+                    "- {} -> {}{}",
+                    e.source.as_str_lossy(),
+                    e.target.as_str_lossy(),
+                    if e.embed { "\tembed" } else { "" }
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_notes(
+    vault: Option<PathBuf>,
+    fmt: OutputFormat,
+    sort: Option<NoteSortArg>,
+    desc: bool,
+    no_links_from: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let service = open_service(vault).await?;
+    if let Some(source) = no_links_from {
+        let source = VaultPath::try_from(source.as_path())?;
+        if service.with_index(|idx| idx.note(&source).is_none()) {
+            anyhow::bail!("note not found: {}", source.as_str_lossy());
+        }
+        let candidates = service.with_index(|idx| idx.link_candidates(&source));
+        match fmt {
+            OutputFormat::Json | OutputFormat::Ndjson => emit_json(&candidates),
+            OutputFormat::Text | OutputFormat::Csv => {
+                println!("candidates: {}", candidates.len());
+                for c in &candidates {
+                    let tags: Vec<&str> = c.shared_tags.iter().map(|t| t.0.as_str()).collect();
+                    println!(
+                        "- {}\tshared_tags={}",
+                        c.path.as_str_lossy(),
+                        tags.join(",")
+                    );
+                }
+            }
+        }
+        return Ok(());
+    }
+    let snapshot = service.index_snapshot();
+    let mut notes = service.build_graph()?.note_summaries(&snapshot);
+
+    if let Some(sort) = sort {
+        notes.sort_by(|a, b| {
+            let ord = match sort {
+                NoteSortArg::Path => a.path.cmp(&b.path),
+                NoteSortArg::Title => a.title.cmp(&b.title),
+                NoteSortArg::Tags => a.counts.tags.cmp(&b.counts.tags),
+                NoteSortArg::Links => a.counts.outgoing_links.cmp(&b.counts.outgoing_links),
+                NoteSortArg::Backlinks => a.counts.backlinks.cmp(&b.counts.backlinks),
+                NoteSortArg::Tasks => a.counts.tasks.cmp(&b.counts.tasks),
+            };
+            let ord = if desc { ord.reverse() } else { ord };
+            ord.then_with(|| a.path.cmp(&b.path))
+        });
+    }
+
+    match fmt {
+        OutputFormat::Json | OutputFormat::Ndjson => emit_json(&notes),
+        OutputFormat::Text | OutputFormat::Csv => {
+            println!("notes: {}", notes.len());
+            for n in &notes {
+                println!(
+                    "- {}\t{}\ttags={}\tlinks={}\tbacklinks={}\ttasks={}\tfrontmatter={:?}",
+                    n.path.as_str_lossy(),
+                    n.title,
+                    n.counts.tags,
+                    n.counts.outgoing_links,
+                    n.counts.backlinks,
+                    n.counts.tasks,
+                    n.counts.frontmatter
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_predicates(
+    vault: Option<PathBuf>,
+    fmt: OutputFormat,
+    unresolved: bool,
+) -> anyhow::Result<()> {
+    let service = open_service(vault).await?;
+    let mut report = service.with_index(|idx| idx.predicate_report());
+    if unresolved {
+        for group in &mut report.predicates {
+            group
+                .edges
+                .retain(|e| !matches!(e.resolution, oxidian::ResolveResult::Resolved(_)));
+        }
+        report.predicates.retain(|g| !g.edges.is_empty());
+    }
+
+    match fmt {
+        OutputFormat::Json | OutputFormat::Ndjson => emit_json(&report),
+        OutputFormat::Text | OutputFormat::Csv => {
+            println!("summary");
+            println!("  predicates: {}", report.predicates.len());
+            println!("  total_edges: {}", report.total_edges);
+            println!("  unresolved: {}", report.unresolved);
+            for group in &report.predicates {
+                println!("\n{}", group.predicate);
+                for e in &group.edges {
+                    let source = e.source.as_str_lossy();
+                    match &e.resolution {
+                        oxidian::ResolveResult::Resolved(p) => {
+                            println!("- {source}\t{}\t{}", e.key, p.as_str_lossy());
+                        }
+                        oxidian::ResolveResult::Missing => {
+                            println!("- {source}\t{}\tmissing\t{:?}", e.key, e.reference);
+                        }
+                        oxidian::ResolveResult::Ambiguous(cands) => {
+                            println!(
+                                "- {source}\t{}\tambiguous\t{:?}\tcandidates={cands:?}",
+                                e.key, e.reference
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 async fn handle_graph(
     vault: Option<PathBuf>,
     fmt: OutputFormat,
     note: Option<PathBuf>,
+    isolated_components: bool,
+    dead_ends: bool,
+    directed_stats: bool,
 ) -> anyhow::Result<()> {
     let service = open_service(vault).await?;
     let snapshot = service.index_snapshot();
     let graph = service.build_graph()?;
+    let components = isolated_components.then(|| graph.connected_components());
+    let dead_ends = dead_ends.then(|| graph.dead_ends(&snapshot));
+    let directed_stats = directed_stats.then(|| graph.directed_stats());
 
     let (source, outgoing) = if let Some(note) = note {
         let source = VaultPath::try_from(note.as_path())?;
@@ -1030,16 +2323,19 @@ async fn handle_graph(
     };
 
     match fmt {
-        OutputFormat::Json => {
+        OutputFormat::Json | OutputFormat::Ndjson => {
             emit_json(&GraphOutput {
                 unresolved_internal_occurrences: graph.backlinks.unresolved,
                 ambiguous_internal_occurrences: graph.backlinks.ambiguous,
                 issue_count: graph.issues.len(),
                 source,
                 outgoing,
+                components,
+                dead_ends,
+                directed_stats,
             });
         }
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::Csv => {
             println!("summary");
             println!(
                 "  unresolved_internal_occurrences: {}",
@@ -1051,6 +2347,38 @@ async fn handle_graph(
             );
             println!("  issue_count: {}", graph.issues.len());
 
+            if let Some(components) = &components {
+                println!("\ncomponents: {}", components.len());
+                for (i, c) in components.iter().enumerate() {
+                    println!("- component {}\tsize={}", i + 1, c.size);
+                    for m in &c.members {
+                        println!("    {}", m.as_str_lossy());
+                    }
+                }
+            }
+
+            if let Some(dead_ends) = &dead_ends {
+                println!("\ndead_ends: {}", dead_ends.len());
+                for p in dead_ends {
+                    println!("- {}", p.as_str_lossy());
+                }
+            }
+
+            if let Some(stats) = &directed_stats {
+                let histogram = |h: &std::collections::BTreeMap<usize, usize>| {
+                    h.iter()
+                        .map(|(degree, nodes)| format!("{degree}={nodes}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                println!("\ndirected_stats");
+                println!("  nodes: {}", stats.nodes);
+                println!("  edges: {}", stats.edges);
+                println!("  reciprocal_pairs: {}", stats.reciprocal_pairs);
+                println!("  in_degree: {}", histogram(&stats.in_degree));
+                println!("  out_degree: {}", histogram(&stats.out_degree));
+            }
+
             if let (Some(src), Some(links)) = (&source, &outgoing) {
                 println!("\nsource: {src}");
                 for o in links {
@@ -1096,18 +2424,30 @@ async fn handle_search(
     vault: Option<PathBuf>,
     fmt: OutputFormat,
     quiet: bool,
-    query: String,
-    mode: SearchMode,
-    limit: usize,
-    min_score: Option<f32>,
+    args: SearchArgs,
 ) -> anyhow::Result<()> {
+    let SearchArgs {
+        query,
+        mode,
+        file_match,
+        limit,
+        min_score,
+    } = args;
+    if !matches!(mode, SearchMode::Files) && !matches!(file_match, FileMatchArg::Fuzzy) {
+        anyhow::bail!("--match prefix|exact only applies to --mode files");
+    }
     match mode {
         SearchMode::Files => {
             let service = open_service(vault).await?;
-            let hits = service.search_filenames_fuzzy(&query, limit);
+            let hits = match file_match {
+                FileMatchArg::Fuzzy => service.search_filenames_fuzzy(&query, limit),
+                FileMatchArg::Prefix => service.search_filenames_prefix(&query, limit),
+                FileMatchArg::Exact => service.search_filenames_exact(&query, limit),
+            };
             match fmt {
                 OutputFormat::Json => emit_json(&hits),
-                OutputFormat::Text => {
+                OutputFormat::Ndjson => emit_ndjson(&hits)?,
+                OutputFormat::Text | OutputFormat::Csv => {
                     for hit in &hits {
                         println!("{}\t{}", hit.score, hit.path.as_str_lossy());
                     }
@@ -1116,10 +2456,23 @@ async fn handle_search(
         }
         SearchMode::Content => {
             let service = open_service(vault).await?;
+            if let OutputFormat::Ndjson = fmt {
+                // Print hits as files are scanned (unranked) instead of waiting for the
+                // whole vault to be scored.
+                let mut rx = service.search_content_fuzzy_stream(&query);
+                let mut printed = 0;
+                while printed < limit
+                    && let Some(hit) = rx.recv().await
+                {
+                    emit_ndjson_line(&hit?)?;
+                    printed += 1;
+                }
+                return Ok(());
+            }
             let hits = service.search_content_fuzzy(&query, limit).await?;
             match fmt {
-                OutputFormat::Json => emit_json(&hits),
-                OutputFormat::Text => {
+                OutputFormat::Json | OutputFormat::Ndjson => emit_json(&hits),
+                OutputFormat::Text | OutputFormat::Csv => {
                     for hit in &hits {
                         println!(
                             "{}\t{}:{}\t{}",
@@ -1154,7 +2507,8 @@ async fn handle_search(
                 };
                 match fmt {
                     OutputFormat::Json => emit_json(&hits),
-                    OutputFormat::Text => {
+                    OutputFormat::Ndjson => emit_ndjson(&hits)?,
+                    OutputFormat::Text | OutputFormat::Csv => {
                         for hit in &hits {
                             println!("{:.3}\t{}", hit.score, hit.path.as_str_lossy());
                         }
@@ -1167,66 +2521,148 @@ async fn handle_search(
     Ok(())
 }
 
-#[allow(clippy::too_many_arguments)]
 async fn handle_query(
     vault: Option<PathBuf>,
     fmt: OutputFormat,
-    prefix: Option<String>,
-    tag: Option<String>,
-    exists: Vec<String>,
-    eq: Vec<String>,
-    contains: Vec<String>,
-    gt: Vec<String>,
-    sort: Option<String>,
-    desc: bool,
-    limit: usize,
+    args: QueryArgs,
 ) -> anyhow::Result<()> {
-    let service = open_service(vault).await?;
+    let QueryArgs {
+        prefix,
+        tag,
+        tag_prefix,
+        tag_source,
+        exclude_tag,
+        exists,
+        not_exists,
+        exists_any,
+        missing,
+        eq,
+        ne,
+        contains,
+        or,
+        gt,
+        gte,
+        lt,
+        lte,
+        min_tags,
+        max_tags,
+        lang,
+        sort,
+        sort_mtime,
+        sort_size,
+        desc,
+        limit,
+        count,
+    } = args;
+    if lang.is_some() && !cfg!(feature = "language") {
+        anyhow::bail!("--lang requires --features language");
+    }
+    let service = if lang.is_some() {
+        let cfg = VaultConfig {
+            detect_language: true,
+            ..Default::default()
+        };
+        open_service_with_config(vault, cfg).await?
+    } else {
+        open_service(vault).await?
+    };
 
     let mut q = Query::notes();
     if let Some(prefix) = prefix {
         q = q.from_path_prefix(prefix);
     }
     if let Some(tag) = tag {
-        q = q.from_tag(tag);
+        q = q.from_tag(tag).tag_source(tag_source.into());
+    }
+    if let Some(tag) = tag_prefix {
+        q = q.from_tag_prefix(tag);
+    }
+    for tag in exclude_tag {
+        q = q.exclude_tag(tag);
     }
 
     for key in exists {
         q = q.where_field(key).exists();
     }
+    for key in not_exists {
+        q = q.where_field(key).not_exists();
+    }
+    q = q.exists_any(exists_any).missing_all(missing);
     for kv in eq {
         let Some((k, v)) = kv.split_once('=') else {
             continue;
         };
         q = q.where_field(k).eq(v);
     }
-    for kv in contains {
+    for kv in ne {
         let Some((k, v)) = kv.split_once('=') else {
             continue;
         };
-        q = q.where_field(k).contains(v);
+        q = q.where_field(k).not_eq(v);
     }
-    for kv in gt {
+    for kv in contains {
         let Some((k, v)) = kv.split_once('=') else {
             continue;
         };
-        if let Ok(n) = v.trim().parse::<f64>() {
-            q = q.where_field(k).gt(n);
-        }
+        q = q.where_field(k).contains(v);
+    }
+    // Malformed pairs are skipped as for `--eq`; a group left empty is dropped rather than
+    // matching nothing.
+    let group: Vec<FieldPredicate> = or
+        .iter()
+        .filter_map(|kv| kv.split_once('='))
+        .map(|(k, v)| FieldPredicate::eq(k, v))
+        .collect();
+    if !group.is_empty() {
+        q = q.any_of(group);
+    }
+    // Pairs with a non-numeric right-hand side are skipped, like malformed `key=value`.
+    let numeric = |pairs: Vec<String>| -> Vec<(String, f64)> {
+        pairs
+            .iter()
+            .filter_map(|kv| {
+                let (k, v) = kv.split_once('=')?;
+                Some((k.to_string(), v.trim().parse::<f64>().ok()?))
+            })
+            .collect()
+    };
+    for (k, n) in numeric(gt) {
+        q = q.where_field(k).gt(n);
+    }
+    for (k, n) in numeric(gte) {
+        q = q.where_field(k).gte(n);
+    }
+    for (k, n) in numeric(lt) {
+        q = q.where_field(k).lt(n);
+    }
+    for (k, n) in numeric(lte) {
+        q = q.where_field(k).lte(n);
+    }
+    q = q.where_tag_count(min_tags, max_tags);
+    if let Some(code) = lang {
+        q = q.where_language(code);
     }
 
     let dir = if desc { SortDir::Desc } else { SortDir::Asc };
     if let Some(field) = sort {
         q = q.sort_by_field(field, dir);
+    } else if sort_mtime {
+        q = q.sort_by_mtime(dir);
+    } else if sort_size {
+        q = q.sort_by_size(dir);
     } else {
         q = q.sort_by_path(dir);
     }
+    if count {
+        return emit_count(fmt, service.query(&q).len());
+    }
     q = q.limit(limit);
 
     let hits: Vec<oxidian::QueryHit> = service.query(&q);
 
     match fmt {
-        OutputFormat::Json => emit_json(&hits),
+        OutputFormat::Json | OutputFormat::Ndjson => emit_json(&hits),
+        OutputFormat::Csv => emit_csv(&["path"], &hits),
         OutputFormat::Text => {
             for hit in &hits {
                 println!("{}", hit.path.as_str_lossy());
@@ -1248,11 +2684,12 @@ async fn handle_check(
             limit,
             reason,
             exclude_reason,
+            fail_on,
         } => {
             let service = open_service(vault).await?;
             let report = service.link_health_report()?;
 
-            let broken: Vec<oxidian::LinkIssue> = report
+            let mut broken: Vec<oxidian::LinkIssue> = report
                 .broken
                 .into_iter()
                 .filter(|issue| {
@@ -1265,10 +2702,12 @@ async fn handle_check(
                         true
                     }
                 })
-                .take(limit)
                 .collect();
+            let failed = fails(fail_on, broken.len(), 0);
+            broken.truncate(limit);
 
             match fmt {
+                OutputFormat::Ndjson => emit_ndjson(&broken)?,
                 OutputFormat::Json => {
                     emit_json(&LinkHealthOutput {
                         internal_occurrences: report.total_internal_occurrences,
@@ -1277,7 +2716,7 @@ async fn handle_check(
                         broken,
                     });
                 }
-                OutputFormat::Text => {
+                OutputFormat::Text | OutputFormat::Csv => {
                     println!("summary");
                     println!(
                         "  internal_occurrences: {}",
@@ -1290,9 +2729,10 @@ async fn handle_check(
                         println!("\nbroken:");
                         for issue in &broken {
                             let where_ = format!(
-                                "{}:{}",
+                                "{}:{}\t{}",
                                 issue.source.as_str_lossy(),
-                                issue.link.location.line
+                                issue.link.location.line,
+                                issue.source_title
                             );
                             match &issue.reason {
                                 LinkIssueReason::MissingTarget => {
@@ -1321,20 +2761,43 @@ async fn handle_check(
                     }
                 }
             }
+            if failed {
+                exit_failed()?;
+            }
         }
-        CheckCommand::Frontmatter { limit } => {
+        CheckCommand::Frontmatter {
+            prefix,
+            limit,
+            field_conflicts,
+            field_report,
+        } => {
             let service = open_service(vault).await?;
             let snapshot = service.index_snapshot();
-            let report = snapshot.frontmatter_report();
+            if field_report {
+                let draft = snapshot.infer_field_schema();
+                match fmt {
+                    OutputFormat::Json | OutputFormat::Ndjson => emit_json(&FieldReportOutput {
+                        field_report: draft.to_report(),
+                        fields: draft.fields,
+                    }),
+                    OutputFormat::Text | OutputFormat::Csv => print!("{}", draft.to_report()),
+                }
+                return Ok(());
+            }
+            let prefix = prefix.unwrap_or_default();
+            let in_scope = |p: &VaultPath| p.as_path().to_string_lossy().starts_with(&prefix);
+            let report = snapshot.frontmatter_report_with_prefix(&prefix);
 
             let missing: Vec<String> = snapshot
                 .notes_without_frontmatter()
+                .filter(|p| in_scope(p))
                 .take(limit)
                 .map(|p| p.as_str_lossy())
                 .collect();
 
             let broken: Vec<FrontmatterBroken> = snapshot
                 .notes_with_broken_frontmatter()
+                .filter(|(p, _)| in_scope(p))
                 .take(limit)
                 .map(|(p, err)| FrontmatterBroken {
                     path: p.as_str_lossy(),
@@ -1342,17 +2805,31 @@ async fn handle_check(
                 })
                 .collect();
 
+            let conflicts: Option<Vec<FieldConflict>> = field_conflicts.then(|| {
+                snapshot
+                    .field_source_conflicts()
+                    .into_iter()
+                    .filter(|(p, _)| in_scope(p))
+                    .take(limit)
+                    .map(|(p, key)| FieldConflict {
+                        path: p.as_str_lossy(),
+                        key,
+                    })
+                    .collect()
+            });
+
             match fmt {
-                OutputFormat::Json => {
+                OutputFormat::Json | OutputFormat::Ndjson => {
                     emit_json(&FrontmatterOutput {
                         notes_without_frontmatter: report.none,
                         notes_with_frontmatter_valid: report.valid,
                         notes_with_frontmatter_broken: report.broken,
                         missing,
                         broken,
+                        field_conflicts: conflicts,
                     });
                 }
-                OutputFormat::Text => {
+                OutputFormat::Text | OutputFormat::Csv => {
                     println!("summary");
                     println!("  notes_without_frontmatter: {}", report.none);
                     println!("  notes_with_frontmatter_valid: {}", report.valid);
@@ -1371,12 +2848,26 @@ async fn handle_check(
                             println!("- {}\t{}", b.path, b.error);
                         }
                     }
+
+                    if let Some(conflicts) = &conflicts {
+                        println!("\nfield_conflicts:");
+                        for c in conflicts {
+                            println!("- {}\t{}", c.path, c.key);
+                        }
+                    }
                 }
             }
         }
-        CheckCommand::Schema { severity, limit } => {
+        CheckCommand::Schema {
+            severity,
+            only_codes,
+            exclude_codes,
+            limit,
+            fail_on,
+        } => {
             let service = open_service(vault).await?;
             let report = service.schema_report();
+            let failed = fails(fail_on, schema_error_count(&report), report.warnings);
 
             let severity_filter = severity.map(Into::into);
             let violations: Vec<oxidian::SchemaViolationRecord> = report
@@ -1387,11 +2878,13 @@ async fn handle_check(
                         .as_ref()
                         .is_none_or(|s| &v.violation.severity == s)
                 })
+                .filter(|v| only_codes.is_empty() || only_codes.contains(&v.violation.code))
+                .filter(|v| !exclude_codes.contains(&v.violation.code))
                 .take(limit)
                 .collect();
 
             match fmt {
-                OutputFormat::Json => {
+                OutputFormat::Json | OutputFormat::Ndjson => {
                     emit_json(&SchemaCheckOutput {
                         status: report.status,
                         errors: report.errors,
@@ -1400,7 +2893,7 @@ async fn handle_check(
                         violations,
                     });
                 }
-                OutputFormat::Text => {
+                OutputFormat::Text | OutputFormat::Csv => {
                     println!("schema");
                     println!("  status: {}", format_schema_status(&report.status));
                     println!("  errors: {}", report.errors);
@@ -1428,11 +2921,129 @@ async fn handle_check(
                     }
                 }
             }
+            if failed {
+                exit_failed()?;
+            }
+        }
+        CheckCommand::Health { fail_on, signal } => {
+            let service = open_service(vault).await?;
+            let schema = service.schema_report();
+            let links = service.link_health_report()?;
+
+            let counts = |s: HealthSignalArg| signal.is_empty() || signal.contains(&s);
+            let mut errors = 0;
+            let mut warnings = 0;
+            if counts(HealthSignalArg::Schema) {
+                errors += schema_error_count(&schema);
+                warnings += schema.warnings;
+            }
+            if counts(HealthSignalArg::Links) {
+                errors += links.broken.len();
+            }
+            let failed = fails(fail_on, errors, warnings);
+
+            let out = HealthOutput {
+                schema_status: schema.status,
+                schema_errors: schema.errors,
+                schema_warnings: schema.warnings,
+                internal_occurrences: links.total_internal_occurrences,
+                broken_links: links.broken.len(),
+                failed,
+            };
+            match fmt {
+                OutputFormat::Json | OutputFormat::Ndjson => emit_json(&out),
+                OutputFormat::Text | OutputFormat::Csv => {
+                    println!("health");
+                    println!("  schema: {}", format_schema_status(&out.schema_status));
+                    println!("  schema_errors: {}", out.schema_errors);
+                    println!("  schema_warnings: {}", out.schema_warnings);
+                    println!("  internal_occurrences: {}", out.internal_occurrences);
+                    println!("  broken_links: {}", out.broken_links);
+                    if failed {
+                        println!("  failed: yes");
+                    }
+                }
+            }
+            if failed {
+                exit_failed()?;
+            }
+        }
+        CheckCommand::Duplicates {
+            merge_plan,
+            exact,
+            limit,
+        } => {
+            let service = open_service(vault).await?;
+            let snapshot = service.index_snapshot();
+            let groups = if exact {
+                snapshot.duplicate_contents()
+            } else {
+                snapshot.duplicate_basenames()
+            };
+            let duplicates: Vec<oxidian::DuplicateGroup> = groups.into_iter().take(limit).collect();
+            let plan: Option<Vec<oxidian::MergePlan>> = if merge_plan {
+                Some(
+                    snapshot
+                        .duplicate_merge_plan()?
+                        .into_iter()
+                        .take(limit)
+                        .collect(),
+                )
+            } else {
+                None
+            };
+
+            match fmt {
+                OutputFormat::Json | OutputFormat::Ndjson => {
+                    emit_json(&DuplicatesOutput {
+                        groups: duplicates.len(),
+                        duplicates,
+                        merge_plan: plan,
+                    });
+                }
+                OutputFormat::Text | OutputFormat::Csv => {
+                    println!("summary");
+                    println!("  duplicate_groups: {}", duplicates.len());
+
+                    if let Some(plan) = &plan {
+                        for group in plan {
+                            println!("\n{}:", group.name);
+                            for r in &group.renames {
+                                println!(
+                                    "- {} -> {}\tinbound={}",
+                                    r.path.as_str_lossy(),
+                                    r.suggested_path.as_str_lossy(),
+                                    r.inbound.len()
+                                );
+                                for b in &r.inbound {
+                                    println!(
+                                        "    {}:{}\traw={:?}",
+                                        b.source.as_str_lossy(),
+                                        b.link.location.line,
+                                        b.link.raw
+                                    );
+                                }
+                            }
+                        }
+                    } else {
+                        for group in &duplicates {
+                            println!("\n{}:", group.name);
+                            for p in &group.notes {
+                                println!("- {}", p.as_str_lossy());
+                            }
+                        }
+                    }
+                }
+            }
         }
-        CheckCommand::Similarity { min_score, top_k } => {
+        CheckCommand::Similarity {
+            min_score,
+            top_k,
+            min_shared_tags,
+        } => {
             #[cfg(not(feature = "similarity"))]
             {
-                let _ = (vault, fmt, quiet, min_score, top_k);
+                let _ = (vault, fmt, quiet, min_score, top_k, min_shared_tags);
                 anyhow::bail!("This command requires --features similarity");
             }
 
@@ -1443,7 +3054,8 @@ async fn handle_check(
                 progress(quiet, "index ready");
 
                 progress(quiet, "computing similarity report...");
-                let report = service.note_similarity_report()?;
+                let mut report = service.note_similarity_report()?;
+                service.with_index(|idx| report.retain_shared_tags(idx, min_shared_tags));
                 progress(
                     quiet,
                     &format!(
@@ -1454,8 +3066,8 @@ async fn handle_check(
                 );
 
                 match fmt {
-                    OutputFormat::Json => emit_json(&report),
-                    OutputFormat::Text => {
+                    OutputFormat::Json | OutputFormat::Ndjson => emit_json(&report),
+                    OutputFormat::Text | OutputFormat::Csv => {
                         println!("total_notes\t{}", report.total_notes);
                         println!("pairs_checked\t{}", report.pairs_checked);
                         for hit in &report.hits {
@@ -1475,10 +3087,31 @@ async fn handle_check(
     Ok(())
 }
 
+/// Writes index stats to `path` via a sibling temp file and rename, so readers never
+/// observe a partial snapshot.
+fn write_watch_snapshot(service: &VaultService, path: &Path) -> anyhow::Result<()> {
+    let snapshot = service.with_index(|idx| WatchSnapshot {
+        files: idx.all_files().count(),
+        notes: idx
+            .all_files()
+            .filter(|f| matches!(f.kind, FileKind::Markdown | FileKind::Canvas))
+            .count(),
+        tags: idx.all_tags().count(),
+        tasks: idx.all_tasks().count(),
+    });
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, serde_json::to_vec(&snapshot)?)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
 async fn handle_watch(
     vault: Option<PathBuf>,
     fmt: OutputFormat,
     quiet: bool,
+    snapshot_path: Option<PathBuf>,
 ) -> anyhow::Result<()> {
     let vault = Vault::open(require_vault(vault)?)?;
     let mut service = VaultService::new(vault)?;
@@ -1486,6 +3119,9 @@ async fn handle_watch(
     let mut rx = service.subscribe();
 
     service.start_watching().await?;
+    if let Some(path) = &snapshot_path {
+        write_watch_snapshot(&service, path)?;
+    }
     progress(quiet, "watching... (Ctrl-C to stop)");
 
     loop {
@@ -1493,12 +3129,16 @@ async fn handle_watch(
             _ = tokio::signal::ctrl_c() => break,
             ev = rx.recv() => {
                 match ev {
-                    Ok(ev) => match fmt {
-                        OutputFormat::Json => {
-                            println!("{}", serde_json::to_string(&ev).expect("json serialization"));
+                    Ok(ev) => {
+                        print_watch_event(fmt, &ev);
+                        if let Some(path) = &snapshot_path {
+                            // Coalesce the rest of this batch into one snapshot write.
+                            while let Ok(ev) = rx.try_recv() {
+                                print_watch_event(fmt, &ev);
+                            }
+                            write_watch_snapshot(&service, path)?;
                         }
-                        OutputFormat::Text => println!("{ev:?}"),
-                    },
+                    }
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
                         progress(quiet, &format!("(lagged {n} events)"));
                     }
@@ -1512,15 +3152,25 @@ async fn handle_watch(
     Ok(())
 }
 
+fn print_watch_event(fmt: OutputFormat, ev: &oxidian::VaultEvent) {
+    match fmt {
+        OutputFormat::Json | OutputFormat::Ndjson => {
+            println!("{}", serde_json::to_string(ev).expect("json serialization"));
+        }
+        OutputFormat::Text | OutputFormat::Csv => println!("{ev:?}"),
+    }
+}
+
 async fn handle_persist(
     vault: Option<PathBuf>,
     fmt: OutputFormat,
     quiet: bool,
     db: Option<PathBuf>,
+    full: bool,
 ) -> anyhow::Result<()> {
     #[cfg(not(feature = "sqlite"))]
     {
-        let _ = (vault, fmt, quiet, db);
+        let _ = (vault, fmt, quiet, db, full);
         anyhow::bail!("This command requires --features sqlite");
     }
 
@@ -1536,23 +3186,35 @@ async fn handle_persist(
             Some(p) => SqliteIndexStore::open_path(p)?,
             None => SqliteIndexStore::open_default(service.vault())?,
         };
-        store.write_full_index(service.vault(), &service.index_snapshot())?;
+        let sync = if full {
+            store.write_full_index(service.vault(), &service.index_snapshot())?;
+            None
+        } else {
+            Some(store.sync_index(service.vault(), &service.index_snapshot())?)
+        };
         let (files, notes, tags, tasks, links) = store.counts()?;
 
         match fmt {
-            OutputFormat::Json => {
+            OutputFormat::Json | OutputFormat::Ndjson => {
                 emit_json(&PersistOutput {
                     files,
                     notes,
                     tags,
                     tasks,
                     links,
+                    sync,
                 });
             }
-            OutputFormat::Text => {
+            OutputFormat::Text | OutputFormat::Csv => {
                 println!(
                     "persisted: files={files} notes={notes} tags={tags} tasks={tasks} links={links}"
                 );
+                if let Some(sync) = &sync {
+                    println!(
+                        "synced: upserted={} removed={} unchanged={}",
+                        sync.upserted, sync.removed, sync.unchanged
+                    );
+                }
             }
         }
 
@@ -1595,7 +3257,11 @@ async fn handle_schema(
     command: SchemaCommand,
 ) -> anyhow::Result<()> {
     match command {
-        SchemaCommand::Init { template, force } => {
+        SchemaCommand::Init {
+            template,
+            from_vault,
+            force,
+        } => {
             let vault = require_vault(vault)?;
             let schema_path = vault.join(".obsidian/oxidian/schema.toml");
             if schema_path.exists() && !force {
@@ -1609,20 +3275,23 @@ async fn handle_schema(
                 fs::create_dir_all(parent)?;
             }
 
-            let schema = generate_schema_template(template);
+            let (schema, template_name) = if from_vault {
+                (build_schema_from_vault(&vault)?, "from-vault".to_string())
+            } else {
+                (generate_schema_template(template), format!("{template:?}"))
+            };
             let contents = toml::to_string_pretty(&schema)
                 .map_err(|err| anyhow::anyhow!("failed to serialize schema: {err}"))?;
             fs::write(&schema_path, contents)?;
 
-            let template_name = format!("{template:?}");
             match fmt {
-                OutputFormat::Json => {
+                OutputFormat::Json | OutputFormat::Ndjson => {
                     emit_json(&SchemaInitOutput {
                         path: schema_path.display().to_string(),
                         template: template_name,
                     });
                 }
-                OutputFormat::Text => {
+                OutputFormat::Text | OutputFormat::Csv => {
                     println!(
                         "schema written to {} (template: {})",
                         schema_path.display(),
@@ -1631,6 +3300,38 @@ async fn handle_schema(
                 }
             }
         }
+        SchemaCommand::Lint => {
+            let service = open_service(vault).await?;
+            match service.schema_status() {
+                oxidian::SchemaStatus::Loaded { .. } => {}
+                oxidian::SchemaStatus::Disabled => {
+                    anyhow::bail!("no schema found; run `oxi schema init` first")
+                }
+                oxidian::SchemaStatus::Error { error, .. } => {
+                    anyhow::bail!("invalid schema: {error}")
+                }
+            }
+            let lints = service.schema_lint();
+
+            match fmt {
+                OutputFormat::Json | OutputFormat::Ndjson => emit_json(&SchemaLintOutput {
+                    count: lints.len(),
+                    lints,
+                }),
+                OutputFormat::Text | OutputFormat::Csv => {
+                    println!("summary");
+                    println!("  lints: {}", lints.len());
+                    for l in &lints {
+                        println!(
+                            "- {}\t{}\t{}",
+                            l.code,
+                            l.scope.as_deref().unwrap_or("-"),
+                            l.message
+                        );
+                    }
+                }
+            }
+        }
     }
 
     Ok(())
@@ -1670,6 +3371,41 @@ fn generate_schema_template(template: SchemaTemplate) -> Schema {
     }
 }
 
+/// One warn-on-unmatched notes scope per top-level folder, skipping ignored and hidden ones.
+fn build_schema_from_vault(root: &Path) -> anyhow::Result<Schema> {
+    let vault = Vault::open(root)?;
+    let mut folders = Vec::new();
+    for entry in fs::read_dir(vault.root())? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') || vault.is_ignored_rel(Path::new(&name)) {
+            continue;
+        }
+        folders.push(name);
+    }
+
+    let scopes = folders
+        .iter()
+        .map(|name| {
+            let (id, scope) = scope_notes(name, None, None, false, UnmatchedBehavior::Warn);
+            (id.to_string(), scope)
+        })
+        .collect();
+    Ok(Schema {
+        version: 1,
+        types: BTreeMap::new(),
+        aliases: BTreeMap::new(),
+        predicates: BTreeMap::new(),
+        vault: VaultSchema {
+            scopes,
+            ..VaultSchema::default()
+        },
+    })
+}
+
 fn build_para_schema() -> Schema {
     Schema {
         version: 1,
@@ -1900,6 +3636,7 @@ fn build_kg_schema() -> Schema {
                                 severity: SchemaSeverity::Error,
                             }),
                             require_any: None,
+                            timestamps: None,
                         }),
                         ..ScopeDef::default()
                     },
@@ -1963,6 +3700,7 @@ fn build_kg_memory_schema() -> Schema {
                     types: sub_types,
                     severity: SchemaSeverity::Error,
                 }),
+                timestamps: None,
             }),
             ..ScopeDef::default()
         },
@@ -2088,6 +3826,7 @@ fn scope_notes_typed<'a>(
                     severity: SchemaSeverity::Warn,
                 }),
                 require_any: None,
+                timestamps: None,
             }),
             ..ScopeDef::default()
         },
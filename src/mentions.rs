@@ -1,6 +1,7 @@
 use std::collections::BTreeSet;
 
-use crate::{Error, Vault, VaultIndex, VaultPath};
+use crate::parse::CodeBlocks;
+use crate::{Error, LinkResolver, ResolveResult, Tag, Vault, VaultIndex, VaultPath};
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct UnlinkedMention {
@@ -11,7 +12,65 @@ pub struct UnlinkedMention {
     pub line_text: String,
 }
 
+/// An unlinked mention located precisely enough to rewrite it as a link.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MentionSuggestion {
+    pub source: VaultPath,
+    pub target: VaultPath,
+    pub line: u32,
+    /// 1-based byte column where the mention starts.
+    pub column: u32,
+    /// 1-based byte column just past the mention.
+    pub end_column: u32,
+    /// The mention as written in the source.
+    pub text: String,
+    /// Wikilink to put in place of `text`.
+    pub replacement: String,
+}
+
+/// A note related to a source note by shared tags that the source does not link to yet.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct LinkCandidate {
+    pub path: VaultPath,
+    pub shared_tags: Vec<Tag>,
+}
+
 impl VaultIndex {
+    /// Notes sharing at least one tag with `source` that none of its outgoing links
+    /// resolve to, most shared tags first.
+    pub fn link_candidates(&self, source: &VaultPath) -> Vec<LinkCandidate> {
+        let Some(note) = self.note(source) else {
+            return Vec::new();
+        };
+        let resolver = self.link_resolver();
+        let linked: BTreeSet<VaultPath> = self
+            .outgoing_links(source)
+            .filter_map(|t| match resolver.resolve_link_target(t, source) {
+                ResolveResult::Resolved(p) => Some(p),
+                _ => None,
+            })
+            .collect();
+
+        let mut out: Vec<LinkCandidate> = self
+            .notes_iter()
+            .filter(|(path, _)| *path != source && !linked.contains(*path))
+            .filter_map(|(path, other)| {
+                let shared: Vec<Tag> = note.tags.intersection(&other.tags).cloned().collect();
+                (!shared.is_empty()).then(|| LinkCandidate {
+                    path: path.clone(),
+                    shared_tags: shared,
+                })
+            })
+            .collect();
+        out.sort_by(|a, b| {
+            b.shared_tags
+                .len()
+                .cmp(&a.shared_tags.len())
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        out
+    }
+
     pub fn unlinked_mentions(
         &self,
         vault: &Vault,
@@ -34,7 +93,8 @@ impl VaultIndex {
             }
             let abs = vault.to_abs(source);
             let text = std::fs::read_to_string(&abs).map_err(|e| Error::io(&abs, e))?;
-            for m in scan_mentions_in_text(source, target, &terms, &text) {
+            let indented_code = vault.config().indented_code_blocks;
+            for m in scan_mentions_in_text(source, target, &terms, &text, indented_code) {
                 out.push(m);
                 if out.len() >= limit {
                     return Ok(out);
@@ -44,6 +104,79 @@ impl VaultIndex {
 
         Ok(out)
     }
+
+    /// Like [`VaultIndex::unlinked_mentions`], but reports every non-overlapping
+    /// occurrence with its column span and a `[[...]]` replacement. Nothing is written.
+    ///
+    /// The replacement links by file stem when that resolves to `target` from the source,
+    /// otherwise by extensionless path, and keeps the original wording as display text
+    /// when it differs.
+    pub fn mention_suggestions(
+        &self,
+        vault: &Vault,
+        target: &VaultPath,
+        limit: usize,
+    ) -> crate::Result<Vec<MentionSuggestion>> {
+        let Some(target_note) = self.note(target) else {
+            return Ok(Vec::new());
+        };
+
+        let terms = mention_terms(target, target_note);
+        if terms.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let resolver = LinkResolver::new(self);
+        let stem = target
+            .as_path()
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_string();
+        let by_path = target
+            .as_path()
+            .with_extension("")
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let mut out = Vec::new();
+        for (source, _note) in self.notes_iter() {
+            if source == target {
+                continue;
+            }
+            let link_ref = match resolver.resolve_internal(&stem, source) {
+                ResolveResult::Resolved(p) if &p == target => stem.as_str(),
+                _ => by_path.as_str(),
+            };
+
+            let abs = vault.to_abs(source);
+            let text = std::fs::read_to_string(&abs).map_err(|e| Error::io(&abs, e))?;
+            for (line_no, line) in mention_lines(&text, vault.config().indented_code_blocks) {
+                for (start, end) in mention_spans(line, &terms) {
+                    let matched = &line[start..end];
+                    let replacement = if matched == link_ref {
+                        format!("[[{link_ref}]]")
+                    } else {
+                        format!("[[{link_ref}|{matched}]]")
+                    };
+                    out.push(MentionSuggestion {
+                        source: source.clone(),
+                        target: target.clone(),
+                        line: line_no,
+                        column: (start + 1) as u32,
+                        end_column: (end + 1) as u32,
+                        text: matched.to_string(),
+                        replacement,
+                    });
+                    if out.len() >= limit {
+                        return Ok(out);
+                    }
+                }
+            }
+        }
+
+        Ok(out)
+    }
 }
 
 fn mention_terms(target: &VaultPath, note: &crate::NoteMeta) -> BTreeSet<String> {
@@ -72,22 +205,10 @@ fn scan_mentions_in_text(
     target: &VaultPath,
     terms: &BTreeSet<String>,
     text: &str,
+    indented_code: bool,
 ) -> Vec<UnlinkedMention> {
     let mut out = Vec::new();
-    let (body, body_start_line) = split_frontmatter_text(text);
-
-    let mut in_fenced = false;
-    for (ix, line) in body.lines().enumerate() {
-        let line_no = body_start_line + ix as u32;
-        let t = line.trim_start();
-        if t.starts_with("```") {
-            in_fenced = !in_fenced;
-            continue;
-        }
-        if in_fenced {
-            continue;
-        }
-
+    for (line_no, line) in mention_lines(text, indented_code) {
         let cleaned = strip_link_spans(line);
         let hay = cleaned.to_lowercase();
 
@@ -110,6 +231,55 @@ fn scan_mentions_in_text(
     out
 }
 
+/// Body lines outside frontmatter and code blocks, with 1-based line numbers.
+fn mention_lines(text: &str, indented_code: bool) -> Vec<(u32, &str)> {
+    let (body, body_start_line) = split_frontmatter_text(text);
+    let mut out = Vec::new();
+    let mut code = CodeBlocks::new(indented_code);
+    for (ix, line) in body.lines().enumerate() {
+        if !code.skip(line) {
+            out.push((body_start_line + ix as u32, line));
+        }
+    }
+    out
+}
+
+/// Byte spans in `line` of non-overlapping term matches outside links, earliest first;
+/// at the same start the longer term wins.
+fn mention_spans(line: &str, terms: &BTreeSet<String>) -> Vec<(usize, usize)> {
+    let (hay, offsets) = lowercase_with_offsets(&strip_link_spans(line));
+    let mut spans: Vec<(usize, usize)> = terms
+        .iter()
+        .filter(|t| !t.is_empty())
+        .flat_map(|t| wordish_matches(&hay, t))
+        .map(|(i, j)| (offsets[i], offsets[j]))
+        .collect();
+    spans.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+
+    let mut out: Vec<(usize, usize)> = Vec::new();
+    for span in spans {
+        if out.last().is_none_or(|last| span.0 >= last.1) {
+            out.push(span);
+        }
+    }
+    out
+}
+
+/// Lowercases `s`, mapping each output byte back to the byte offset of its source char.
+/// The map has one extra trailing entry for `s.len()`.
+fn lowercase_with_offsets(s: &str) -> (String, Vec<usize>) {
+    let mut out = String::with_capacity(s.len());
+    let mut offsets = Vec::with_capacity(s.len() + 1);
+    for (off, c) in s.char_indices() {
+        for lc in c.to_lowercase() {
+            out.push(lc);
+            offsets.resize(out.len(), off);
+        }
+    }
+    offsets.push(s.len());
+    (out, offsets)
+}
+
 fn split_frontmatter_text(content: &str) -> (&str, u32) {
     let Some(rest) = content
         .strip_prefix("---\n")
@@ -143,8 +313,9 @@ fn split_frontmatter_text(content: &str) -> (&str, u32) {
 }
 
 fn strip_link_spans(line: &str) -> String {
-    // Replace link syntaxes with spaces to avoid matching mentions inside links.
-    let mut out = String::with_capacity(line.len());
+    // Blank out link syntaxes to avoid matching mentions inside links. Byte offsets are
+    // preserved so matches map back onto the original line.
+    let mut out: Vec<u8> = Vec::with_capacity(line.len());
     let bytes = line.as_bytes();
     let mut i = 0usize;
     while i < bytes.len() {
@@ -154,7 +325,7 @@ fn strip_link_spans(line: &str) -> String {
             && bytes[i + 1] == b'['
             && let Some(end) = find_bytes(bytes, i + 2, b']', b']')
         {
-            out.push(' ');
+            blank(&mut out, i, end + 2);
             i = end + 2;
             continue;
         }
@@ -164,7 +335,7 @@ fn strip_link_spans(line: &str) -> String {
             && bytes[i + 2] == b'['
             && let Some(end) = find_bytes(bytes, i + 3, b']', b']')
         {
-            out.push(' ');
+            blank(&mut out, i, end + 2);
             i = end + 2;
             continue;
         }
@@ -178,7 +349,7 @@ fn strip_link_spans(line: &str) -> String {
                     && bytes[j + 1] == b'('
                     && let Some(close_paren) = bytes[j + 2..].iter().position(|b| *b == b')')
                 {
-                    out.push(' ');
+                    blank(&mut out, i, j + 2 + close_paren + 1);
                     i = j + 2 + close_paren + 1;
                     continue;
                 }
@@ -189,15 +360,20 @@ fn strip_link_spans(line: &str) -> String {
         if bytes[i] == b'<'
             && let Some(off) = bytes[i + 1..].iter().position(|b| *b == b'>')
         {
-            out.push(' ');
+            blank(&mut out, i, i + 1 + off + 1);
             i = i + 1 + off + 1;
             continue;
         }
 
-        out.push(bytes[i] as char);
+        out.push(bytes[i]);
         i += 1;
     }
-    out
+    // Spans start and end on ASCII delimiters, so the kept bytes stay valid UTF-8.
+    String::from_utf8(out).unwrap_or_default()
+}
+
+fn blank(out: &mut Vec<u8>, from: usize, to: usize) {
+    out.resize(out.len() + (to - from), b' ');
 }
 
 fn find_bytes(bytes: &[u8], from: usize, a: u8, b: u8) -> Option<usize> {
@@ -212,16 +388,21 @@ fn find_bytes(bytes: &[u8], from: usize, a: u8, b: u8) -> Option<usize> {
 }
 
 fn find_wordish(hay: &str, needle: &str) -> bool {
+    !wordish_matches(hay, needle).is_empty()
+}
+
+fn wordish_matches(hay: &str, needle: &str) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
     let mut start = 0usize;
     while let Some(pos) = hay[start..].find(needle) {
         let i = start + pos;
         let j = i + needle.len();
         if has_word_boundary(hay, i, j, needle) {
-            return true;
+            out.push((i, j));
         }
-        start = i + 1;
+        start = i + hay[i..].chars().next().map_or(1, char::len_utf8);
     }
-    false
+    out
 }
 
 fn has_word_boundary(hay: &str, i: usize, j: usize, needle: &str) -> bool {
@@ -1,16 +1,20 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::{Link, LinkKind, LinkLocation, LinkTarget, Subpath, Tag, VaultPath};
+use crate::config::TagCharset;
+use crate::{Link, LinkKind, LinkLocation, LinkTarget, Subpath, Tag, VaultConfig, VaultPath};
 
 #[derive(Debug, Clone)]
 pub(crate) struct ParsedNote {
     pub title: String,
     pub tags: BTreeSet<Tag>,
+    pub frontmatter_tags: BTreeSet<Tag>,
+    pub inline_tags: BTreeSet<Tag>,
     pub links: BTreeSet<LinkTarget>,
     pub link_occurrences: Vec<Link>,
     pub frontmatter: FrontmatterParse,
     pub inline_fields: Vec<(String, String)>,
     pub tasks: Vec<ParsedTask>,
+    pub language: Option<String>,
 }
 
 type InlineExtraction = (
@@ -26,6 +30,10 @@ pub(crate) struct ParsedTask {
     pub line: u32,
     pub status: crate::TaskStatus,
     pub text: String,
+    pub due: Option<crate::Date>,
+    pub done: Option<crate::Date>,
+    pub priority: crate::TaskPriority,
+    pub tags: BTreeSet<Tag>,
 }
 
 #[derive(Debug, Clone)]
@@ -35,15 +43,25 @@ pub(crate) enum FrontmatterParse {
     Broken { error: String },
 }
 
-pub(crate) fn parse_markdown_note(path: &VaultPath, content: &str) -> ParsedNote {
+pub(crate) fn parse_markdown_note(
+    path: &VaultPath,
+    content: &str,
+    cfg: &VaultConfig,
+) -> ParsedNote {
     let (frontmatter, body, body_start_line) = split_frontmatter(content);
-    let mut tags = BTreeSet::new();
-    if let FrontmatterParse::Valid(fm) = &frontmatter {
-        tags.extend(extract_frontmatter_tags(fm));
-    }
+    let frontmatter_tags = match &frontmatter {
+        FrontmatterParse::Valid(fm) => extract_frontmatter_tags(fm),
+        _ => BTreeSet::new(),
+    };
     let (inline_tags, links, link_occurrences, inline_fields, tasks) =
-        extract_inline_tags_links_fields(body, body_start_line);
-    tags.extend(inline_tags);
+        extract_inline_tags_links_fields(
+            body,
+            body_start_line,
+            &cfg.tag_charset,
+            cfg.indented_code_blocks,
+            &cfg.task_marks,
+        );
+    let tags = frontmatter_tags.union(&inline_tags).cloned().collect();
 
     let title = extract_title(
         path,
@@ -52,67 +70,84 @@ pub(crate) fn parse_markdown_note(path: &VaultPath, content: &str) -> ParsedNote
             _ => None,
         },
         body,
+        cfg.indented_code_blocks,
     );
 
+    #[cfg(feature = "language")]
+    let language = cfg
+        .detect_language
+        .then(|| crate::language::detect_language(body))
+        .flatten();
+    #[cfg(not(feature = "language"))]
+    let language = None;
+
     ParsedNote {
         title,
         tags,
+        frontmatter_tags,
+        inline_tags,
         links,
         link_occurrences,
         frontmatter,
         inline_fields,
         tasks,
+        language,
     }
 }
 
 fn split_frontmatter(content: &str) -> (FrontmatterParse, &str, u32) {
-    let trimmed = content;
-    let Some(rest) = trimmed
-        .strip_prefix("---\n")
-        .or_else(|| trimmed.strip_prefix("---\r\n"))
-    else {
+    if strip_opening_fence(content).is_none() {
         return (FrontmatterParse::None, content, 1);
+    }
+    let Some((fm_text, body)) = frontmatter_split(content) else {
+        return (
+            FrontmatterParse::Broken {
+                error: "frontmatter fence not closed".to_string(),
+            },
+            content,
+            1,
+        );
     };
 
-    // Find a closing fence on its own line.
-    // We accept either "---\n" or "---\r\n".
-    let mut idx = 0usize;
+    let start_line = 1 + count_newlines(&content[..content.len() - body.len()]) as u32;
+    match serde_yaml::from_str::<serde_yaml::Value>(fm_text) {
+        Ok(v) => (FrontmatterParse::Valid(v), body, start_line),
+        Err(err) => (
+            FrontmatterParse::Broken {
+                error: err.to_string(),
+            },
+            body,
+            start_line,
+        ),
+    }
+}
+
+/// Splits a leading `---` block into its YAML text and the body after the closing fence.
+///
+/// Either fence may end in `\n` or `\r\n`. Returns `None` when the content has no opening
+/// fence or the block is never closed.
+pub(crate) fn frontmatter_split(content: &str) -> Option<(&str, &str)> {
+    let rest = strip_opening_fence(content)?;
     let bytes = rest.as_bytes();
+    let mut idx = 0usize;
     while idx < bytes.len() {
-        // Find end of line
         let line_end = match memchr::memchr(b'\n', &bytes[idx..]) {
             Some(off) => idx + off + 1,
             None => bytes.len(),
         };
         let line = &rest[idx..line_end];
-        let line_trim = line.trim_end_matches(['\r', '\n']);
-        if line_trim == "---" {
-            let fm_text = &rest[..idx];
-            let body = &rest[line_end..];
-            let start_line = 1 + count_newlines(&content[..content.len() - body.len()]) as u32;
-            match serde_yaml::from_str::<serde_yaml::Value>(fm_text) {
-                Ok(v) => return (FrontmatterParse::Valid(v), body, start_line),
-                Err(err) => {
-                    return (
-                        FrontmatterParse::Broken {
-                            error: err.to_string(),
-                        },
-                        body,
-                        start_line,
-                    );
-                }
-            }
+        if line.trim_end_matches(['\r', '\n']) == "---" {
+            return Some((&rest[..idx], &rest[line_end..]));
         }
         idx = line_end;
     }
+    None
+}
 
-    (
-        FrontmatterParse::Broken {
-            error: "frontmatter fence not closed".to_string(),
-        },
-        content,
-        1,
-    )
+fn strip_opening_fence(content: &str) -> Option<&str> {
+    content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
 }
 
 fn extract_frontmatter_tags(fm: &serde_yaml::Value) -> BTreeSet<Tag> {
@@ -135,28 +170,35 @@ fn extract_tags_from_yaml_value(v: &serde_yaml::Value) -> BTreeSet<Tag> {
     let mut out = BTreeSet::new();
     match v {
         serde_yaml::Value::Sequence(seq) => {
+            // Each list item is one tag; only scalar strings carry several.
             for item in seq {
                 if let Some(tag) = item.as_str().and_then(normalize_tag) {
                     out.insert(tag);
                 }
             }
         }
-        serde_yaml::Value::String(s) => {
-            for part in s
-                .split(|c: char| c.is_whitespace() || c == ',')
-                .filter(|p| !p.is_empty())
-            {
-                if let Some(tag) = normalize_tag(part) {
-                    out.insert(tag);
-                }
-            }
-        }
+        serde_yaml::Value::String(s) => out.extend(split_frontmatter_tag_string(s)),
         _ => {}
     }
     out
 }
 
-fn extract_title(path: &VaultPath, fm: Option<&serde_yaml::Value>, body: &str) -> String {
+/// Splits a scalar frontmatter tag string such as `"#a #b"`, `"a, b"` or `"#a#b"` into tags.
+///
+/// `#` can't occur inside a tag, so it is treated as a separator like whitespace and commas;
+/// stray quotes from double-quoted YAML (`'"#a"'`) are dropped as well.
+fn split_frontmatter_tag_string(s: &str) -> impl Iterator<Item = Tag> + '_ {
+    s.split(|c: char| c.is_whitespace() || matches!(c, ',' | '#' | '"' | '\''))
+        .filter(|p| !p.is_empty())
+        .filter_map(normalize_tag)
+}
+
+fn extract_title(
+    path: &VaultPath,
+    fm: Option<&serde_yaml::Value>,
+    body: &str,
+    indented_code: bool,
+) -> String {
     if let Some(title) = fm
         .and_then(|v| v.as_mapping())
         .and_then(|map| map.get(serde_yaml::Value::String("title".into())))
@@ -168,13 +210,9 @@ fn extract_title(path: &VaultPath, fm: Option<&serde_yaml::Value>, body: &str) -
     }
 
     // First H1.
-    let mut in_fenced = false;
+    let mut code = CodeBlocks::new(indented_code);
     for line in body.lines() {
-        if is_fence(line) {
-            in_fenced = !in_fenced;
-            continue;
-        }
-        if in_fenced {
+        if code.skip(line) {
             continue;
         }
         if let Some(h) = line.strip_prefix("# ") {
@@ -193,42 +231,53 @@ fn extract_title(path: &VaultPath, fm: Option<&serde_yaml::Value>, body: &str) -
         .to_string()
 }
 
-fn extract_inline_tags_links_fields(body: &str, body_start_line: u32) -> InlineExtraction {
+fn extract_inline_tags_links_fields(
+    body: &str,
+    body_start_line: u32,
+    charset: &TagCharset,
+    indented_code: bool,
+    task_marks: &BTreeMap<char, crate::TaskStatus>,
+) -> InlineExtraction {
     let mut tags = BTreeSet::new();
     let mut links = BTreeSet::new();
     let mut link_occurrences = Vec::new();
     let mut fields: Vec<(String, String)> = Vec::new();
     let mut tasks: Vec<ParsedTask> = Vec::new();
-    let mut in_fenced = false;
+    let mut code = CodeBlocks::new(indented_code);
 
     for (line_ix, line) in body.lines().enumerate() {
-        if is_fence(line) {
-            in_fenced = !in_fenced;
-            continue;
-        }
-        if in_fenced {
+        if code.skip(line) {
             continue;
         }
 
-        tags.extend(extract_inline_tags_from_line(line));
+        let line_tags = extract_inline_tags_from_line(line, charset);
         let (targets, occs) = extract_links_from_line(line, body_start_line + line_ix as u32);
         links.extend(targets);
         link_occurrences.extend(occs);
         fields.extend(extract_inline_fields_from_line(line));
 
-        if let Some((status, text)) = parse_task_line(line) {
+        if let Some((status, text)) = parse_task_line(line, task_marks) {
+            let (text, priority) = strip_task_priority(&text);
             tasks.push(ParsedTask {
                 line: body_start_line + line_ix as u32,
                 status,
+                due: task_due(&text),
+                done: task_done(&text),
                 text,
+                priority,
+                tags: line_tags.clone(),
             });
         }
+        tags.extend(line_tags);
     }
 
     (tags, links, link_occurrences, fields, tasks)
 }
 
-fn parse_task_line(line: &str) -> Option<(crate::TaskStatus, String)> {
+fn parse_task_line(
+    line: &str,
+    task_marks: &BTreeMap<char, crate::TaskStatus>,
+) -> Option<(crate::TaskStatus, String)> {
     let s = line.trim_start();
     let mut rest = s;
 
@@ -262,21 +311,70 @@ fn parse_task_line(line: &str) -> Option<(crate::TaskStatus, String)> {
         rest = &rest[i + 2..];
     }
 
-    let bytes = rest.as_bytes();
-    if bytes.len() < 3 || bytes[0] != b'[' || bytes[2] != b']' {
-        return None;
-    }
-    let mark = bytes[1] as char;
-    let status = match mark {
-        ' ' => crate::TaskStatus::Todo,
-        'x' | 'X' => crate::TaskStatus::Done,
-        '>' => crate::TaskStatus::InProgress,
-        '-' => crate::TaskStatus::Cancelled,
-        '?' => crate::TaskStatus::Blocked,
-        _ => return None,
+    let mut chars = rest.strip_prefix('[')?.chars();
+    let mark = chars.next()?;
+    let after = chars.as_str().strip_prefix(']')?;
+    let status = match task_marks.get(&mark) {
+        Some(status) => *status,
+        None => match mark {
+            ' ' => crate::TaskStatus::Todo,
+            'x' | 'X' => crate::TaskStatus::Done,
+            '>' => crate::TaskStatus::InProgress,
+            '-' => crate::TaskStatus::Cancelled,
+            '?' => crate::TaskStatus::Blocked,
+            _ => return None,
+        },
     };
-    let text = rest[3..].trim_start();
-    Some((status, text.to_string()))
+    Some((status, after.trim_start().to_string()))
+}
+
+const PRIORITY_MARKERS: [(char, crate::TaskPriority); 5] = [
+    ('🔺', crate::TaskPriority::Highest),
+    ('⏫', crate::TaskPriority::High),
+    ('🔼', crate::TaskPriority::Medium),
+    ('🔽', crate::TaskPriority::Low),
+    ('⏬', crate::TaskPriority::Lowest),
+];
+
+/// Removes the first priority emoji (and a trailing variation selector) from task text.
+fn strip_task_priority(text: &str) -> (String, crate::TaskPriority) {
+    let Some((ix, marker, priority)) = text.char_indices().find_map(|(ix, c)| {
+        PRIORITY_MARKERS
+            .iter()
+            .find(|(m, _)| *m == c)
+            .map(|(m, p)| (ix, *m, *p))
+    }) else {
+        return (text.to_string(), crate::TaskPriority::None);
+    };
+    let before = text[..ix].trim_end();
+    let after = text[ix + marker.len_utf8()..].trim_start_matches('\u{fe0f}');
+    let after = after.trim_start();
+    let joined = if before.is_empty() || after.is_empty() {
+        format!("{before}{after}")
+    } else {
+        format!("{before} {after}")
+    };
+    (joined, priority)
+}
+
+/// Due date written in task text, in Tasks (`📅 date`) or Dataview (`[due:: date]`) style.
+fn task_due(text: &str) -> Option<crate::Date> {
+    task_date(text, '📅', "due::")
+}
+
+/// Completion date, in Tasks (`✅ date`) or Dataview (`[completion:: date]`) style.
+fn task_done(text: &str) -> Option<crate::Date> {
+    task_date(text, '✅', "completion::")
+}
+
+fn task_date(text: &str, emoji: char, field: &str) -> Option<crate::Date> {
+    let rest = if let Some((_, rest)) = text.split_once(emoji) {
+        rest
+    } else {
+        let ix = text.find(field)?;
+        &text[ix + field.len()..]
+    };
+    crate::Date::parse_iso8601(rest.trim_start().get(..10)?)
 }
 
 fn count_newlines(s: &str) -> usize {
@@ -404,61 +502,133 @@ fn is_field_key_char(c: char) -> bool {
     c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '/' | '.')
 }
 
-fn is_fence(line: &str) -> bool {
-    let t = line.trim_start();
-    t.starts_with("```")
+/// Line-by-line tracker of fenced (```` ``` ```` or `~~~`) and, optionally, indented code.
+pub(crate) struct CodeBlocks {
+    indented: bool,
+    /// Fence character and run length of the open fenced block.
+    fence: Option<(char, usize)>,
+    in_indented: bool,
+    prev_blank: bool,
+    /// The last non-blank text line belonged to a list, so indentation continues it.
+    in_list: bool,
 }
 
-fn extract_inline_tags_from_line(line: &str) -> BTreeSet<Tag> {
-    let mut out = BTreeSet::new();
+impl CodeBlocks {
+    pub(crate) fn new(indented: bool) -> Self {
+        Self {
+            indented,
+            fence: None,
+            in_indented: false,
+            prev_blank: true,
+            in_list: false,
+        }
+    }
 
-    let bytes = line.as_bytes();
-    let mut i = 0usize;
-    while i < bytes.len() {
-        if bytes[i] != b'#' {
-            i += 1;
-            continue;
+    /// Returns true when `line` is code or a fence delimiter and should not be scanned.
+    pub(crate) fn skip(&mut self, line: &str) -> bool {
+        if let Some((ch, len)) = self.fence {
+            // A closing fence repeats the opening character at least as many times.
+            if let Some((c, n, rest)) = fence_run(line)
+                && c == ch
+                && n >= len
+                && rest.trim().is_empty()
+            {
+                self.fence = None;
+                self.prev_blank = true;
+            }
+            return true;
         }
 
-        // Avoid headings like "# Title".
-        if i + 1 < bytes.len() && bytes[i + 1] == b' ' {
-            i += 1;
+        let blank = line.trim().is_empty();
+        let indented = line.starts_with("    ") || line.starts_with('\t');
+        if self.in_indented {
+            if blank || indented {
+                return true;
+            }
+            self.in_indented = false;
+        }
+        if self.indented && indented && !blank && self.prev_blank && !self.in_list {
+            self.in_indented = true;
+            return true;
+        }
+
+        // Backtick fences may not carry backticks in their info string (that is inline code).
+        if let Some((c, n, rest)) = fence_run(line)
+            && (c == '~' || !rest.contains('`'))
+        {
+            self.fence = Some((c, n));
+            return true;
+        }
+
+        if !blank {
+            self.in_list = is_list_item(line) || (self.in_list && indented);
+        }
+        self.prev_blank = blank;
+        false
+    }
+}
+
+/// Splits a potential fence into its character, run length and info string.
+fn fence_run(line: &str) -> Option<(char, usize, &str)> {
+    let t = line.trim_start();
+    let c = t.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let n = t.len() - t.trim_start_matches(c).len();
+    (n >= 3).then(|| (c, n, &t[n..]))
+}
+
+fn is_list_item(line: &str) -> bool {
+    let t = line.trim_start();
+    if t.starts_with("- ") || t.starts_with("* ") || t.starts_with("+ ") {
+        return true;
+    }
+    let digits = t.len() - t.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    digits > 0 && (t[digits..].starts_with(". ") || t[digits..].starts_with(") "))
+}
+
+fn extract_inline_tags_from_line(line: &str, charset: &TagCharset) -> BTreeSet<Tag> {
+    let mut out = BTreeSet::new();
+
+    let mut prev: Option<char> = None;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != '#' {
+            prev = Some(c);
             continue;
         }
 
         // Require a boundary before '#'.
-        if i > 0 {
-            let prev = bytes[i - 1] as char;
-            if prev.is_alphanumeric() || prev == '/' {
-                i += 1;
-                continue;
-            }
+        if prev.is_some_and(|p| p.is_alphanumeric() || p == '/') {
+            prev = Some(c);
+            continue;
         }
+        prev = Some(c);
 
-        let mut j = i + 1;
-        while j < bytes.len() {
-            let c = bytes[j] as char;
-            if is_tag_char(c) {
-                j += 1;
-            } else {
+        // Headings like "# Title" stop here: a space is never a tag char.
+        let start = i + 1;
+        let mut end = start;
+        while let Some(&(j, ch)) = chars.peek() {
+            if !charset.allows(ch) {
                 break;
             }
+            end = j + ch.len_utf8();
+            prev = Some(ch);
+            chars.next();
         }
 
-        if j > i + 1 {
-            let raw = &line[i + 1..j];
-            if let Some(tag) = normalize_tag(raw) {
-                out.insert(tag);
-            }
+        if end > start
+            && let Some(tag) = normalize_tag(&line[start..end])
+        {
+            out.insert(tag);
         }
-
-        i = j.max(i + 1);
     }
 
     out
 }
 
-fn extract_links_from_line(line: &str, line_no: u32) -> (BTreeSet<LinkTarget>, Vec<Link>) {
+pub(crate) fn extract_links_from_line(
+    line: &str,
+    line_no: u32,
+) -> (BTreeSet<LinkTarget>, Vec<Link>) {
     let mut targets = BTreeSet::new();
     let mut occs = Vec::new();
 
@@ -484,6 +654,15 @@ fn extract_wikilinks_and_embeds(line: &str, line_no: u32) -> (BTreeSet<LinkTarge
     let bytes = line.as_bytes();
     let mut i = 0usize;
     while i + 1 < bytes.len() {
+        if is_escaped(bytes, i) && matches!(bytes[i], b'!' | b'[') {
+            // `\![[x]]` is a literal `!` before a normal link; `\[[x]]` is no link at all.
+            i += if bytes[i] == b'[' && bytes[i + 1] == b'[' {
+                2
+            } else {
+                1
+            };
+            continue;
+        }
         let mut embed = false;
         let start = if bytes[i] == b'!'
             && i + 2 < bytes.len()
@@ -544,6 +723,10 @@ fn extract_markdown_links_and_embeds(
     let bytes = line.as_bytes();
     let mut i = 0usize;
     while i < bytes.len() {
+        if is_escaped(bytes, i) {
+            i += 1;
+            continue;
+        }
         let mut embed = false;
         let start = if bytes[i] == b'!' {
             embed = true;
@@ -604,6 +787,17 @@ fn extract_markdown_links_and_embeds(
     (targets, occs)
 }
 
+/// True when the byte at `ix` is preceded by an odd number of backslashes.
+fn is_escaped(bytes: &[u8], ix: usize) -> bool {
+    bytes[..ix]
+        .iter()
+        .rev()
+        .take_while(|&&b| b == b'\\')
+        .count()
+        % 2
+        == 1
+}
+
 fn extract_autourls(line: &str, line_no: u32) -> (BTreeSet<LinkTarget>, Vec<Link>) {
     let mut targets = BTreeSet::new();
     let mut occs = Vec::new();
@@ -670,7 +864,8 @@ fn normalize_wikilink_components(
     // Subpath: prefer block if present, else heading.
     let (target_raw, subpath) = if let Some((left, right)) = before_alias.split_once('^') {
         (
-            left.trim(),
+            // Obsidian writes block refs as `note#^id`.
+            left.trim().trim_end_matches('#').trim_end(),
             Some(Subpath::Block(right.trim().to_string())).filter(|sp| match sp {
                 Subpath::Block(b) => !b.is_empty(),
                 _ => true,
@@ -695,7 +890,7 @@ fn normalize_wikilink_components(
 
     Some((
         LinkTarget::Internal {
-            reference: target_raw.to_string(),
+            reference: strip_note_extension(target_raw).to_string(),
         },
         subpath,
         display,
@@ -724,7 +919,7 @@ fn normalize_markdown_target(raw: &str) -> Option<(LinkTarget, Option<Subpath>)>
         }
         return Some((
             LinkTarget::Internal {
-                reference: left.to_string(),
+                reference: strip_note_extension(left).to_string(),
             },
             Some(Subpath::Heading(right.to_string())).filter(|sp| match sp {
                 Subpath::Heading(h) => !h.is_empty(),
@@ -735,14 +930,26 @@ fn normalize_markdown_target(raw: &str) -> Option<(LinkTarget, Option<Subpath>)>
 
     Some((
         LinkTarget::Internal {
-            reference: s.to_string(),
+            reference: strip_note_extension(s).to_string(),
         },
         None,
     ))
 }
 
-fn is_tag_char(c: char) -> bool {
-    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '/')
+/// Drops a trailing `.md` so `[[Note]]` and `[..](Note.md)` share one target; the
+/// written form stays on [`Link::raw`](crate::Link::raw).
+fn strip_note_extension(reference: &str) -> &str {
+    match reference.len().checked_sub(3) {
+        Some(cut) if cut > 0 && reference.is_char_boundary(cut) => {
+            let (stem, ext) = reference.split_at(cut);
+            if ext.eq_ignore_ascii_case(".md") && !stem.ends_with('/') {
+                stem
+            } else {
+                reference
+            }
+        }
+        _ => reference,
+    }
 }
 
 fn normalize_tag(raw: &str) -> Option<Tag> {
@@ -754,7 +961,8 @@ fn normalize_tag(raw: &str) -> Option<Tag> {
     if s.is_empty() {
         return None;
     }
-    let s = s.trim_matches('/');
+    // Trailing dots are sentence punctuation, not part of the tag.
+    let s = s.trim_matches('/').trim_end_matches('.');
     let s = s.trim();
     if s.is_empty() {
         return None;
@@ -777,7 +985,12 @@ mod tests {
 
     fn parse(path: &str, content: &str) -> ParsedNote {
         let path = VaultPath::try_from(std::path::Path::new(path)).unwrap();
-        parse_markdown_note(&path, content)
+        parse_markdown_note(&path, content, &VaultConfig::default())
+    }
+
+    fn parse_with(path: &str, content: &str, cfg: &VaultConfig) -> ParsedNote {
+        let path = VaultPath::try_from(std::path::Path::new(path)).unwrap();
+        parse_markdown_note(&path, content, cfg)
     }
 
     #[test]
@@ -793,6 +1006,53 @@ mod tests {
         assert_eq!(note.title, "Hello");
     }
 
+    #[test]
+    fn hash_prefixed_frontmatter_tag_strings_are_normalized() {
+        let names = |content: &str| {
+            parse("notes/a.md", content)
+                .tags
+                .into_iter()
+                .map(|t| t.0)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("---\ntags: \"#a #b\"\n---\n"), vec!["a", "b"]);
+        assert_eq!(names("---\ntags: [\"#a\", \"#b\"]\n---\n"), vec!["a", "b"]);
+        assert_eq!(names("---\ntags: \"#a#b, #c\"\n---\n"), vec!["a", "b", "c"]);
+        assert_eq!(names("---\ntags: '\"#a\"'\n---\n"), vec!["a"]);
+    }
+
+    #[test]
+    fn frontmatter_tag_list_items_are_not_split() {
+        let names = |content: &str| {
+            parse("notes/a.md", content)
+                .tags
+                .into_iter()
+                .map(|t| t.0)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names("---\ntags:\n  - rock'n'roll\n  - \"#jazz\"\n---\n"),
+            vec!["jazz", "rock'n'roll"]
+        );
+        assert_eq!(
+            names("---\ntags: [\"my tag\", b]\n---\n"),
+            vec!["b", "my tag"]
+        );
+        assert_eq!(names("---\ntags: my tag\n---\n"), vec!["my", "tag"]);
+    }
+
+    #[test]
+    fn frontmatter_and_inline_tags_are_kept_apart() {
+        let note = parse(
+            "notes/a.md",
+            "---\ntags: [project, shared]\n---\nBody #shared #casual\n",
+        );
+        let names = |set: &BTreeSet<Tag>| set.iter().map(|t| t.0.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&note.frontmatter_tags), vec!["project", "shared"]);
+        assert_eq!(names(&note.inline_tags), vec!["casual", "shared"]);
+        assert_eq!(names(&note.tags), vec!["casual", "project", "shared"]);
+    }
+
     #[test]
     fn fenced_code_blocks_are_ignored() {
         let note = parse(
@@ -809,6 +1069,37 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn tilde_fences_are_ignored_until_a_matching_close() {
+        let note = parse(
+            "a.md",
+            "~~~~\n#hidden\n~~~\n```\n[[still-hidden]]\n~~~~\nafter #shown\n",
+        );
+        assert_eq!(note.tags, BTreeSet::from([Tag("shown".into())]));
+        assert!(note.links.is_empty());
+
+        // Inline triple-backtick code is not a fence.
+        let note = parse("a.md", "```inline``` #kept\n");
+        assert!(note.tags.contains(&Tag("kept".into())));
+    }
+
+    #[test]
+    fn indented_code_is_ignored_when_enabled() {
+        let content = "Intro #intro\n\n    #code [[code-link]]\n\n\tmore #code2\nBack #out\n\n- item\n    - [ ] nested #nested\n";
+        let cfg = VaultConfig {
+            indented_code_blocks: true,
+            ..VaultConfig::default()
+        };
+        let note = parse_with("a.md", content, &cfg);
+        let tags: Vec<&str> = note.tags.iter().map(|t| t.0.as_str()).collect();
+        assert_eq!(tags, vec!["intro", "nested", "out"]);
+        assert!(note.links.is_empty());
+        assert_eq!(note.tasks.len(), 1);
+
+        let note = parse("a.md", content);
+        assert!(note.tags.contains(&Tag("code".into())));
+    }
+
     #[test]
     fn headings_are_not_tags() {
         let note = parse("a.md", "# Title\n## Subtitle\n#tag\n");
@@ -830,6 +1121,75 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn block_refs_written_with_hash_caret_keep_a_clean_target() {
+        let note = parse("a.md", "See [[Note#^abc123]] and [[Other^def|label]].");
+        let targets: Vec<(&LinkTarget, Option<&Subpath>)> = note
+            .link_occurrences
+            .iter()
+            .map(|l| (&l.target, l.subpath.as_ref()))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                (
+                    &LinkTarget::Internal {
+                        reference: "Note".into()
+                    },
+                    Some(&Subpath::Block("abc123".into()))
+                ),
+                (
+                    &LinkTarget::Internal {
+                        reference: "Other".into()
+                    },
+                    Some(&Subpath::Block("def".into()))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn wikilink_and_markdown_link_share_target_without_extension() {
+        let note = parse(
+            "a.md",
+            "[[Note]] and [Note](Note.md) and [[Note.MD#Part]]\n",
+        );
+        let target = LinkTarget::Internal {
+            reference: "Note".into(),
+        };
+        assert_eq!(note.links, BTreeSet::from([target.clone()]));
+        assert!(note.link_occurrences.iter().all(|l| l.target == target));
+        assert!(
+            note.link_occurrences
+                .iter()
+                .any(|l| l.raw.contains("Note.md"))
+        );
+
+        let canvas = parse("a.md", "[[Board.canvas]]\n");
+        assert!(canvas.links.contains(&LinkTarget::Internal {
+            reference: "Board.canvas".into()
+        }));
+    }
+
+    #[test]
+    fn escaped_brackets_suppress_links() {
+        let x = LinkTarget::Internal {
+            reference: "x".into(),
+        };
+        assert!(parse("a.md", r"\[\[x\]\]").links.is_empty());
+        assert!(parse("a.md", r"\[[x]]").links.is_empty());
+        assert!(parse("a.md", r"\[label](x.md)").links.is_empty());
+        assert!(parse("a.md", "[[x]]").links.contains(&x));
+
+        // An escaped backslash does not escape the bracket.
+        assert!(parse("a.md", r"\\[[x]]").links.contains(&x));
+
+        // An escaped `!` leaves a plain (non-embed) link.
+        let note = parse("a.md", r"\![[x]]");
+        assert_eq!(note.link_occurrences.len(), 1);
+        assert!(!note.link_occurrences[0].embed);
+    }
+
     #[test]
     fn inline_fields_support_bare_and_bracketed_variants() {
         let note = parse(
@@ -873,4 +1233,65 @@ mod tests {
         assert_eq!(note.tasks[3].status, crate::TaskStatus::Cancelled);
         assert_eq!(note.tasks[4].status, crate::TaskStatus::Blocked);
     }
+
+    #[test]
+    fn configured_unicode_marks_map_to_statuses() {
+        let body = "- [☑] shipped\n- [✅] filed\n- [x] plain\n";
+        assert_eq!(parse("a.md", body).tasks.len(), 1);
+
+        let mut cfg = VaultConfig::default();
+        cfg.task_marks.insert('☑', crate::TaskStatus::Done);
+        cfg.task_marks.insert('✅', crate::TaskStatus::Done);
+        let note = parse_with("a.md", body, &cfg);
+        assert_eq!(note.tasks.len(), 3);
+        assert_eq!(note.tasks[0].status, crate::TaskStatus::Done);
+        assert_eq!(note.tasks[0].text, "shipped");
+        assert_eq!(note.tasks[1].status, crate::TaskStatus::Done);
+        assert_eq!(note.tasks[1].text, "filed");
+    }
+
+    #[test]
+    fn crlf_line_numbers_match_lf() {
+        let lf = "---\nkey: value\n---\n\nSee [[other]].\n- [ ] todo\n";
+        let crlf = lf.replace('\n', "\r\n");
+        for content in [lf, crlf.as_str()] {
+            let note = parse("a.md", content);
+            assert!(matches!(note.frontmatter, FrontmatterParse::Valid(_)));
+            assert_eq!(note.link_occurrences[0].location.line, 5);
+            assert_eq!(note.tasks[0].line, 6);
+            assert_eq!(note.tasks[0].text, "todo");
+        }
+    }
+
+    #[test]
+    fn unicode_tags_are_kept_whole_by_default() {
+        let note = parse("a.md", "Notes on #café and #日本語/メモ here.\n");
+        let tags: Vec<_> = note.tags.iter().map(|t| t.0.as_str()).collect();
+        assert!(tags.contains(&"café"));
+        assert!(tags.contains(&"日本語/メモ"));
+    }
+
+    #[test]
+    fn dotted_tags_require_configured_charset() {
+        let body = "Released #v1.2.\n";
+
+        let note = parse("a.md", body);
+        let tags: Vec<_> = note.tags.iter().map(|t| t.0.as_str()).collect();
+        assert_eq!(tags, vec!["v1"]);
+
+        let mut cfg = VaultConfig::default();
+        cfg.tag_charset.extra.push('.');
+        let note = parse_with("a.md", body, &cfg);
+        let tags: Vec<_> = note.tags.iter().map(|t| t.0.as_str()).collect();
+        assert_eq!(tags, vec!["v1.2"]);
+    }
+
+    #[test]
+    fn ascii_charset_truncates_unicode_tags() {
+        let mut cfg = VaultConfig::default();
+        cfg.tag_charset.unicode = false;
+        let note = parse_with("a.md", "#café\n", &cfg);
+        let tags: Vec<_> = note.tags.iter().map(|t| t.0.as_str()).collect();
+        assert_eq!(tags, vec!["caf"]);
+    }
 }
@@ -1,3 +1,80 @@
 mod markdown;
 
-pub(crate) use markdown::{FrontmatterParse, parse_markdown_note};
+use std::collections::BTreeSet;
+
+#[cfg(feature = "similarity")]
+pub(crate) use markdown::frontmatter_split;
+pub(crate) use markdown::{
+    CodeBlocks, FrontmatterParse, ParsedNote, extract_links_from_line, parse_markdown_note,
+};
+
+use crate::index::note_fields;
+use crate::{FieldMap, FrontmatterStatus, Link, LinkTarget, Tag, Task, VaultConfig, VaultPath};
+
+/// A single Markdown note parsed in isolation, without a [`Vault`](crate::Vault) or index.
+///
+/// Fields are merged exactly as the index does with the default [`VaultConfig`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ParsedNotePublic {
+    pub title: String,
+    /// Frontmatter and inline tags, merged.
+    pub tags: BTreeSet<Tag>,
+    pub frontmatter_tags: BTreeSet<Tag>,
+    pub inline_tags: BTreeSet<Tag>,
+    pub aliases: BTreeSet<String>,
+    pub links: BTreeSet<LinkTarget>,
+    pub link_occurrences: Vec<Link>,
+    pub frontmatter: FrontmatterStatus,
+    /// Frontmatter and inline fields, merged.
+    pub fields: FieldMap,
+    /// Raw inline `key:: value` pairs in document order.
+    pub inline_fields: Vec<(String, String)>,
+    pub tasks: Vec<Task>,
+}
+
+/// Parses one Markdown note into tags, links, tasks and fields.
+///
+/// ```
+/// use oxidian::{FieldValue, VaultPath, parse_note};
+///
+/// let path = VaultPath::try_from(std::path::Path::new("notes/today.md")).unwrap();
+/// let note = parse_note(&path, "---\nstatus: draft\n---\n# Today\n\n- [ ] ship #work\n");
+///
+/// assert_eq!(note.title, "Today");
+/// assert!(note.tags.iter().any(|t| t.0 == "work"));
+/// assert_eq!(note.tasks[0].text, "ship #work");
+/// assert_eq!(note.fields.get("status"), Some(&FieldValue::String("draft".into())));
+/// ```
+pub fn parse_note(path: &VaultPath, content: &str) -> ParsedNotePublic {
+    let cfg = VaultConfig::default();
+    let parsed = parse_markdown_note(path, content, &cfg);
+    let derived = note_fields(&parsed, cfg.field_merge_policy);
+    let tasks = parsed
+        .tasks
+        .into_iter()
+        .map(|t| Task {
+            path: path.clone(),
+            line: t.line,
+            status: t.status,
+            text: t.text,
+            due: t.due,
+            done: t.done,
+            priority: t.priority,
+            tags: t.tags,
+        })
+        .collect();
+
+    ParsedNotePublic {
+        title: parsed.title,
+        tags: parsed.tags,
+        frontmatter_tags: parsed.frontmatter_tags,
+        inline_tags: parsed.inline_tags,
+        aliases: derived.aliases,
+        links: parsed.links,
+        link_occurrences: parsed.link_occurrences,
+        frontmatter: derived.frontmatter,
+        fields: derived.fields,
+        inline_fields: parsed.inline_fields,
+        tasks,
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use crate::fields::normalize_field_key;
+use crate::parse::extract_links_from_line;
+use crate::{LinkTarget, ResolveResult, VaultIndex, VaultPath};
+
+/// One `predicate:: [[target]]` relationship found in a note's fields.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PredicateEdge {
+    pub source: VaultPath,
+    /// Field key as written in the note (may be an alias).
+    pub key: String,
+    /// Link reference as written, without subpath or display text.
+    pub reference: String,
+    pub resolution: ResolveResult,
+}
+
+/// All edges for one canonical predicate.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PredicateEdges {
+    pub predicate: String,
+    pub edges: Vec<PredicateEdge>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct PredicateReport {
+    pub predicates: Vec<PredicateEdges>,
+    pub total_edges: usize,
+    /// Edges whose target is missing or ambiguous.
+    pub unresolved: usize,
+}
+
+impl VaultIndex {
+    /// Lists link-valued fields whose key is a schema predicate (or alias), grouped by
+    /// canonical predicate. Empty when no schema is loaded.
+    pub fn predicate_report(&self) -> PredicateReport {
+        let Some(schema) = self.schema() else {
+            return PredicateReport::default();
+        };
+
+        let resolver = self.link_resolver();
+        let mut by_predicate: BTreeMap<String, Vec<PredicateEdge>> = BTreeMap::new();
+        for (source, note) in self.notes_iter() {
+            for (raw_key, value) in &note.fields {
+                let Some(key) = normalize_field_key(raw_key) else {
+                    continue;
+                };
+                let canonical = match schema.aliases.get(&key) {
+                    Some(alias) => alias.clone(),
+                    None => key,
+                };
+                if !schema.predicates.contains_key(&canonical) {
+                    continue;
+                }
+
+                for text in value.strings() {
+                    let (_, links) = extract_links_from_line(text, 0);
+                    for link in links {
+                        let LinkTarget::Internal { reference } = &link.target else {
+                            continue;
+                        };
+                        by_predicate
+                            .entry(canonical.clone())
+                            .or_default()
+                            .push(PredicateEdge {
+                                source: source.clone(),
+                                key: raw_key.clone(),
+                                reference: reference.clone(),
+                                resolution: resolver.resolve_link_target(&link.target, source),
+                            });
+                    }
+                }
+            }
+        }
+
+        let mut report = PredicateReport::default();
+        for (predicate, edges) in by_predicate {
+            report.total_edges += edges.len();
+            report.unresolved += edges
+                .iter()
+                .filter(|e| !matches!(e.resolution, ResolveResult::Resolved(_)))
+                .count();
+            report.predicates.push(PredicateEdges { predicate, edges });
+        }
+        report
+    }
+}
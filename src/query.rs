@@ -1,4 +1,11 @@
-use crate::{FieldValue, Tag, TaskStatus, VaultIndex, VaultPath, fields::normalize_field_key};
+use std::collections::BTreeSet;
+
+use regex::Regex;
+
+use crate::{
+    Date, Error, FieldValue, Tag, Task, TaskPriority, TaskStatus, VaultIndex, VaultPath,
+    fields::normalize_field_key,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -16,11 +23,41 @@ pub enum SortDir {
     Desc,
 }
 
+/// Which of a note's tag sets [`Query::from_tag`] matches against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagSource {
+    /// Frontmatter or inline.
+    #[default]
+    Any,
+    Frontmatter,
+    Inline,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SortKey {
     Path,
     Field(String),
+    /// File modification time.
+    Mtime,
+    /// File size in bytes.
+    Size,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSortKey {
+    Path,
+    Line,
+    /// Open work first: todo, in progress, blocked, done, cancelled.
+    Status,
+    /// Earliest due date first; tasks without one sort last.
+    Due,
+    /// Earliest completion date first; tasks without one sort last.
+    Done,
+    /// Highest priority first.
+    Priority,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
@@ -31,16 +68,99 @@ pub struct Sort {
 
 #[derive(Debug, Clone, PartialEq)]
 enum Predicate {
-    Exists { key: String },
-    Eq { key: String, value: FieldValue },
-    Contains { key: String, needle: String },
-    Cmp { key: String, op: CmpOp, rhs: f64 },
+    Exists {
+        key: String,
+    },
+    ExistsAny {
+        keys: Vec<String>,
+    },
+    MissingAll {
+        keys: Vec<String>,
+    },
+    Eq {
+        key: String,
+        value: FieldValue,
+    },
+    NotEq {
+        key: String,
+        value: FieldValue,
+    },
+    Contains {
+        key: String,
+        needle: String,
+    },
+    Cmp {
+        key: String,
+        op: CmpOp,
+        rhs: f64,
+    },
+    TagCount {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    AnyOf(Vec<Predicate>),
+    Language(String),
+}
+
+/// A standalone field condition, combined into OR groups with [`Query::any_of`].
+///
+/// A key that does not normalize yields a predicate that never matches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldPredicate(Option<Predicate>);
+
+impl FieldPredicate {
+    pub fn exists(key: impl AsRef<str>) -> Self {
+        Self(normalize_field_key(key.as_ref()).map(|key| Predicate::Exists { key }))
+    }
+
+    pub fn not_exists(key: impl AsRef<str>) -> Self {
+        Self(normalize_field_key(key.as_ref()).map(|key| Predicate::MissingAll { keys: vec![key] }))
+    }
+
+    pub fn eq<V: Into<FieldValue>>(key: impl AsRef<str>, v: V) -> Self {
+        let value = v.into();
+        Self(normalize_field_key(key.as_ref()).map(|key| Predicate::Eq { key, value }))
+    }
+
+    pub fn not_eq<V: Into<FieldValue>>(key: impl AsRef<str>, v: V) -> Self {
+        let value = v.into();
+        Self(normalize_field_key(key.as_ref()).map(|key| Predicate::NotEq { key, value }))
+    }
+
+    pub fn contains(key: impl AsRef<str>, needle: impl Into<String>) -> Self {
+        let needle = needle.into();
+        Self(normalize_field_key(key.as_ref()).map(|key| Predicate::Contains { key, needle }))
+    }
+
+    pub fn gt(key: impl AsRef<str>, rhs: f64) -> Self {
+        Self::cmp(key, CmpOp::Gt, rhs)
+    }
+
+    pub fn gte(key: impl AsRef<str>, rhs: f64) -> Self {
+        Self::cmp(key, CmpOp::Gte, rhs)
+    }
+
+    pub fn lt(key: impl AsRef<str>, rhs: f64) -> Self {
+        Self::cmp(key, CmpOp::Lt, rhs)
+    }
+
+    pub fn lte(key: impl AsRef<str>, rhs: f64) -> Self {
+        Self::cmp(key, CmpOp::Lte, rhs)
+    }
+
+    fn cmp(key: impl AsRef<str>, op: CmpOp, rhs: f64) -> Self {
+        Self(normalize_field_key(key.as_ref()).map(|key| Predicate::Cmp { key, op, rhs }))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Query {
     path_prefix: Option<String>,
     tag: Option<Tag>,
+    /// Whether `tag` also matches its nested tags.
+    tag_prefix: bool,
+    tag_source: TagSource,
+    exclude_tags: Vec<Tag>,
     predicates: Vec<Predicate>,
     sort: Option<Sort>,
     limit: Option<usize>,
@@ -57,13 +177,42 @@ pub struct TaskHit {
     pub line: u32,
     pub status: TaskStatus,
     pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due: Option<Date>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub done: Option<Date>,
+    #[serde(skip_serializing_if = "TaskPriority::is_none")]
+    pub priority: TaskPriority,
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<Tag>,
 }
 
+/// Compiled task-text regex; compared by source pattern.
+#[derive(Debug, Clone)]
+struct TextPattern(Regex);
+
+impl PartialEq for TextPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for TextPattern {}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TaskQuery {
+    path: Option<VaultPath>,
     path_prefix: Option<String>,
     status: Option<TaskStatus>,
+    exclude_status: Vec<TaskStatus>,
     contains: Option<String>,
+    text_regex: Option<TextPattern>,
+    due_from: Option<Date>,
+    due_to: Option<Date>,
+    min_priority: Option<TaskPriority>,
+    completed_since: Option<Date>,
+    tag: Option<Tag>,
+    sort: Option<(TaskSortKey, SortDir)>,
     limit: Option<usize>,
 }
 
@@ -72,6 +221,9 @@ impl Query {
         Self {
             path_prefix: None,
             tag: None,
+            tag_prefix: false,
+            tag_source: TagSource::Any,
+            exclude_tags: Vec::new(),
             predicates: Vec::new(),
             sort: None,
             limit: None,
@@ -84,11 +236,29 @@ impl Query {
     }
 
     pub fn from_tag(mut self, tag: impl Into<String>) -> Self {
-        self.tag = Some(Tag(tag
-            .into()
-            .trim()
-            .trim_start_matches('#')
-            .to_lowercase()));
+        self.tag = Some(query_tag(tag.into()));
+        self.tag_prefix = false;
+        self
+    }
+
+    /// Like [`from_tag`](Self::from_tag), but also matches nested tags: `project` keeps
+    /// notes tagged `project/web`, though not `projectx`.
+    pub fn from_tag_prefix(mut self, tag: impl Into<String>) -> Self {
+        let tag = query_tag(tag.into());
+        self.tag = Some(Tag(tag.0.trim_end_matches('/').to_string()));
+        self.tag_prefix = true;
+        self
+    }
+
+    /// Drop notes carrying `tag`, from frontmatter or inline (repeatable).
+    pub fn exclude_tag(mut self, tag: impl Into<String>) -> Self {
+        self.exclude_tags.push(query_tag(tag.into()));
+        self
+    }
+
+    /// Restricts [`from_tag`](Self::from_tag) to frontmatter or inline tags.
+    pub fn tag_source(mut self, source: TagSource) -> Self {
+        self.tag_source = source;
         self
     }
 
@@ -99,6 +269,51 @@ impl Query {
         }
     }
 
+    /// Keep notes whose number of tags lies within `min..=max` (either bound optional).
+    pub fn where_tag_count(mut self, min: Option<usize>, max: Option<usize>) -> Self {
+        if min.is_some() || max.is_some() {
+            self.predicates.push(Predicate::TagCount { min, max });
+        }
+        self
+    }
+
+    /// Keep notes whose detected language is `code` (ISO 639-1, case-insensitive).
+    ///
+    /// Matches nothing unless the index was built with
+    /// [`VaultConfig::detect_language`](crate::VaultConfig::detect_language).
+    pub fn where_language(mut self, code: impl AsRef<str>) -> Self {
+        let code = code.as_ref().trim().to_lowercase();
+        self.predicates.push(Predicate::Language(code));
+        self
+    }
+
+    /// Keep notes that set at least one of `keys`.
+    pub fn exists_any(mut self, keys: Vec<String>) -> Self {
+        let keys = normalize_field_keys(keys);
+        if !keys.is_empty() {
+            self.predicates.push(Predicate::ExistsAny { keys });
+        }
+        self
+    }
+
+    /// Keep notes that set none of `keys`.
+    pub fn missing_all(mut self, keys: Vec<String>) -> Self {
+        let keys = normalize_field_keys(keys);
+        if !keys.is_empty() {
+            self.predicates.push(Predicate::MissingAll { keys });
+        }
+        self
+    }
+
+    /// Keep notes matching at least one of `preds`; groups AND with every other filter.
+    ///
+    /// An empty group matches nothing.
+    pub fn any_of(mut self, preds: Vec<FieldPredicate>) -> Self {
+        let preds = preds.into_iter().filter_map(|p| p.0).collect();
+        self.predicates.push(Predicate::AnyOf(preds));
+        self
+    }
+
     pub fn sort_by_path(mut self, dir: SortDir) -> Self {
         self.sort = Some(Sort {
             key: SortKey::Path,
@@ -107,6 +322,24 @@ impl Query {
         self
     }
 
+    /// Sort by file modification time; equal times fall back to path order.
+    pub fn sort_by_mtime(mut self, dir: SortDir) -> Self {
+        self.sort = Some(Sort {
+            key: SortKey::Mtime,
+            dir,
+        });
+        self
+    }
+
+    /// Sort by file size; equal sizes fall back to path order.
+    pub fn sort_by_size(mut self, dir: SortDir) -> Self {
+        self.sort = Some(Sort {
+            key: SortKey::Size,
+            dir,
+        });
+        self
+    }
+
     pub fn sort_by_field(mut self, key: impl AsRef<str>, dir: SortDir) -> Self {
         let Some(k) = normalize_field_key(key.as_ref()) else {
             return self;
@@ -125,7 +358,15 @@ impl Query {
 
     pub(crate) fn execute(&self, index: &VaultIndex) -> Vec<QueryHit> {
         let mut candidates: Vec<VaultPath> = if let Some(tag) = &self.tag {
-            index.files_with_tag(tag).cloned().collect()
+            if self.tag_prefix {
+                index
+                    .files_with_tag_prefix(tag)
+                    .into_iter()
+                    .cloned()
+                    .collect()
+            } else {
+                index.files_with_tag(tag).cloned().collect()
+            }
         } else {
             index.notes_iter_paths().cloned().collect()
         };
@@ -138,6 +379,24 @@ impl Query {
             let Some(note) = index.note(p) else {
                 return false;
             };
+            if let Some(tag) = &self.tag {
+                let tags = match self.tag_source {
+                    TagSource::Any => &note.tags,
+                    TagSource::Frontmatter => &note.frontmatter_tags,
+                    TagSource::Inline => &note.inline_tags,
+                };
+                let found = if self.tag_prefix {
+                    tags.iter().any(|t| t.is_within(tag))
+                } else {
+                    tags.contains(tag)
+                };
+                if !found {
+                    return false;
+                }
+            }
+            if self.exclude_tags.iter().any(|t| note.tags.contains(t)) {
+                return false;
+            }
 
             for pred in &self.predicates {
                 if !eval_predicate(pred, note) {
@@ -162,16 +421,67 @@ impl Query {
     }
 }
 
+fn query_tag(raw: String) -> Tag {
+    Tag(raw.trim().trim_start_matches('#').to_lowercase())
+}
+
+fn task_hit(t: &Task) -> TaskHit {
+    TaskHit {
+        path: t.path.clone(),
+        line: t.line,
+        status: t.status,
+        text: t.text.clone(),
+        due: t.due,
+        done: t.done,
+        priority: t.priority,
+        tags: t.tags.clone(),
+    }
+}
+
+fn dated_first(a: Option<Date>, b: Option<Date>) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(x), Some(y)) => x.cmp(&y),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+}
+
+fn status_rank(status: TaskStatus) -> u8 {
+    match status {
+        TaskStatus::Todo => 0,
+        TaskStatus::InProgress => 1,
+        TaskStatus::Blocked => 2,
+        TaskStatus::Done => 3,
+        TaskStatus::Cancelled => 4,
+    }
+}
+
 impl TaskQuery {
     pub fn all() -> Self {
         Self {
+            path: None,
             path_prefix: None,
             status: None,
+            exclude_status: Vec::new(),
             contains: None,
+            text_regex: None,
+            due_from: None,
+            due_to: None,
+            min_priority: None,
+            completed_since: None,
+            tag: None,
+            sort: None,
             limit: None,
         }
     }
 
+    /// Restrict to the tasks of exactly one note.
+    pub fn from_path(mut self, path: VaultPath) -> Self {
+        self.path = Some(path);
+        self
+    }
+
     pub fn from_path_prefix(mut self, prefix: impl Into<String>) -> Self {
         self.path_prefix = Some(prefix.into());
         self
@@ -182,11 +492,85 @@ impl TaskQuery {
         self
     }
 
+    /// Drop tasks with `status`; may be called repeatedly.
+    pub fn exclude_status(mut self, status: TaskStatus) -> Self {
+        if !self.exclude_status.contains(&status) {
+            self.exclude_status.push(status);
+        }
+        self
+    }
+
     pub fn contains_text(mut self, needle: impl Into<String>) -> Self {
         self.contains = Some(needle.into());
         self
     }
 
+    /// Keep tasks whose text matches `pattern`; combines with [`Self::contains_text`] (AND).
+    pub fn text_matches(mut self, pattern: &str) -> crate::Result<Self> {
+        let re = Regex::new(pattern).map_err(|e| {
+            Error::InvalidQuery(format!("invalid task text regex '{pattern}': {e}"))
+        })?;
+        self.text_regex = Some(TextPattern(re));
+        Ok(self)
+    }
+
+    /// Keep tasks due within `from..=to`; tasks without a due date are dropped.
+    ///
+    /// Date filters intersect, so this narrows any earlier `due_*` bound.
+    pub fn due_between(self, from: Date, to: Date) -> Self {
+        self.due_from_bound(from).due_to_bound(to)
+    }
+
+    /// Keep tasks due strictly before `date`; tasks without a due date are dropped.
+    pub fn due_before(self, date: Date) -> Self {
+        self.due_to_bound(date.add_days(-1))
+    }
+
+    /// Keep tasks due strictly after `date`; tasks without a due date are dropped.
+    pub fn due_after(self, date: Date) -> Self {
+        self.due_from_bound(date.add_days(1))
+    }
+
+    fn due_from_bound(mut self, from: Date) -> Self {
+        self.due_from = Some(self.due_from.map_or(from, |d| d.max(from)));
+        self
+    }
+
+    fn due_to_bound(mut self, to: Date) -> Self {
+        self.due_to = Some(self.due_to.map_or(to, |d| d.min(to)));
+        self
+    }
+
+    /// Keep done tasks completed on or after `date`; done tasks without a completion date
+    /// are dropped.
+    pub fn completed_since(mut self, date: Date) -> Self {
+        self.completed_since = Some(date);
+        self
+    }
+
+    /// Keep tasks at `priority` or above; unmarked tasks rank as [`TaskPriority::None`].
+    pub fn min_priority(mut self, priority: TaskPriority) -> Self {
+        self.min_priority = Some(priority);
+        self
+    }
+
+    /// Keep tasks whose own line carries `tag` (leading `#` optional, case-insensitive).
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(query_tag(tag.into()));
+        self
+    }
+
+    /// Order results by `key`; ties keep the default path-then-line order.
+    pub fn sort_by(mut self, key: TaskSortKey, dir: SortDir) -> Self {
+        self.sort = Some((key, dir));
+        self
+    }
+
+    /// Highest priority first; shorthand for `sort_by(TaskSortKey::Priority, SortDir::Asc)`.
+    pub fn sort_by_priority(self) -> Self {
+        self.sort_by(TaskSortKey::Priority, SortDir::Asc)
+    }
+
     pub fn limit(mut self, n: usize) -> Self {
         self.limit = Some(n);
         self
@@ -194,42 +578,91 @@ impl TaskQuery {
 
     pub(crate) fn execute(&self, index: &VaultIndex) -> Vec<TaskHit> {
         let mut out = Vec::new();
-        let needle = self.contains.as_deref();
-
-        for (path, note) in index.notes_iter() {
-            if let Some(prefix) = &self.path_prefix
-                && !path.as_path().to_string_lossy().starts_with(prefix)
-            {
-                continue;
-            }
 
-            for t in &note.tasks {
-                if let Some(st) = self.status
-                    && t.status != st
-                {
-                    continue;
-                }
-                if let Some(n) = needle
-                    && !t.text.contains(n)
+        if let Some(path) = &self.path {
+            let tasks = index.note_tasks(path).unwrap_or_default();
+            out.extend(tasks.iter().filter(|t| self.matches_task(t)).map(task_hit));
+        } else {
+            for (path, note) in index.notes_iter() {
+                if let Some(prefix) = &self.path_prefix
+                    && !path.as_path().to_string_lossy().starts_with(prefix)
                 {
                     continue;
                 }
-
-                out.push(TaskHit {
-                    path: t.path.clone(),
-                    line: t.line,
-                    status: t.status,
-                    text: t.text.clone(),
-                });
+                out.extend(
+                    note.tasks
+                        .iter()
+                        .filter(|t| self.matches_task(t))
+                        .map(task_hit),
+                );
             }
         }
 
         out.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.line.cmp(&b.line)));
+        if let Some((key, dir)) = self.sort {
+            // Stable sort: equal keys stay in path-then-line order.
+            out.sort_by(|a, b| {
+                let ord = match key {
+                    TaskSortKey::Path => a.path.cmp(&b.path),
+                    TaskSortKey::Line => a.line.cmp(&b.line),
+                    TaskSortKey::Status => status_rank(a.status).cmp(&status_rank(b.status)),
+                    TaskSortKey::Priority => b.priority.cmp(&a.priority),
+                    TaskSortKey::Due => dated_first(a.due, b.due),
+                    TaskSortKey::Done => dated_first(a.done, b.done),
+                };
+                match dir {
+                    SortDir::Asc => ord,
+                    SortDir::Desc => ord.reverse(),
+                }
+            });
+        }
         if let Some(limit) = self.limit {
             out.truncate(limit);
         }
         out
     }
+
+    fn matches_task(&self, t: &Task) -> bool {
+        if let Some(st) = self.status
+            && t.status != st
+        {
+            return false;
+        }
+        if self.exclude_status.contains(&t.status) {
+            return false;
+        }
+        if let Some(n) = self.contains.as_deref()
+            && !t.text.contains(n)
+        {
+            return false;
+        }
+        if let Some(TextPattern(re)) = &self.text_regex
+            && !re.is_match(&t.text)
+        {
+            return false;
+        }
+        if self.min_priority.is_some_and(|min| t.priority < min) {
+            return false;
+        }
+        if let Some(since) = self.completed_since
+            && !(t.status == TaskStatus::Done && t.done.is_some_and(|d| since <= d))
+        {
+            return false;
+        }
+        if let Some(tag) = &self.tag
+            && !t.tags.contains(tag)
+        {
+            return false;
+        }
+        if (self.due_from.is_some() || self.due_to.is_some())
+            && !t.due.is_some_and(|d| {
+                self.due_from.is_none_or(|from| from <= d) && self.due_to.is_none_or(|to| d <= to)
+            })
+        {
+            return false;
+        }
+        true
+    }
 }
 
 pub struct FieldPredicateBuilder {
@@ -250,6 +683,17 @@ impl FieldPredicateBuilder {
         self.q
     }
 
+    /// Keep notes without the field; a field set to an empty value still counts as present.
+    pub fn not_exists(mut self) -> Query {
+        let Some(k) = self.norm_key() else {
+            return self.q;
+        };
+        self.q
+            .predicates
+            .push(Predicate::MissingAll { keys: vec![k] });
+        self.q
+    }
+
     pub fn eq<V: Into<FieldValue>>(mut self, v: V) -> Query {
         let Some(k) = self.norm_key() else {
             return self.q;
@@ -261,6 +705,18 @@ impl FieldPredicateBuilder {
         self.q
     }
 
+    /// Keep notes whose field does not equal `v`, including notes without the field.
+    pub fn not_eq<V: Into<FieldValue>>(mut self, v: V) -> Query {
+        let Some(k) = self.norm_key() else {
+            return self.q;
+        };
+        self.q.predicates.push(Predicate::NotEq {
+            key: k,
+            value: v.into(),
+        });
+        self.q
+    }
+
     pub fn contains(mut self, needle: impl Into<String>) -> Query {
         let Some(k) = self.norm_key() else {
             return self.q;
@@ -301,10 +757,13 @@ fn eval_predicate(pred: &Predicate, note: &crate::NoteMeta) -> bool {
     let fields = &note.fields;
     match pred {
         Predicate::Exists { key } => fields.contains_key(key),
+        Predicate::ExistsAny { keys } => keys.iter().any(|k| fields.contains_key(k)),
+        Predicate::MissingAll { keys } => !keys.iter().any(|k| fields.contains_key(k)),
         Predicate::Eq { key, value } => match fields.get(key) {
             None => false,
             Some(v) => field_eq(v, value),
         },
+        Predicate::NotEq { key, value } => fields.get(key).is_none_or(|v| !field_eq(v, value)),
         Predicate::Contains { key, needle } => match fields.get(key) {
             None => false,
             Some(v) => field_contains(v, needle),
@@ -318,9 +777,19 @@ fn eval_predicate(pred: &Predicate, note: &crate::NoteMeta) -> bool {
             }),
             _ => false,
         },
+        Predicate::AnyOf(preds) => preds.iter().any(|p| eval_predicate(p, note)),
+        Predicate::TagCount { min, max } => {
+            let n = note.tags.len();
+            min.is_none_or(|m| n >= m) && max.is_none_or(|m| n <= m)
+        }
+        Predicate::Language(code) => note.language.as_deref() == Some(code.as_str()),
     }
 }
 
+fn normalize_field_keys(keys: Vec<String>) -> Vec<String> {
+    keys.iter().filter_map(|k| normalize_field_key(k)).collect()
+}
+
 fn field_eq(a: &FieldValue, b: &FieldValue) -> bool {
     match a {
         FieldValue::List(items) => items.iter().any(|it| it == b),
@@ -375,9 +844,28 @@ fn sort_candidates(index: &VaultIndex, paths: &mut [VaultPath], sort: &Sort) {
                 }
             });
         }
+        SortKey::Mtime => sort_by_file_meta(index, paths, sort.dir, |f| f.mtime),
+        SortKey::Size => sort_by_file_meta(index, paths, sort.dir, |f| f.size),
     }
 }
 
+fn sort_by_file_meta<K: Ord>(
+    index: &VaultIndex,
+    paths: &mut [VaultPath],
+    dir: SortDir,
+    key: impl Fn(&crate::FileMeta) -> K,
+) {
+    paths.sort_by(|a, b| {
+        let ak = index.file(a).map(&key);
+        let bk = index.file(b).map(&key);
+        let ord = match dir {
+            SortDir::Asc => ak.cmp(&bk),
+            SortDir::Desc => bk.cmp(&ak),
+        };
+        ord.then_with(|| a.cmp(b))
+    });
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 enum SortValue {
     Number(i64),
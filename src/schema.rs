@@ -5,9 +5,11 @@ use regex::Regex;
 use tracing::{error, info};
 
 use crate::fields::normalize_field_key;
+use crate::index::lowercase_extension;
 use crate::{Error, FieldMap, FieldValue, Result, Tag, Vault, VaultPath};
 
-#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+/// Ordered from least to most severe.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SchemaSeverity {
     Warn,
@@ -203,6 +205,65 @@ pub struct VaultSchema {
     pub deny: Vec<LayoutRuleEntry>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub scopes: BTreeMap<String, ScopeDef>,
+    /// Frontmatter keys whose values name attachments (e.g. `image`, `cover`), counted as
+    /// references by the orphan check.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachment_keys: Vec<String>,
+    /// Treat an untyped note as its scope's type for predicate-domain checks when the
+    /// scope's `notes.type.allowed` lists exactly one type.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub infer_type_from_scope: bool,
+    /// How note `type` values and declared type names are normalized before comparing.
+    #[serde(default, skip_serializing_if = "TypeNormalization::is_default")]
+    pub type_normalization: TypeNormalization,
+}
+
+/// Normalization applied to both a note's `type` value and the schema's type names
+/// (`types`, scope `notes.type.allowed`, `require_any.types`, predicate `domain`).
+///
+/// ```toml
+/// [vault.type_normalization]
+/// unify_separators = true  # `Daily-Note` matches `daily_note`
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct TypeNormalization {
+    /// Strip surrounding whitespace (default: true).
+    pub trim: bool,
+    /// Compare ASCII case-insensitively (default: true).
+    pub lowercase: bool,
+    /// Treat `-`, `_` and spaces as the same separator (default: false).
+    pub unify_separators: bool,
+}
+
+impl Default for TypeNormalization {
+    fn default() -> Self {
+        Self {
+            trim: true,
+            lowercase: true,
+            unify_separators: false,
+        }
+    }
+}
+
+impl TypeNormalization {
+    pub fn apply(&self, raw: &str) -> String {
+        let s = if self.trim { raw.trim() } else { raw };
+        let s = if self.lowercase {
+            s.to_ascii_lowercase()
+        } else {
+            s.to_string()
+        };
+        if self.unify_separators {
+            s.replace(['-', ' '], "_")
+        } else {
+            s
+        }
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -250,6 +311,23 @@ pub struct ScopeNotes {
     pub r#type: Option<ScopeNoteType>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub require_any: Option<ScopeRequireAny>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamps: Option<ScopeTimestamps>,
+}
+
+/// Frontmatter keys that must hold an ISO-8601 date (e.g. `created`, `updated`).
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct ScopeTimestamps {
+    #[serde(
+        default = "default_timestamp_keys",
+        skip_serializing_if = "is_default_timestamp_keys"
+    )]
+    pub keys: Vec<String>,
+    #[serde(
+        default = "default_severity",
+        skip_serializing_if = "is_default_severity"
+    )]
+    pub severity: SchemaSeverity,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -388,6 +466,10 @@ fn is_default_severity(v: &SchemaSeverity) -> bool {
     *v == SchemaSeverity::Warn
 }
 
+fn is_default_timestamp_keys(v: &[String]) -> bool {
+    v == default_timestamp_keys().as_slice()
+}
+
 fn is_default_resolve(v: &ScopeResolution) -> bool {
     *v == ScopeResolution::MostSpecific
 }
@@ -427,27 +509,57 @@ impl Schema {
         out.extend(self.validate_predicates(rel, fields, inline_fields));
         out.extend(self.validate_scope_note_type(rel, fields));
         out.extend(self.validate_scope_require_any(rel, fields, tags));
+        out.extend(self.validate_scope_timestamps(rel, fields));
 
         out
     }
 
+    /// Checks one path against the layout rules.
+    ///
+    /// Deny rules take precedence: when any vault-level or scope-level deny rule matches,
+    /// the path gets a single `layout_denied` violation at the most severe matching rule's
+    /// severity, and no allow, kind, extension or unscoped checks run for it, even if an
+    /// allow rule also matches it.
     pub fn validate_layout_for_path(&self, vault: &Vault, rel: &VaultPath) -> Vec<SchemaViolation> {
         let rel_str = path_to_rel_string(rel.as_path());
         let mut out = Vec::new();
 
+        let mut denied: Option<(SchemaSeverity, Option<String>)> = None;
+        let mut deny = |severity: SchemaSeverity, scope: Option<String>| {
+            if denied
+                .as_ref()
+                .is_none_or(|(current, _)| severity > *current)
+            {
+                denied = Some((severity, scope));
+            }
+        };
         for entry in &self.vault.deny {
             let rule = entry.as_rule();
             if rule_matches(&rule, &rel_str) {
-                out.push(layout_rule_violation(
-                    "layout_denied",
-                    &rel_str,
-                    None,
-                    rule.severity,
-                ));
+                deny(rule.severity, None);
             }
         }
 
         let selection = self.scope_selection(&rel_str);
+        if let Some(selection) = &selection {
+            let rel_within = strip_scope_prefix(&rel_str, &selection.scope_path);
+            for rule in selection.collect_deny() {
+                if rule_matches(&rule, &rel_within) {
+                    deny(rule.severity, Some(selection.scope_id.to_string()));
+                }
+            }
+        }
+
+        if let Some((severity, scope)) = denied {
+            out.push(layout_rule_violation(
+                "layout_denied",
+                &rel_str,
+                scope,
+                severity,
+            ));
+            return out;
+        }
+
         let Some(selection) = selection else {
             if let Some(severity) = self.vault.unscoped.as_severity() {
                 out.push(SchemaViolation {
@@ -466,18 +578,6 @@ impl Schema {
         let scope = selection.scope;
         let rel_within = strip_scope_prefix(&rel_str, &scope_path);
 
-        let deny_rules = selection.collect_deny();
-        for rule in &deny_rules {
-            if rule_matches(rule, &rel_within) {
-                out.push(layout_rule_violation(
-                    "layout_denied",
-                    &rel_str,
-                    Some(scope_id_str.to_string()),
-                    rule.severity.clone(),
-                ));
-            }
-        }
-
         if !scope.allows_kind(vault, rel.as_path()) {
             if let Some(severity) = scope.unmatched.as_severity() {
                 out.push(SchemaViolation {
@@ -550,6 +650,101 @@ impl Schema {
         out
     }
 
+    /// Statically checks the schema itself for scopes and rules that behave confusingly:
+    /// duplicate or nested scope paths, allow rules whose literal extension the scope's
+    /// `kinds` or `extensions` rejects, deny rules made redundant the same way, and rules
+    /// repeated within one list. All findings are warnings.
+    pub fn lint(&self, vault: &Vault) -> Vec<SchemaViolation> {
+        let mut out = Vec::new();
+        let lint = |code: &str, scope: Option<&str>, message: String| SchemaViolation {
+            severity: SchemaSeverity::Warn,
+            code: code.to_string(),
+            message,
+            scope: scope.map(str::to_string),
+            detail: None,
+        };
+
+        for (outer_id, outer) in &self.vault.scopes {
+            let outer_path = normalized_path(self.resolved_scope_path(outer_id, outer));
+            for (inner_id, inner) in &self.vault.scopes {
+                let inner_path = normalized_path(self.resolved_scope_path(inner_id, inner));
+                if inner_id == outer_id || !scope_matches(&inner_path, &outer_path) {
+                    continue;
+                }
+                if inner_path == outer_path {
+                    // Reported once per pair; on a tie the later scope id is selected.
+                    if outer_id < inner_id {
+                        out.push(lint(
+                            "lint_scope_duplicate_path",
+                            Some(outer_id),
+                            format!(
+                                "scope '{outer_id}' has the same path '{outer_path}' as scope \
+                                 '{inner_id}'; paths under it resolve to '{inner_id}'"
+                            ),
+                        ));
+                    }
+                    continue;
+                }
+                out.push(lint(
+                    "lint_scope_overlap",
+                    Some(inner_id),
+                    format!(
+                        "scope '{inner_id}' ('{inner_path}') is nested in scope '{outer_id}' \
+                         ('{outer_path}'); paths under '{inner_path}' resolve to '{inner_id}' \
+                         (most_specific)"
+                    ),
+                ));
+            }
+        }
+
+        for (id, scope) in &self.vault.scopes {
+            for entry in &scope.allow {
+                let rule = entry.as_rule();
+                if let Some(reason) = scope.rejected_rule_extension(vault, &rule) {
+                    out.push(lint(
+                        "lint_rule_unreachable",
+                        Some(id),
+                        format!(
+                            "allow rule '{}' can never match: {reason}",
+                            rule_pattern(&rule)
+                        ),
+                    ));
+                }
+            }
+            for entry in &scope.deny {
+                let rule = entry.as_rule();
+                if let Some(reason) = scope.rejected_rule_extension(vault, &rule) {
+                    out.push(lint(
+                        "lint_rule_redundant",
+                        Some(id),
+                        format!(
+                            "deny rule '{}' only matches paths the scope rejects anyway: {reason}",
+                            rule_pattern(&rule)
+                        ),
+                    ));
+                }
+            }
+            for (label, rules) in [("allow", &scope.allow), ("deny", &scope.deny)] {
+                for pattern in duplicate_rule_patterns(rules) {
+                    out.push(lint(
+                        "lint_rule_duplicate",
+                        Some(id),
+                        format!("{label} rule '{pattern}' is listed more than once"),
+                    ));
+                }
+            }
+        }
+        for pattern in duplicate_rule_patterns(&self.vault.deny) {
+            out.push(lint(
+                "lint_rule_duplicate",
+                None,
+                format!("vault deny rule '{pattern}' is listed more than once"),
+            ));
+        }
+
+        out
+    }
+
     pub fn validate_vault_layout(&self, vault: &Vault) -> Vec<SchemaViolationRecord> {
         let mut out = Vec::new();
         for (id, scope) in &self.vault.scopes {
@@ -625,13 +820,14 @@ impl Schema {
             return out;
         }
 
-        let allowed: Vec<String> = self.types.keys().map(|t| t.to_ascii_lowercase()).collect();
+        let norm = &self.vault.type_normalization;
+        let allowed: Vec<String> = self.types.keys().map(|t| norm.apply(t)).collect();
 
         let mut types = Vec::new();
         match value {
             FieldValue::String(s) => {
                 if !s.trim().is_empty() {
-                    types.push(s.trim().to_ascii_lowercase());
+                    types.push(norm.apply(s));
                 }
             }
             FieldValue::List(items) => {
@@ -639,7 +835,7 @@ impl Schema {
                     if let FieldValue::String(s) = item
                         && !s.trim().is_empty()
                     {
-                        types.push(s.trim().to_ascii_lowercase());
+                        types.push(norm.apply(s));
                     }
                 }
             }
@@ -677,7 +873,12 @@ impl Schema {
         inline_fields: &[(String, String)],
     ) -> Vec<SchemaViolation> {
         let mut out = Vec::new();
-        let note_type = extract_note_type(fields);
+        let note_type = self.extract_note_type(fields).or_else(|| {
+            self.vault
+                .infer_type_from_scope
+                .then(|| self.scope_implied_type(rel))
+                .flatten()
+        });
 
         for (raw_key, raw_value) in inline_fields {
             let Some(key) = normalize_field_key(raw_key) else {
@@ -711,7 +912,7 @@ impl Schema {
             };
 
             if let Some(note_type) = &note_type
-                && !predicate_domain_allows(&def.domain, note_type)
+                && !self.predicate_domain_allows(&def.domain, note_type)
             {
                 out.push(SchemaViolation {
                     severity: def.severity.clone(),
@@ -729,6 +930,37 @@ impl Schema {
         out
     }
 
+    /// The single type a note's scope allows, if it allows exactly one.
+    fn scope_implied_type(&self, rel: &VaultPath) -> Option<String> {
+        let selection = self.scope_selection(&path_to_rel_string(rel.as_path()))?;
+        match selection.notes()?.r#type.as_ref()?.allowed.as_slice() {
+            [only] => Some(self.vault.type_normalization.apply(only)),
+            _ => None,
+        }
+    }
+
+    /// The note's first non-empty `type` value, normalized.
+    fn extract_note_type(&self, fields: &FieldMap) -> Option<String> {
+        let norm = &self.vault.type_normalization;
+        let value = fields.get("type")?;
+        match value {
+            FieldValue::String(s) if !s.trim().is_empty() => Some(norm.apply(s)),
+            FieldValue::List(items) => items.iter().find_map(|item| match item {
+                FieldValue::String(s) if !s.trim().is_empty() => Some(norm.apply(s)),
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
+
+    fn predicate_domain_allows(&self, domain: &[String], note_type: &str) -> bool {
+        if domain.iter().any(|d| d == "*") {
+            return true;
+        }
+        let norm = &self.vault.type_normalization;
+        domain.iter().any(|d| norm.apply(d) == note_type)
+    }
+
     fn validate_scope_note_type(&self, rel: &VaultPath, fields: &FieldMap) -> Vec<SchemaViolation> {
         let rel_str = path_to_rel_string(rel.as_path());
         let Some(selection) = self.scope_selection(&rel_str) else {
@@ -749,7 +981,7 @@ impl Schema {
             return Vec::new();
         };
 
-        let note_type = extract_note_type(fields);
+        let note_type = self.extract_note_type(fields);
         if note_type_rule.required && note_type.is_none() {
             return vec![SchemaViolation {
                 severity: note_type_rule.severity.clone(),
@@ -770,7 +1002,7 @@ impl Schema {
                     note_type_rule
                         .allowed
                         .iter()
-                        .any(|a| a.eq_ignore_ascii_case(t))
+                        .any(|a| self.vault.type_normalization.apply(a) == t)
                 })
                 .unwrap_or(false);
             if !matches {
@@ -819,7 +1051,7 @@ impl Schema {
             return Vec::new();
         }
 
-        let note_type = extract_note_type(fields);
+        let note_type = self.extract_note_type(fields);
         let mut matches = false;
 
         if !require_any.types.is_empty()
@@ -828,7 +1060,7 @@ impl Schema {
             matches = require_any
                 .types
                 .iter()
-                .any(|t| t.eq_ignore_ascii_case(note_type));
+                .any(|t| self.vault.type_normalization.apply(t) == note_type);
         }
 
         if !matches && !require_any.tags.is_empty() {
@@ -856,6 +1088,50 @@ impl Schema {
         Vec::new()
     }
 
+    fn validate_scope_timestamps(
+        &self,
+        rel: &VaultPath,
+        fields: &FieldMap,
+    ) -> Vec<SchemaViolation> {
+        let rel_str = path_to_rel_string(rel.as_path());
+        let Some(selection) = self.scope_selection(&rel_str) else {
+            return Vec::new();
+        };
+
+        let scope_id_str = selection.scope_id;
+        if !selection.scope.allows_kind_note() {
+            return Vec::new();
+        }
+        let Some(timestamps) = selection.notes().and_then(|n| n.timestamps) else {
+            return Vec::new();
+        };
+
+        let mut out = Vec::new();
+        for key in &timestamps.keys {
+            let Some(key) = normalize_field_key(key) else {
+                continue;
+            };
+            match fields.get(&key) {
+                None | Some(FieldValue::Null) => out.push(SchemaViolation {
+                    severity: timestamps.severity.clone(),
+                    code: "note_timestamp_missing".to_string(),
+                    message: format!("path '{rel_str}' requires frontmatter '{key}'"),
+                    scope: Some(scope_id_str.to_string()),
+                    detail: None,
+                }),
+                Some(value) if value.as_date().is_none() => out.push(SchemaViolation {
+                    severity: timestamps.severity.clone(),
+                    code: "note_timestamp_invalid".to_string(),
+                    message: format!("path '{rel_str}' field '{key}' is not an ISO-8601 date"),
+                    scope: Some(scope_id_str.to_string()),
+                    detail: None,
+                }),
+                Some(_) => {}
+            }
+        }
+        out
+    }
+
     /// Find the scope definition (and its id) that matches a vault path.
     pub(crate) fn scope_for_path<'a>(&'a self, rel: &VaultPath) -> Option<(&'a str, &'a ScopeDef)> {
         let rel_str = path_to_rel_string(rel.as_path());
@@ -952,15 +1228,32 @@ impl ScopeDef {
         self.kinds.iter().any(|k| k == &ScopeKind::Note)
     }
 
+    /// Why a rule ending in a literal extension can never pass this scope's filters.
+    fn rejected_rule_extension(&self, vault: &Vault, rule: &LayoutRule) -> Option<String> {
+        let pattern = rule.glob.as_deref().or(rule.template.as_deref())?;
+        let ext = literal_extension(pattern)?;
+        let probe = PathBuf::from(format!("probe.{ext}"));
+        if !self.allows_extension(&probe) {
+            return Some(format!(
+                "extension '{ext}' is not in extensions {:?}",
+                self.extensions
+            ));
+        }
+        if !self.allows_kind(vault, &probe) {
+            let kind = scope_kind_for_path(vault, &probe);
+            return Some(format!(
+                "'{ext}' files are {kind:?}, not in kinds {:?}",
+                self.kinds
+            ));
+        }
+        None
+    }
+
     fn allows_extension(&self, rel: &Path) -> bool {
         if self.extensions.is_empty() {
             return true;
         }
-        let ext = rel
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_ascii_lowercase();
+        let ext = lowercase_extension(rel);
         self.extensions.iter().any(|e| e.eq_ignore_ascii_case(&ext))
     }
 }
@@ -997,6 +1290,35 @@ fn validate_rules(rules: &[LayoutRule], label: &str, scope_id: Option<&str>) ->
     Ok(())
 }
 
+/// The literal extension a glob or template requires, if its last segment ends in one.
+fn literal_extension(pattern: &str) -> Option<String> {
+    let last = pattern.rsplit('/').next().unwrap_or(pattern);
+    let (_, ext) = last.rsplit_once('.')?;
+    (!ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+        .then(|| ext.to_ascii_lowercase())
+}
+
+fn rule_pattern(rule: &LayoutRule) -> &str {
+    rule.glob
+        .as_deref()
+        .or(rule.regex.as_deref())
+        .or(rule.template.as_deref())
+        .unwrap_or("")
+}
+
+fn duplicate_rule_patterns(entries: &[LayoutRuleEntry]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut dups = BTreeSet::new();
+    for entry in entries {
+        let rule = entry.as_rule();
+        let key = (rule.glob.clone(), rule.regex.clone(), rule.template.clone());
+        if !seen.insert(key) {
+            dups.insert(rule_pattern(&rule).to_string());
+        }
+    }
+    dups.into_iter().collect()
+}
+
 fn rule_validation_prefix(label: &str, scope_id: Option<&str>) -> String {
     match scope_id {
         Some(scope_id) => format!("scope '{scope_id}' {label} rule"),
@@ -1455,6 +1777,10 @@ fn default_severity() -> SchemaSeverity {
     SchemaSeverity::Warn
 }
 
+fn default_timestamp_keys() -> Vec<String> {
+    vec!["created".to_string(), "updated".to_string()]
+}
+
 fn default_scope_resolution() -> ScopeResolution {
     ScopeResolution::MostSpecific
 }
@@ -1479,39 +1805,6 @@ fn value_looks_like_link(value: &str) -> bool {
     s.contains("](") && s.contains(')')
 }
 
-fn extract_note_type(fields: &FieldMap) -> Option<String> {
-    let value = fields.get("type")?;
-    match value {
-        FieldValue::String(s) => {
-            let t = s.trim();
-            if t.is_empty() {
-                None
-            } else {
-                Some(t.to_ascii_lowercase())
-            }
-        }
-        FieldValue::List(items) => items.iter().find_map(|item| match item {
-            FieldValue::String(s) => {
-                let t = s.trim();
-                if t.is_empty() {
-                    None
-                } else {
-                    Some(t.to_ascii_lowercase())
-                }
-            }
-            _ => None,
-        }),
-        _ => None,
-    }
-}
-
-fn predicate_domain_allows(domain: &[String], note_type: &str) -> bool {
-    if domain.iter().any(|d| d == "*") {
-        return true;
-    }
-    domain.iter().any(|d| d.eq_ignore_ascii_case(note_type))
-}
-
 fn normalize_tag_name(raw: &str) -> Option<String> {
     let mut s = raw.trim();
     if let Some(rest) = s.strip_prefix('#') {
@@ -1530,12 +1823,12 @@ fn normalize_tag_name(raw: &str) -> Option<String> {
 }
 
 fn scope_kind_for_path(vault: &Vault, rel: &Path) -> ScopeKind {
-    let ext = rel.extension().and_then(|s| s.to_str()).unwrap_or("");
+    let ext = lowercase_extension(rel);
     if vault
         .config()
         .note_extensions
         .iter()
-        .any(|e| e.eq_ignore_ascii_case(ext))
+        .any(|e| e.eq_ignore_ascii_case(&ext))
     {
         return ScopeKind::Note;
     }
@@ -1543,7 +1836,7 @@ fn scope_kind_for_path(vault: &Vault, rel: &Path) -> ScopeKind {
         .config()
         .attachment_extensions
         .iter()
-        .any(|e| e.eq_ignore_ascii_case(ext))
+        .any(|e| e.eq_ignore_ascii_case(&ext))
     {
         return ScopeKind::Attachment;
     }
@@ -1,4 +1,6 @@
+use std::ops::ControlFlow;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 
 use notify::{EventKind, RecursiveMode, Watcher};
@@ -65,6 +67,8 @@ pub struct VaultService {
     shutdown_tx: watch::Sender<bool>,
     watcher: Option<notify::RecommendedWatcher>,
     watch_task: Option<tokio::task::JoinHandle<()>>,
+    /// Watcher events received but not yet applied to the index.
+    outstanding: Arc<watch::Sender<usize>>,
 }
 
 impl VaultService {
@@ -78,6 +82,7 @@ impl VaultService {
             shutdown_tx,
             watcher: None,
             watch_task: None,
+            outstanding: Arc::new(watch::channel(0).0),
         })
     }
 
@@ -116,6 +121,14 @@ impl VaultService {
         self.with_index(|idx| idx.search_filenames_fuzzy(query, limit))
     }
 
+    pub fn search_filenames_prefix(&self, prefix: &str, limit: usize) -> Vec<crate::SearchHit> {
+        self.with_index(|idx| idx.search_filenames_prefix(prefix, limit))
+    }
+
+    pub fn search_filenames_exact(&self, query: &str, limit: usize) -> Vec<crate::SearchHit> {
+        self.with_index(|idx| idx.search_filenames_exact(query, limit))
+    }
+
     pub async fn search_content_fuzzy(
         &self,
         query: &str,
@@ -129,6 +142,33 @@ impl VaultService {
             .map_err(|e| Error::InvalidVaultPath(format!("search task failed: {e}")))?
     }
 
+    /// Streams fuzzy content hits as files are scanned, unsorted by score.
+    ///
+    /// The channel closes once the scan finishes; a read error is sent as the last item.
+    /// Dropping the receiver stops the scan. Must be called within a Tokio runtime.
+    pub fn search_content_fuzzy_stream(
+        &self,
+        query: &str,
+    ) -> mpsc::UnboundedReceiver<Result<crate::ContentSearchHit>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let snapshot = self.index_snapshot();
+        let vault = self.vault.clone();
+        let q = query.to_string();
+        tokio::task::spawn_blocking(move || {
+            let res = snapshot.search_content_fuzzy_each(&vault, &q, |hit| {
+                if tx.send(Ok(hit)).is_err() {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
+            if let Err(e) = res {
+                let _ = tx.send(Err(e));
+            }
+        });
+        rx
+    }
+
     #[cfg(feature = "similarity")]
     pub async fn search_content_semantic(
         &self,
@@ -164,10 +204,27 @@ impl VaultService {
         self.with_index(|idx| idx.query(q))
     }
 
+    /// Like [`VaultService::query`], but evaluates on the blocking pool so large indexes
+    /// don't stall the async executor.
+    pub async fn query_async(&self, q: &crate::Query) -> Result<Vec<crate::QueryHit>> {
+        let index = Arc::clone(&self.index);
+        let q = q.clone();
+        tokio::task::spawn_blocking(move || {
+            let guard = index.read().unwrap_or_else(|e| e.into_inner());
+            guard.query(&q)
+        })
+        .await
+        .map_err(|e| Error::InvalidVaultPath(format!("query task failed: {e}")))
+    }
+
     pub fn schema_status(&self) -> SchemaStatus {
         self.with_index(|idx| idx.schema_status().clone())
     }
 
+    pub fn schema_lint(&self) -> Vec<crate::SchemaViolation> {
+        self.with_index(|idx| idx.schema_lint(&self.vault))
+    }
+
     pub fn schema_report(&self) -> crate::SchemaReport {
         self.with_index(|idx| idx.schema_report())
     }
@@ -180,6 +237,25 @@ impl VaultService {
         self.with_index(|idx| idx.query_tasks(q))
     }
 
+    pub fn query_tasks_by_status(
+        &self,
+        q: &crate::TaskQuery,
+    ) -> std::collections::BTreeMap<crate::TaskStatus, Vec<crate::TaskHit>> {
+        self.with_index(|idx| idx.query_tasks_by_status(q))
+    }
+
+    /// Like [`VaultService::query_tasks`], but evaluates on the blocking pool.
+    pub async fn query_tasks_async(&self, q: &crate::TaskQuery) -> Result<Vec<crate::TaskHit>> {
+        let index = Arc::clone(&self.index);
+        let q = q.clone();
+        tokio::task::spawn_blocking(move || {
+            let guard = index.read().unwrap_or_else(|e| e.into_inner());
+            guard.query_tasks(&q)
+        })
+        .await
+        .map_err(|e| Error::InvalidVaultPath(format!("task query task failed: {e}")))
+    }
+
     pub fn link_health_report(&self) -> crate::Result<crate::LinkHealthReport> {
         let snapshot = self.index_snapshot();
         snapshot.link_health_report(self.vault())
@@ -209,6 +285,17 @@ impl VaultService {
         snapshot.note_similarity_for(self.vault(), source)
     }
 
+    /// Like [`Self::note_similarity_for`], with the KNN candidates rescored by `reranker`.
+    #[cfg(feature = "similarity")]
+    pub fn note_similarity_for_reranked(
+        &self,
+        source: &VaultPath,
+        reranker: &dyn crate::Reranker,
+    ) -> crate::Result<Vec<crate::NoteSimilarityHit>> {
+        let hits = self.note_similarity_for(source)?;
+        crate::rerank_note_hits(self.vault(), hits, reranker)
+    }
+
     pub fn build_backlinks(&self) -> crate::Result<crate::BacklinksIndex> {
         let snapshot = self.index_snapshot();
         snapshot.build_backlinks(self.vault())
@@ -232,6 +319,19 @@ impl VaultService {
             .map_err(|e| Error::InvalidVaultPath(format!("mentions task failed: {e}")))?
     }
 
+    pub async fn mention_suggestions(
+        &self,
+        target: &VaultPath,
+        limit: usize,
+    ) -> Result<Vec<crate::MentionSuggestion>> {
+        let snapshot = self.index_snapshot();
+        let vault = self.vault.clone();
+        let target = target.clone();
+        tokio::task::spawn_blocking(move || snapshot.mention_suggestions(&vault, &target, limit))
+            .await
+            .map_err(|e| Error::InvalidVaultPath(format!("mentions task failed: {e}")))?
+    }
+
     pub async fn start_watching(&mut self) -> Result<()> {
         if self.watcher.is_some() {
             return Ok(());
@@ -239,7 +339,9 @@ impl VaultService {
 
         let (raw_tx, raw_rx) =
             mpsc::unbounded_channel::<std::result::Result<notify::Event, notify::Error>>();
+        let outstanding = Arc::clone(&self.outstanding);
         let mut watcher = notify::recommended_watcher(move |res| {
+            outstanding.send_modify(|n| *n += 1);
             let _ = raw_tx.send(res);
         })?;
         watcher.watch(self.vault.root(), RecursiveMode::Recursive)?;
@@ -248,15 +350,43 @@ impl VaultService {
         let index = Arc::clone(&self.index);
         let events = self.events.clone();
         let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let outstanding = Arc::clone(&self.outstanding);
         let debounce = self.vault.config().watch_debounce;
         self.watch_task = Some(tokio::spawn(async move {
-            watch_loop(vault, index, events, raw_rx, &mut shutdown_rx, debounce).await;
+            watch_loop(
+                vault,
+                index,
+                events,
+                raw_rx,
+                &mut shutdown_rx,
+                &outstanding,
+                debounce,
+            )
+            .await;
+            outstanding.send_replace(0);
         }));
         self.watcher = Some(watcher);
 
         Ok(())
     }
 
+    /// Resolves once every watcher event received so far has been applied to the index.
+    ///
+    /// The OS watcher reports writes asynchronously, so when nothing is pending this first
+    /// gives it one debounce window to deliver a just-made change. Returns immediately
+    /// when the service is not watching.
+    pub async fn wait_for_idle(&self) {
+        if self.watch_task.is_none() {
+            return;
+        }
+        let mut rx = self.outstanding.subscribe();
+        if *rx.borrow_and_update() == 0 {
+            let debounce = self.vault.config().watch_debounce;
+            let _ = tokio::time::timeout(debounce, rx.changed()).await;
+        }
+        let _ = rx.wait_for(|n| *n == 0).await;
+    }
+
     pub async fn reload_schema(&self) -> Result<()> {
         let vault = self.vault.clone();
         let schema_state = SchemaState::load(&vault);
@@ -285,6 +415,21 @@ impl VaultService {
         Ok(())
     }
 
+    /// Replaces the vault config and rebuilds the index under it.
+    ///
+    /// A running watcher keeps the config it was started with (notably `watch_debounce`),
+    /// so call this before [`Self::start_watching`]. A changed `schema_path` reloads the
+    /// schema from the new location.
+    pub async fn set_config(&mut self, cfg: crate::VaultConfig) -> Result<()> {
+        let schema_moved = cfg.schema_path != self.vault.config().schema_path;
+        *self.vault.config_mut() = cfg;
+        if schema_moved {
+            self.reload_schema().await
+        } else {
+            self.build_index().await
+        }
+    }
+
     pub async fn shutdown(&mut self) {
         let _ = self.shutdown_tx.send(true);
         self.watcher.take();
@@ -306,9 +451,11 @@ async fn watch_loop(
     events: broadcast::Sender<VaultEvent>,
     mut raw_rx: mpsc::UnboundedReceiver<std::result::Result<notify::Event, notify::Error>>,
     shutdown_rx: &mut watch::Receiver<bool>,
+    outstanding: &watch::Sender<usize>,
     debounce: std::time::Duration,
 ) {
     let mut pending: Vec<notify::Event> = Vec::new();
+    let rejected_paths = Arc::new(AtomicUsize::new(0));
     let mut debounce_armed = false;
     let debounce_timer =
         tokio::time::sleep(std::time::Duration::from_secs(60 * 60 * 24 * 365 * 10));
@@ -337,6 +484,7 @@ async fn watch_loop(
                             .reset(tokio::time::Instant::now() + debounce);
                     }
                     Err(err) => {
+                        outstanding.send_modify(|n| *n = n.saturating_sub(1));
                         let _ = events.send(VaultEvent::Error { path: None, error: err.to_string() });
                     }
                 }
@@ -349,11 +497,14 @@ async fn watch_loop(
                 }
 
                 let batch = std::mem::take(&mut pending);
+                let batch_len = batch.len();
                 debounce_armed = false;
 
                 let vault2 = vault.clone();
                 let index2 = Arc::clone(&index);
-                let applied = tokio::task::spawn_blocking(move || apply_events(&vault2, &index2, batch)).await;
+                let rejected2 = Arc::clone(&rejected_paths);
+                let applied = tokio::task::spawn_blocking(move || apply_events(&vault2, &index2, &rejected2, batch)).await;
+                outstanding.send_modify(|n| *n = n.saturating_sub(batch_len));
                 let applied = match applied {
                     Ok(Ok(list)) => list,
                     Ok(Err(err)) => {
@@ -394,9 +545,10 @@ enum Op {
 fn apply_events(
     vault: &Vault,
     index: &RwLock<VaultIndex>,
+    rejected_paths: &AtomicUsize,
     batch: Vec<notify::Event>,
 ) -> Result<Vec<VaultEvent>> {
-    let ops = events_to_ops(vault, &batch);
+    let ops = events_to_ops(vault, rejected_paths, &batch);
 
     let mut out = Vec::new();
     let mut guard = index.write().unwrap_or_else(|e| e.into_inner());
@@ -442,25 +594,25 @@ fn apply_events(
                 out.push(VaultEvent::Removed { path, cause, delta });
             }
             Op::Rename { from, to, cause } => {
-                let removed = guard.remove_path(&from);
-                let added = match guard.upsert_path(vault, to.clone()) {
+                let (old_tags, old_links) = guard
+                    .note(&from)
+                    .map(|n| (n.tags.clone(), n.links.clone()))
+                    .unwrap_or_default();
+                let delta = match guard.rename_path(vault, &from, to.clone()) {
                     Ok(d) => d,
                     Err(err) => {
                         out.push(VaultEvent::Error {
                             path: Some(to.clone()),
                             error: err.to_string(),
                         });
-                        IndexDelta::default()
+                        IndexDelta {
+                            removed_tags: old_tags,
+                            removed_links: old_links,
+                            ..IndexDelta::default()
+                        }
                     }
                 };
 
-                let delta = IndexDelta {
-                    added_tags: added.added_tags,
-                    removed_tags: removed.removed_tags,
-                    added_links: added.added_links,
-                    removed_links: removed.removed_links,
-                };
-
                 out.push(VaultEvent::Renamed {
                     from,
                     to,
@@ -474,7 +626,7 @@ fn apply_events(
     Ok(out)
 }
 
-fn events_to_ops(vault: &Vault, batch: &[notify::Event]) -> Vec<Op> {
+fn events_to_ops(vault: &Vault, rejected_paths: &AtomicUsize, batch: &[notify::Event]) -> Vec<Op> {
     let mut ops = Vec::new();
     let mut upsert_ix: std::collections::HashMap<VaultPath, usize> =
         std::collections::HashMap::new();
@@ -495,8 +647,8 @@ fn events_to_ops(vault: &Vault, batch: &[notify::Event]) -> Vec<Op> {
         match &ev.kind {
             EventKind::Modify(notify::event::ModifyKind::Name(_)) if ev.paths.len() == 2 => {
                 if let (Some(from), Some(to)) = (
-                    to_vault_path(vault, &ev.paths[0]),
-                    to_vault_path(vault, &ev.paths[1]),
+                    to_vault_path(vault, rejected_paths, &ev.paths[0]),
+                    to_vault_path(vault, rejected_paths, &ev.paths[1]),
                 ) {
                     ops.push(Op::Rename {
                         from,
@@ -511,7 +663,7 @@ fn events_to_ops(vault: &Vault, batch: &[notify::Event]) -> Vec<Op> {
 
             EventKind::Remove(_) => {
                 for p in &ev.paths {
-                    if let Some(rel) = to_vault_path(vault, p) {
+                    if let Some(rel) = to_vault_path(vault, rejected_paths, p) {
                         if let Some(ix) = remove_ix.get(&rel).copied() {
                             let Op::Remove { cause, .. } = &mut ops[ix] else {
                                 continue;
@@ -532,7 +684,7 @@ fn events_to_ops(vault: &Vault, batch: &[notify::Event]) -> Vec<Op> {
             _ => {
                 for p in &ev.paths {
                     // Only upsert indexable paths; still allow remove events to clean up.
-                    if let Some(rel) = to_vault_path(vault, p) {
+                    if let Some(rel) = to_vault_path(vault, rejected_paths, p) {
                         if !vault.is_indexable_rel(rel.as_path()) {
                             continue;
                         }
@@ -604,8 +756,28 @@ fn rank_cause(cause: &ReindexCause) -> u8 {
     }
 }
 
-fn to_vault_path(vault: &Vault, abs: &Path) -> Option<VaultPath> {
-    vault.to_rel(abs).ok()
+/// Only every this-many watch paths rejected by [`Vault::to_rel`] is logged.
+const REJECTED_PATH_WARN_EVERY: usize = 100;
+
+/// `rejected_paths` counts rejections for one watcher, so each vault logs its own first one.
+fn to_vault_path(vault: &Vault, rejected_paths: &AtomicUsize, abs: &Path) -> Option<VaultPath> {
+    match vault.to_rel(abs) {
+        Ok(rel) => Some(rel),
+        // Events on the root directory itself are expected and carry no file.
+        Err(_) if abs == vault.root() => None,
+        Err(err) => {
+            let rejected = rejected_paths.fetch_add(1, Ordering::Relaxed);
+            if rejected.is_multiple_of(REJECTED_PATH_WARN_EVERY) {
+                tracing::warn!(
+                    path = %abs.display(),
+                    error = %err,
+                    rejected = rejected + 1,
+                    "dropping watch event path"
+                );
+            }
+            None
+        }
+    }
 }
 
 impl VaultService {
@@ -654,7 +826,7 @@ mod tests {
             vec![p],
         );
 
-        let ops = events_to_ops(&vault, &[ev]);
+        let ops = events_to_ops(&vault, &AtomicUsize::new(0), &[ev]);
         assert!(ops.is_empty());
     }
 
@@ -670,7 +842,7 @@ mod tests {
             vec![dir_path],
         );
 
-        let out = apply_events(&vault, &lock, vec![ev]).unwrap();
+        let out = apply_events(&vault, &lock, &AtomicUsize::new(0), vec![ev]).unwrap();
         assert!(out.is_empty());
     }
 }
@@ -1,8 +1,15 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::time::Instant;
 use tracing::{debug, info};
+#[cfg(not(feature = "sqlite"))]
+use tracing::{trace, warn};
+#[cfg(feature = "sqlite")]
 use zerocopy::AsBytes;
 
+#[cfg(not(feature = "sqlite"))]
+use crate::embeddings::note_text_for_embedding;
 use crate::embeddings::{EmbeddingModel, clean_markdown_for_embedding};
+#[cfg(feature = "sqlite")]
 use crate::sqlite::SqliteIndexStore;
 use crate::{Error, Result, Vault, VaultIndex, VaultPath};
 
@@ -26,12 +33,106 @@ pub struct NoteSimilarityReport {
     pub hits: Vec<NoteSimilarityHit>,
 }
 
+impl NoteSimilarityReport {
+    /// Drops hits whose two notes have fewer than `min` tags in common.
+    pub fn retain_shared_tags(&mut self, index: &VaultIndex, min: usize) {
+        if min == 0 {
+            return;
+        }
+        self.hits.retain(
+            |hit| match (index.note(&hit.source), index.note(&hit.target)) {
+                (Some(a), Some(b)) => a.tags.intersection(&b.tags).count() >= min,
+                _ => false,
+            },
+        );
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct SimilaritySettings {
     pub min_score: f32,
     pub top_k: usize,
 }
 
+/// Second-stage scorer applied to the top KNN candidates of a note.
+///
+/// Implement this to plug in a model-based reranker (e.g. a cross-encoder).
+pub trait Reranker {
+    /// Relevance of `candidate` to `source`, both cleaned note text, in `0.0..=1.0`.
+    fn score(&self, source: &str, candidate: &str) -> f32;
+}
+
+/// Dependency-free default reranker: Jaccard overlap of lowercased word tokens.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenOverlapReranker;
+
+impl Reranker for TokenOverlapReranker {
+    fn score(&self, source: &str, candidate: &str) -> f32 {
+        let a = word_tokens(source);
+        let b = word_tokens(candidate);
+        let union = a.union(&b).count();
+        if union == 0 {
+            return 0.0;
+        }
+        a.intersection(&b).count() as f32 / union as f32
+    }
+}
+
+fn word_tokens(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Rescores `hits` with `reranker` and reorders them by the new score.
+///
+/// Each hit's `score` is replaced by the reranker score; ties keep source/target order.
+pub fn rerank_note_hits(
+    vault: &Vault,
+    hits: Vec<NoteSimilarityHit>,
+    reranker: &dyn Reranker,
+) -> Result<Vec<NoteSimilarityHit>> {
+    let start = Instant::now();
+    let mut texts: BTreeMap<VaultPath, String> = BTreeMap::new();
+    let mut load = |path: &VaultPath| -> Result<String> {
+        if let Some(text) = texts.get(path) {
+            return Ok(text.clone());
+        }
+        let abs = vault.to_abs(path);
+        let raw = std::fs::read_to_string(&abs).map_err(|e| Error::io(&abs, e))?;
+        let cleaned = clean_markdown_for_embedding(&raw, vault.config().indented_code_blocks);
+        texts.insert(path.clone(), cleaned.clone());
+        Ok(cleaned)
+    };
+
+    let mut out = Vec::with_capacity(hits.len());
+    for mut hit in hits {
+        let source = load(&hit.source)?;
+        let target = load(&hit.target)?;
+        hit.score = reranker.score(&source, &target).clamp(0.0, 1.0);
+        out.push(hit);
+    }
+    sort_note_hits(&mut out);
+    debug!(
+        hit_count = out.len(),
+        elapsed_ms = start.elapsed().as_millis(),
+        "note similarity rerank complete"
+    );
+    Ok(out)
+}
+
+/// Orders hits by score descending, breaking ties by source then target path.
+fn sort_note_hits(hits: &mut [NoteSimilarityHit]) {
+    hits.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.source.cmp(&b.source))
+            .then_with(|| a.target.cmp(&b.target))
+    });
+}
+
 pub(crate) fn search_content_semantic(
     index: &VaultIndex,
     vault: &Vault,
@@ -49,15 +150,16 @@ pub(crate) fn search_content_semantic_with_min_score(
     limit: usize,
     min_score: f32,
 ) -> Result<Vec<SemanticSearchHit>> {
+    if min_score.is_nan() {
+        return Err(Error::Embedding(
+            "semantic search invalid min_score: NaN".to_string(),
+        ));
+    }
+    // Hits are normalized to 0..=1, so anything outside that range is equivalent to a bound.
+    let min_score = min_score.clamp(0.0, 1.0);
     if query.trim().is_empty() || limit == 0 {
         return Ok(Vec::new());
     }
-    if !(0.0..=1.0).contains(&min_score) {
-        return Err(Error::Embedding(format!(
-            "semantic search invalid min_score: {}",
-            min_score
-        )));
-    }
 
     let cfg = vault.config();
     let note_count = index.notes_iter_paths().count();
@@ -83,24 +185,22 @@ pub(crate) fn search_content_semantic_with_min_score(
     }
 
     let model = EmbeddingModel::load_cached(vault)?;
-    let mut store = SqliteIndexStore::open_default(vault)?;
     let embed_start = Instant::now();
-    store.ensure_embeddings(vault, index, model.as_ref())?;
+    let store = EmbeddingStore::prepare(vault, index, model.as_ref())?;
     debug!(
         elapsed_ms = embed_start.elapsed().as_millis(),
         "semantic search embeddings ready"
     );
 
-    let cleaned_query = clean_markdown_for_embedding(query);
+    let cleaned_query = clean_markdown_for_embedding(query, false);
     if cleaned_query.is_empty() {
         return Ok(Vec::new());
     }
     let query_embedding = model.embed_text(&cleaned_query)?;
-    let candidates = store.knn_for_embedding(query_embedding.as_bytes(), limit)?;
+    let candidates = store.nearest(&query_embedding, limit)?;
     let candidate_count = candidates.len();
     let mut hits = Vec::new();
-    for (path, distance) in candidates {
-        let score = distance_to_cosine(distance);
+    for (path, score) in candidates {
         if score < min_score {
             continue;
         }
@@ -177,9 +277,8 @@ pub fn note_similarity_report_with_settings(
     }
 
     let model = EmbeddingModel::load_cached(vault)?;
-    let mut store = SqliteIndexStore::open_default(vault)?;
     let embed_start = Instant::now();
-    store.ensure_embeddings(vault, index, model.as_ref())?;
+    let store = EmbeddingStore::prepare(vault, index, model.as_ref())?;
     debug!(
         elapsed_ms = embed_start.elapsed().as_millis(),
         "note similarity embeddings ready"
@@ -198,14 +297,13 @@ pub fn note_similarity_report_with_settings(
             Some(v) => v,
             None => continue,
         };
-        let candidates = store.knn_for_embedding(embedding.as_bytes(), settings.top_k + 1)?;
+        let candidates = store.nearest(&embedding, settings.top_k + 1)?;
         total_candidates += candidates.len();
-        for (target, distance) in candidates {
+        for (target, score) in candidates {
             if &target == source {
                 continue;
             }
             report.pairs_checked += 1;
-            let score = distance_to_cosine(distance);
             if score < settings.min_score {
                 continue;
             }
@@ -221,13 +319,7 @@ pub fn note_similarity_report_with_settings(
         }
     }
 
-    report.hits.sort_by(|a, b| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-            .then_with(|| a.source.cmp(&b.source))
-            .then_with(|| a.target.cmp(&b.target))
-    });
+    sort_note_hits(&mut report.hits);
 
     let elapsed_ms = start.elapsed().as_millis();
     let avg_candidates = if note_paths.is_empty() {
@@ -260,9 +352,8 @@ pub(crate) fn note_similarity_for(
         "note similarity query start"
     );
     let model = EmbeddingModel::load_cached(vault)?;
-    let mut store = SqliteIndexStore::open_default(vault)?;
     let embed_start = Instant::now();
-    store.ensure_embeddings(vault, index, model.as_ref())?;
+    let store = EmbeddingStore::prepare(vault, index, model.as_ref())?;
     debug!(
         elapsed_ms = embed_start.elapsed().as_millis(),
         "note similarity embeddings ready"
@@ -272,14 +363,13 @@ pub(crate) fn note_similarity_for(
         Some(v) => v,
         None => return Ok(Vec::new()),
     };
-    let candidates = store.knn_for_embedding(embedding.as_bytes(), cfg.similarity_top_k + 1)?;
+    let candidates = store.nearest(&embedding, cfg.similarity_top_k + 1)?;
     let candidate_count = candidates.len();
     let mut hits = Vec::new();
-    for (target, distance) in candidates {
+    for (target, score) in candidates {
         if &target == source {
             continue;
         }
-        let score = distance_to_cosine(distance);
         if score < cfg.similarity_min_score {
             continue;
         }
@@ -289,11 +379,7 @@ pub(crate) fn note_similarity_for(
             score,
         });
     }
-    hits.sort_by(|a, b| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    sort_note_hits(&mut hits);
     debug!(
         hit_count = hits.len(),
         candidate_count,
@@ -303,6 +389,120 @@ pub(crate) fn note_similarity_for(
     Ok(hits)
 }
 
+/// Note embeddings for one similarity run.
+///
+/// Backed by the SQLite vector table when built with `sqlite`; otherwise every note is
+/// embedded in memory and neighbours are found by brute-force cosine.
+enum EmbeddingStore {
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteIndexStore),
+    #[cfg(not(feature = "sqlite"))]
+    Memory(InMemoryEmbeddings),
+}
+
+impl EmbeddingStore {
+    fn prepare(vault: &Vault, index: &VaultIndex, model: &EmbeddingModel) -> Result<Self> {
+        #[cfg(feature = "sqlite")]
+        {
+            let mut store = SqliteIndexStore::open_default(vault)?;
+            store.ensure_embeddings(vault, index, model)?;
+            Ok(Self::Sqlite(store))
+        }
+        #[cfg(not(feature = "sqlite"))]
+        {
+            InMemoryEmbeddings::build(vault, index, model).map(Self::Memory)
+        }
+    }
+
+    fn embedding_for_path(&self, path: &VaultPath) -> Result<Option<Vec<f32>>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => store.embedding_for_path(path),
+            #[cfg(not(feature = "sqlite"))]
+            Self::Memory(mem) => Ok(mem.embedding_for_path(path).map(<[f32]>::to_vec)),
+        }
+    }
+
+    /// Up to `k` notes closest to `query`, as `(path, cosine score)` pairs, best first.
+    fn nearest(&self, query: &[f32], k: usize) -> Result<Vec<(VaultPath, f32)>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite(store) => Ok(store
+                .knn_for_embedding(query.as_bytes(), k)?
+                .into_iter()
+                .map(|(path, distance)| (path, distance_to_cosine(distance)))
+                .collect()),
+            #[cfg(not(feature = "sqlite"))]
+            Self::Memory(mem) => Ok(mem.nearest(query, k)),
+        }
+    }
+}
+
+/// Embeddings for every note, keyed (and iterated) by path.
+#[cfg(not(feature = "sqlite"))]
+struct InMemoryEmbeddings {
+    entries: BTreeMap<VaultPath, Vec<f32>>,
+}
+
+#[cfg(not(feature = "sqlite"))]
+impl InMemoryEmbeddings {
+    fn build(vault: &Vault, index: &VaultIndex, model: &EmbeddingModel) -> Result<Self> {
+        let mut paths: Vec<VaultPath> = index.notes_iter_paths().cloned().collect();
+        paths.sort();
+        info!(total_notes = paths.len(), "in-memory embedding start");
+        let mut entries = BTreeMap::new();
+        for path in paths {
+            let abs = vault.to_abs(&path);
+            let text = match std::fs::read_to_string(&abs) {
+                Ok(v) => v,
+                Err(err) => {
+                    warn!(path = abs.display().to_string(), error = %err, "failed to read note for embedding");
+                    return Err(Error::io(&abs, err));
+                }
+            };
+            let embedding = model.embed_text(&note_text_for_embedding(vault.config(), &text))?;
+            trace!(path = path.as_str_lossy(), "embedding computed");
+            entries.insert(path, embedding);
+        }
+        Ok(Self { entries })
+    }
+
+    fn embedding_for_path(&self, path: &VaultPath) -> Option<&[f32]> {
+        self.entries.get(path).map(Vec::as_slice)
+    }
+
+    fn nearest(&self, query: &[f32], k: usize) -> Vec<(VaultPath, f32)> {
+        let mut scored: Vec<(VaultPath, f32)> = self
+            .entries
+            .iter()
+            .map(|(path, v)| (path.clone(), cosine_similarity(query, v)))
+            .collect();
+        scored.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        scored.truncate(k);
+        scored
+    }
+}
+
+/// Cosine of the angle between `a` and `b`, clamped to `0.0..=1.0` like the KNN scores.
+#[cfg(not(feature = "sqlite"))]
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    (dot / (norm_a.sqrt() * norm_b.sqrt())).clamp(0.0, 1.0)
+}
+
+#[cfg(feature = "sqlite")]
 fn distance_to_cosine(distance: f32) -> f32 {
     let score = 1.0 - (distance * distance) / 2.0;
     if score < 0.0 {
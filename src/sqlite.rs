@@ -14,7 +14,9 @@ use tracing::{info, trace, warn};
 use zerocopy::AsBytes;
 
 #[cfg(feature = "similarity")]
-use crate::embeddings::{EmbeddingModel, clean_markdown_for_embedding, hash_text};
+use crate::embeddings::{EmbeddingModel, note_text_for_embedding};
+#[cfg(feature = "similarity")]
+use crate::index::hash_text;
 use crate::{
     Error, FileKind, FrontmatterStatus, Link, LinkKind, LinkTarget, NoteMeta, Result, Subpath,
     TaskStatus, Vault, VaultIndex, VaultPath,
@@ -24,6 +26,17 @@ pub struct SqliteIndexStore {
     conn: Connection,
 }
 
+/// Outcome of [`SqliteIndexStore::sync_index`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct IndexSyncStats {
+    /// Files whose rows were (re)written.
+    pub upserted: usize,
+    /// Files removed from the store because they are no longer indexed.
+    pub removed: usize,
+    /// Files whose persisted mtime/size already matched.
+    pub unchanged: usize,
+}
+
 #[cfg(feature = "similarity")]
 static VEC_INIT: Once = Once::new();
 
@@ -113,7 +126,84 @@ impl SqliteIndexStore {
             .conn
             .transaction()
             .map_err(|e| Error::InvalidVaultPath(e.to_string()))?;
-        let p = path.as_str_lossy();
+        Self::remove_path_in_tx(&tx, &path.as_str_lossy())?;
+        tx.commit()
+            .map_err(|e| Error::InvalidVaultPath(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Bring the store in line with `index`, rewriting only files whose kind,
+    /// mtime or size differ from the persisted `files` row and deleting rows for
+    /// files that are no longer indexed.
+    pub fn sync_index(&mut self, vault: &Vault, index: &VaultIndex) -> Result<IndexSyncStats> {
+        let persisted = self.persisted_file_stamps()?;
+        let tx = self
+            .conn
+            .transaction()
+            .map_err(|e| Error::InvalidVaultPath(e.to_string()))?;
+
+        let mut stats = IndexSyncStats::default();
+        let mut seen = std::collections::HashSet::new();
+        for f in index.all_files() {
+            let p = f.path.as_str_lossy();
+            let stamp = (
+                file_kind_int(f.kind),
+                system_time_to_unix(f.mtime),
+                f.size as i64,
+            );
+            if persisted.get(&p) == Some(&stamp) {
+                stats.unchanged += 1;
+            } else {
+                Self::upsert_path_in_tx(vault, index, &tx, &f.path)?;
+                stats.upserted += 1;
+            }
+            seen.insert(p);
+        }
+
+        for p in persisted.keys() {
+            if !seen.contains(p) {
+                Self::remove_path_in_tx(&tx, p)?;
+                stats.removed += 1;
+            }
+        }
+
+        tx.commit()
+            .map_err(|e| Error::InvalidVaultPath(e.to_string()))?;
+        debug!(
+            upserted = stats.upserted,
+            removed = stats.removed,
+            unchanged = stats.unchanged,
+            "sqlite index synced"
+        );
+        Ok(stats)
+    }
+
+    fn persisted_file_stamps(&self) -> Result<std::collections::HashMap<String, (i64, i64, i64)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, kind, mtime, size FROM files")
+            .map_err(|e| Error::InvalidVaultPath(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |r| {
+                Ok((
+                    r.get::<_, String>(0)?,
+                    (
+                        r.get::<_, i64>(1)?,
+                        r.get::<_, i64>(2)?,
+                        r.get::<_, i64>(3)?,
+                    ),
+                ))
+            })
+            .map_err(|e| Error::InvalidVaultPath(e.to_string()))?;
+        let mut out = std::collections::HashMap::new();
+        for row in rows {
+            let (path, stamp) = row.map_err(|e| Error::InvalidVaultPath(e.to_string()))?;
+            out.insert(path, stamp);
+        }
+        Ok(out)
+    }
+
+    fn remove_path_in_tx(tx: &rusqlite::Transaction<'_>, p: &str) -> Result<()> {
         tx.execute("DELETE FROM links WHERE src_path=?1", params![p])
             .map_err(|e| Error::InvalidVaultPath(e.to_string()))?;
         tx.execute("DELETE FROM tasks WHERE path=?1", params![p])
@@ -131,8 +221,6 @@ impl SqliteIndexStore {
             tx.execute("DELETE FROM note_embedding_meta WHERE path=?1", params![p])
                 .map_err(|e| Error::InvalidVaultPath(e.to_string()))?;
         }
-        tx.commit()
-            .map_err(|e| Error::InvalidVaultPath(e.to_string()))?;
         Ok(())
     }
 
@@ -173,7 +261,7 @@ impl SqliteIndexStore {
                     return Err(Error::io(&abs, err));
                 }
             };
-            let cleaned = clean_markdown_for_embedding(&text);
+            let cleaned = note_text_for_embedding(vault.config(), &text);
             let hash = hash_text(&cleaned);
             let stored_hash = self.embedding_hash(path)?;
             if stored_hash.as_deref() == Some(hash.as_str()) {
@@ -22,6 +22,17 @@ impl VaultPath {
     pub fn as_str_lossy(&self) -> String {
         self.0.to_string_lossy().to_string()
     }
+
+    /// Renders the path with the platform separator, for human-facing output only.
+    ///
+    /// Use [`VaultPath::as_str_lossy`] for matching and serialization.
+    pub fn display_native(&self) -> String {
+        self.0
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(std::path::MAIN_SEPARATOR_STR)
+    }
 }
 
 impl TryFrom<&Path> for VaultPath {
@@ -79,6 +90,12 @@ impl Vault {
         if !root.exists() {
             return Err(Error::VaultNotFound(root));
         }
+        if !root.is_dir() {
+            return Err(Error::InvalidVaultPath(format!(
+                "not a directory: {}",
+                root.display()
+            )));
+        }
         let root = std::fs::canonicalize(&root).map_err(|e| Error::io(&root, e))?;
         Ok(Self { root, cfg })
     }
@@ -91,10 +108,27 @@ impl Vault {
         &self.cfg
     }
 
+    /// Mutable access to the config.
+    ///
+    /// Indexes already built from this vault are not updated: changes to extensions
+    /// (including `text_attachment_extensions`), `ignore_dirs`, `max_walk_depth`,
+    /// `tag_charset`, `field_merge_policy`, `indented_code_blocks`, `detect_language`,
+    /// `task_marks` or `schema_path` only apply to the next build. Prefer
+    /// [`VaultService::set_config`](crate::VaultService::set_config), which rebuilds
+    /// for you.
+    pub fn config_mut(&mut self) -> &mut VaultConfig {
+        &mut self.cfg
+    }
+
     pub fn to_abs(&self, rel: &VaultPath) -> PathBuf {
         self.root.join(rel.as_path())
     }
 
+    /// Converts an absolute (or root-relative) path into a [`VaultPath`].
+    ///
+    /// Fails with [`Error::PathOutsideVault`] when the path does not lie under the root,
+    /// and with [`Error::InvalidVaultPath`] when what remains is not a valid vault path
+    /// (e.g. the root itself, or `..` left over from a path that does not exist).
     pub fn to_rel(&self, abs: &Path) -> Result<VaultPath> {
         let abs = if abs.is_absolute() {
             abs.to_path_buf()
@@ -131,6 +165,12 @@ impl Vault {
         if rel.as_os_str().is_empty() {
             return false;
         }
+
+        if let Some(max) = self.cfg.max_walk_depth
+            && rel.components().count() > max
+        {
+            return false;
+        }
         let file_name = rel.file_name().and_then(|s| s.to_str()).unwrap_or("");
         if file_name.starts_with('.') {
             // Obsidian notes can be dotfiles, but default to ignoring.
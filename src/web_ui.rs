@@ -76,6 +76,8 @@ enum ClientMessage {
         enabled: bool,
         min_score: f32,
         top_k: usize,
+        #[serde(default)]
+        edges: bool,
     },
 }
 
@@ -101,6 +103,8 @@ struct GraphEdge {
     id: String,
     source: String,
     target: String,
+    /// `link`, `tag`, or `similarity`.
+    kind: String,
 }
 
 #[derive(Debug, Serialize, Clone, Copy)]
@@ -109,6 +113,7 @@ struct SimilarityMeta {
     enabled: bool,
     min_score: f32,
     top_k: usize,
+    edges: bool,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -116,6 +121,7 @@ struct SimilarityConfig {
     enabled: bool,
     min_score: f32,
     top_k: usize,
+    edges: bool,
 }
 
 impl Default for SimilarityConfig {
@@ -124,10 +130,18 @@ impl Default for SimilarityConfig {
             enabled: false,
             min_score: DEFAULT_SIMILARITY_MIN_SCORE,
             top_k: DEFAULT_SIMILARITY_TOP_K,
+            edges: false,
         }
     }
 }
 
+/// Similarity data layered over the link graph: cluster ids and/or note pairs to draw as edges.
+#[derive(Debug, Default)]
+struct SimilarityOverlay {
+    clusters: Option<BTreeMap<String, u32>>,
+    edges: Vec<(String, String)>,
+}
+
 /// Resolve an on-disk static directory for the UI, allowing overrides
 /// during development. Falls back to embedded assets when unset.
 fn resolve_static_dir() -> Option<PathBuf> {
@@ -283,10 +297,12 @@ fn handle_client_message(text: &str) -> Option<SimilarityConfig> {
             enabled,
             min_score,
             top_k,
+            edges,
         } => Some(normalize_similarity_settings(SimilarityConfig {
             enabled,
             min_score,
             top_k,
+            edges,
         })),
     }
 }
@@ -298,6 +314,7 @@ fn normalize_similarity_settings(settings: SimilarityConfig) -> SimilarityConfig
         enabled: settings.enabled,
         min_score,
         top_k,
+        edges: settings.edges,
     }
 }
 
@@ -334,12 +351,11 @@ fn graph_payload(
 ) -> anyhow::Result<GraphPayload> {
     let snapshot = service.index_snapshot();
     let graph = snapshot.build_graph(service.vault())?;
-    let (cluster_ids, similarity_meta) =
-        similarity_cluster_ids(service, &snapshot, similarity_settings)?;
+    let (overlay, similarity_meta) = similarity_overlay(service, &snapshot, similarity_settings)?;
     Ok(build_graph_payload(
         &snapshot,
         &graph,
-        cluster_ids.as_ref(),
+        &overlay,
         similarity_meta,
     ))
 }
@@ -350,60 +366,70 @@ fn similarity_meta(settings: SimilarityConfig) -> SimilarityMeta {
         enabled: cfg!(feature = "similarity") && settings.enabled,
         min_score: settings.min_score,
         top_k: settings.top_k,
+        edges: cfg!(feature = "similarity") && settings.edges,
     }
 }
 
-fn similarity_cluster_ids(
+fn similarity_overlay(
     service: &VaultService,
     snapshot: &VaultIndex,
     settings: SimilarityConfig,
-) -> anyhow::Result<(Option<BTreeMap<String, u32>>, SimilarityMeta)> {
+) -> anyhow::Result<(SimilarityOverlay, SimilarityMeta)> {
     let meta = similarity_meta(settings);
     #[cfg(feature = "similarity")]
     {
-        if !settings.enabled {
-            return Ok((None, meta));
+        if !settings.enabled && !settings.edges {
+            return Ok((SimilarityOverlay::default(), meta));
         }
-        let settings = SimilaritySettings {
-            min_score: settings.min_score,
+        let min_score = settings.min_score;
+        let report = match service.note_similarity_report_with_settings(SimilaritySettings {
+            min_score,
             top_k: settings.top_k,
-        };
-        let report = match service.note_similarity_report_with_settings(settings) {
+        }) {
             Ok(report) => report,
             Err(err) => {
                 warn!(error = %err, "failed to compute similarity clusters");
                 return Ok((
-                    None,
+                    SimilarityOverlay::default(),
                     SimilarityMeta {
                         enabled: false,
+                        edges: false,
                         ..meta
                     },
                 ));
             }
         };
-        let note_ids: Vec<String> = snapshot
-            .all_files()
-            .filter(|f| matches!(f.kind, FileKind::Markdown | FileKind::Canvas))
-            .map(|f| f.path.as_str_lossy().to_string())
-            .collect();
-        let clusters = cluster_ids_from_hits(&note_ids, &report.hits);
-        return Ok((Some(clusters), meta));
+        let clusters = settings.enabled.then(|| {
+            let note_ids: Vec<String> = snapshot
+                .all_files()
+                .filter(|f| matches!(f.kind, FileKind::Markdown | FileKind::Canvas))
+                .map(|f| f.path.as_str_lossy().to_string())
+                .collect();
+            cluster_ids_from_hits(&note_ids, &report.hits)
+        });
+        let edges = if settings.edges {
+            similarity_edge_pairs(&report.hits, min_score)
+        } else {
+            Vec::new()
+        };
+        return Ok((SimilarityOverlay { clusters, edges }, meta));
     }
     #[cfg(not(feature = "similarity"))]
     {
         let _ = service;
         let _ = snapshot;
         let _ = settings;
-        Ok((None, meta))
+        Ok((SimilarityOverlay::default(), meta))
     }
 }
 
 fn build_graph_payload(
     snapshot: &VaultIndex,
     graph: &GraphIndex,
-    clusters: Option<&BTreeMap<String, u32>>,
+    overlay: &SimilarityOverlay,
     similarity: SimilarityMeta,
 ) -> GraphPayload {
+    let clusters = overlay.clusters.as_ref();
     let mut nodes: BTreeMap<String, GraphNode> = BTreeMap::new();
     let mut edges = Vec::new();
     let mut edge_keys: BTreeSet<String> = BTreeSet::new();
@@ -423,7 +449,7 @@ fn build_graph_payload(
         }
     }
 
-    for target in graph.backlinks.targets() {
+    for target in graph.backlinks.targets_sorted() {
         let target_id = target.as_str_lossy().to_string();
         insert_node(snapshot, target, &mut nodes, clusters);
 
@@ -434,6 +460,24 @@ fn build_graph_payload(
         }
     }
 
+    // Similarity is symmetric, so skip pairs already joined by a link in either direction.
+    for (source_id, target_id) in &overlay.edges {
+        if !nodes.contains_key(source_id) || !nodes.contains_key(target_id) {
+            continue;
+        }
+        let linked = edge_keys.contains(&format!("link:{source_id}->{target_id}"))
+            || edge_keys.contains(&format!("link:{target_id}->{source_id}"));
+        if !linked {
+            insert_edge(
+                source_id,
+                target_id,
+                "similarity",
+                &mut edges,
+                &mut edge_keys,
+            );
+        }
+    }
+
     let mut degree: BTreeMap<String, usize> = BTreeMap::new();
     for edge in &edges {
         *degree.entry(edge.source.clone()).or_default() += 1;
@@ -469,6 +513,7 @@ fn insert_edge(
             id: key,
             source: source_id.to_string(),
             target: target_id.to_string(),
+            kind: kind.to_string(),
         });
     }
 }
@@ -488,13 +533,22 @@ fn insert_node(
         .file(path)
         .map(|meta| file_kind_label(meta.kind))
         .unwrap_or("other");
+    // Notes carry a title (frontmatter, H1, or filename); other files use their file name.
+    let label = match snapshot.note(path) {
+        Some(note) => note.title.clone(),
+        None => path
+            .as_path()
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| id.clone()),
+    };
 
     let cluster_id = clusters.and_then(|map| map.get(&id).copied());
     nodes.insert(
         id.clone(),
         GraphNode {
             id: id.clone(),
-            label: id,
+            label,
             kind: kind.to_string(),
             size: 1.0,
             cluster_id,
@@ -528,6 +582,28 @@ fn file_kind_label(kind: FileKind) -> &'static str {
     }
 }
 
+/// Unordered note pairs scoring at least `min_score`, each listed once.
+#[cfg(feature = "similarity")]
+fn similarity_edge_pairs(hits: &[NoteSimilarityHit], min_score: f32) -> Vec<(String, String)> {
+    let mut pairs: BTreeSet<(String, String)> = BTreeSet::new();
+    for hit in hits {
+        if hit.score < min_score {
+            continue;
+        }
+        let source = hit.source.as_str_lossy().to_string();
+        let target = hit.target.as_str_lossy().to_string();
+        if source == target {
+            continue;
+        }
+        if source < target {
+            pairs.insert((source, target));
+        } else {
+            pairs.insert((target, source));
+        }
+    }
+    pairs.into_iter().collect()
+}
+
 #[cfg(feature = "similarity")]
 fn cluster_ids_from_hits(note_ids: &[String], hits: &[NoteSimilarityHit]) -> BTreeMap<String, u32> {
     let mut adjacency: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
@@ -600,12 +676,13 @@ mod tests {
         let payload = build_graph_payload(
             &snapshot,
             &graph,
-            None,
+            &SimilarityOverlay::default(),
             SimilarityMeta {
                 available: false,
                 enabled: false,
                 min_score: DEFAULT_SIMILARITY_MIN_SCORE,
                 top_k: DEFAULT_SIMILARITY_TOP_K,
+                edges: false,
             },
         );
 
@@ -631,6 +708,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn graph_nodes_are_labeled_with_note_titles() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(
+            dir.path().join("alpha.md"),
+            "---\ntitle: Alpha Project\n---\n[[beta]] #tag-a",
+        )
+        .expect("write alpha");
+        std::fs::write(dir.path().join("beta.md"), "beta note").expect("write beta");
+
+        let vault = Vault::open(dir.path()).expect("vault open");
+        let service = VaultService::new(vault).expect("service");
+
+        let rt = tokio::runtime::Runtime::new().expect("runtime");
+        rt.block_on(service.build_index()).expect("build index");
+
+        let snapshot = service.index_snapshot();
+        let graph = snapshot.build_graph(service.vault()).expect("graph");
+        let payload = build_graph_payload(
+            &snapshot,
+            &graph,
+            &SimilarityOverlay::default(),
+            SimilarityMeta {
+                available: false,
+                enabled: false,
+                min_score: DEFAULT_SIMILARITY_MIN_SCORE,
+                top_k: DEFAULT_SIMILARITY_TOP_K,
+                edges: false,
+            },
+        );
+
+        let label = |id: &str| {
+            payload
+                .nodes
+                .iter()
+                .find(|node| node.id == id)
+                .map(|node| node.label.clone())
+        };
+        assert_eq!(label("alpha.md").as_deref(), Some("Alpha Project"));
+        assert_eq!(label("beta.md").as_deref(), Some("beta"));
+        assert_eq!(label("tag:tag-a").as_deref(), Some("#tag-a"));
+    }
+
     #[cfg(feature = "similarity")]
     #[test]
     fn clusters_group_connected_hits() {
@@ -652,4 +772,66 @@ mod tests {
         assert_eq!(clusters.get("a.md"), clusters.get("b.md"));
         assert_ne!(clusters.get("a.md"), clusters.get("c.md"));
     }
+
+    #[cfg(feature = "similarity")]
+    #[test]
+    fn similarity_edges_are_added_without_duplicating_links() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        std::fs::write(dir.path().join("a.md"), "[[b]]").expect("write a");
+        std::fs::write(dir.path().join("b.md"), "b note").expect("write b");
+        std::fs::write(dir.path().join("c.md"), "c note").expect("write c");
+
+        let vault = Vault::open(dir.path()).expect("vault open");
+        let service = VaultService::new(vault).expect("service");
+
+        let rt = tokio::runtime::Runtime::new().expect("runtime");
+        rt.block_on(service.build_index()).expect("build index");
+
+        let hit = |source: &str, target: &str, score: f32| NoteSimilarityHit {
+            source: VaultPath::try_from(std::path::Path::new(source)).expect("source"),
+            target: VaultPath::try_from(std::path::Path::new(target)).expect("target"),
+            score,
+        };
+        let hits = vec![
+            hit("b.md", "a.md", 0.9),
+            hit("a.md", "c.md", 0.8),
+            hit("c.md", "a.md", 0.8),
+            hit("b.md", "c.md", 0.1),
+        ];
+
+        let snapshot = service.index_snapshot();
+        let graph = snapshot.build_graph(service.vault()).expect("graph");
+        let overlay = SimilarityOverlay {
+            clusters: None,
+            edges: similarity_edge_pairs(&hits, 0.6),
+        };
+        let payload = build_graph_payload(
+            &snapshot,
+            &graph,
+            &overlay,
+            SimilarityMeta {
+                available: true,
+                enabled: false,
+                min_score: 0.6,
+                top_k: DEFAULT_SIMILARITY_TOP_K,
+                edges: true,
+            },
+        );
+
+        let similarity: Vec<(&str, &str)> = payload
+            .edges
+            .iter()
+            .filter(|edge| edge.kind == "similarity")
+            .map(|edge| (edge.source.as_str(), edge.target.as_str()))
+            .collect();
+        assert_eq!(similarity, vec![("a.md", "c.md")]);
+        assert_eq!(
+            payload
+                .edges
+                .iter()
+                .filter(|edge| edge.kind == "link")
+                .count(),
+            1
+        );
+    }
 }
@@ -34,7 +34,48 @@ async fn aliases_are_extracted_from_frontmatter() -> anyhow::Result<()> {
             "notes/b.md",
         ))?)
         .expect("b present");
-    assert!(b.aliases.contains("hello, world"));
+    assert!(b.aliases.contains("hello"));
+    assert!(b.aliases.contains("world"));
+    assert_eq!(b.aliases.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn aliases_split_comma_and_newline_separated_strings() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    std::fs::write(
+        vault_root.join("notes/comma.md"),
+        "---\naliases: \"One, Two Words ,three\"\n---\n",
+    )?;
+    std::fs::write(
+        vault_root.join("notes/block.md"),
+        "---\naliases: |\n  First Alias\n   Second\n\n  THIRD\n---\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+    let idx = service.index_snapshot();
+
+    let comma = idx
+        .note(&oxidian::VaultPath::try_from(std::path::Path::new(
+            "notes/comma.md",
+        ))?)
+        .expect("comma present");
+    let got: Vec<&str> = comma.aliases.iter().map(String::as_str).collect();
+    assert_eq!(got, vec!["one", "three", "two words"]);
+
+    let block = idx
+        .note(&oxidian::VaultPath::try_from(std::path::Path::new(
+            "notes/block.md",
+        ))?)
+        .expect("block present");
+    let got: Vec<&str> = block.aliases.iter().map(String::as_str).collect();
+    assert_eq!(got, vec!["first alias", "second", "third"]);
 
     Ok(())
 }
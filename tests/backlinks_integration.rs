@@ -39,3 +39,75 @@ async fn backlinks_are_built_from_resolved_internal_links() -> anyhow::Result<()
 
     Ok(())
 }
+
+#[tokio::test]
+async fn unreferenced_lists_notes_without_inbound_links() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    std::fs::write(vault_root.join("notes/hub.md"), "[[linked]]\n")?;
+    std::fs::write(vault_root.join("notes/linked.md"), "# Linked\n")?;
+    std::fs::write(vault_root.join("notes/lonely.md"), "[[lonely]]\n")?;
+    std::fs::write(vault_root.join("board.canvas"), "{}")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let backlinks = service.build_backlinks()?;
+    let unreferenced: Vec<String> = service
+        .with_index(|idx| backlinks.unreferenced(idx))
+        .iter()
+        .map(|p| p.as_str_lossy())
+        .collect();
+
+    // A self-link does not make a note referenced; canvases are not listed.
+    assert_eq!(unreferenced, vec!["notes/hub.md", "notes/lonely.md"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn targets_sorted_is_stable_path_order() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    for name in ["delta", "alpha", "charlie", "bravo"] {
+        std::fs::write(vault_root.join(format!("notes/{name}.md")), "# x\n")?;
+    }
+    std::fs::write(
+        vault_root.join("notes/hub.md"),
+        "[[delta]] [[alpha]] [[charlie]] [[bravo]]\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let backlinks = service.build_backlinks()?;
+    let first: Vec<String> = backlinks
+        .targets_sorted()
+        .iter()
+        .map(|p| p.as_str_lossy())
+        .collect();
+    let second: Vec<String> = service
+        .build_backlinks()?
+        .targets_sorted()
+        .iter()
+        .map(|p| p.as_str_lossy())
+        .collect();
+    assert_eq!(
+        first,
+        vec![
+            "notes/alpha.md",
+            "notes/bravo.md",
+            "notes/charlie.md",
+            "notes/delta.md"
+        ]
+    );
+    assert_eq!(first, second);
+
+    Ok(())
+}
@@ -89,7 +89,8 @@ fn stats_text_output() {
         .success()
         .stdout(predicate::str::contains("files:"))
         .stdout(predicate::str::contains("notes:"))
-        .stdout(predicate::str::contains("tags:"));
+        .stdout(predicate::str::contains("tags:"))
+        .stdout(predicate::str::contains("    markdown: 3"));
 }
 
 #[test]
@@ -140,7 +141,61 @@ fn stats_with_tag_filter_json() {
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     assert_eq!(json["ok"], true);
     assert_eq!(json["data"]["tag_filter"], "rust");
-    assert!(json["data"]["tagged_files"].as_array().unwrap().len() >= 2);
+    let tagged = json["data"]["tagged_files"].as_array().unwrap();
+    assert!(tagged.len() >= 2);
+    let hello = tagged
+        .iter()
+        .find(|f| f["path"] == "notes/hello.md")
+        .expect("hello is tagged #rust");
+    assert_eq!(hello["title"], "Hello World");
+}
+
+#[test]
+fn stats_other_groups_unknown_files_by_extension() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+    fs::write(vault.join("notes/data.foo"), "x").unwrap();
+    fs::write(vault.join("more.FOO"), "y").unwrap();
+    fs::write(vault.join("Makefile"), "all:").unwrap();
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "stats",
+            "--other",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let other = &json["data"]["other_files"];
+    assert_eq!(other["foo"]["count"], 2);
+    assert_eq!(
+        other["foo"]["paths"],
+        serde_json::json!(["more.FOO", "notes/data.foo"])
+    );
+    assert_eq!(other[""]["paths"], serde_json::json!(["Makefile"]));
+}
+
+#[test]
+fn stats_with_tag_filter_text_lists_titles() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    cmd()
+        .args(["--vault", vault.to_str().unwrap(), "stats", "--tag", "rust"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- notes/hello.md\tHello World"))
+        .stdout(predicate::str::contains(
+            "- notes/other-note.md\tOther Note",
+        ));
 }
 
 // ---------------------------------------------------------------------------
@@ -222,6 +277,48 @@ fn tasks_json_output() {
     assert!(tasks[0]["line"].is_u64());
 }
 
+#[test]
+fn tasks_csv_output_has_header_and_quoted_rows() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+    fs::write(
+        vault.join("notes/shopping.md"),
+        "- [ ] milk, eggs and \"bread\"\n",
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args(["--vault", vault.to_str().unwrap(), "-o", "csv", "tasks"])
+        .args(["--sort", "path"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "path,line,status,text,due,done,priority,tags");
+    assert_eq!(lines.len(), 1 + 3);
+    assert!(lines.contains(&"notes/shopping.md,1,todo,\"milk, eggs and \"\"bread\"\"\",,,,"));
+
+    // Empty results still print the header.
+    let output = cmd()
+        .args(["--vault", vault.to_str().unwrap(), "-o", "csv", "tasks"])
+        .args(["--status", "cancelled"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "path,line,status,text,due,done,priority,tags\n"
+    );
+
+    cmd()
+        .args(["--vault", vault.to_str().unwrap(), "-o", "csv", "stats"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("-o csv is only supported by"));
+}
+
 #[test]
 fn tasks_filter_by_status_json() {
     let tmp = tempfile::tempdir().unwrap();
@@ -249,6 +346,130 @@ fn tasks_filter_by_status_json() {
     }
 }
 
+#[test]
+fn tasks_path_lists_only_that_note() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(vault.join("a")).unwrap();
+    fs::write(vault.join("a/b.md"), "- [ ] mine\n- [x] also mine\n").unwrap();
+    fs::write(vault.join("a/bc.md"), "- [ ] sibling\n").unwrap();
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "tasks",
+            "--path",
+            "a/b.md",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let tasks = json["data"].as_array().unwrap();
+    assert_eq!(tasks.len(), 2);
+    for task in tasks {
+        assert_eq!(task["path"], "a/b.md");
+    }
+
+    cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "tasks",
+            "--path",
+            "a/missing.md",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("note not found"));
+}
+
+#[test]
+fn links_external_groups_sources_by_url() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(&vault).unwrap();
+    fs::write(
+        vault.join("a.md"),
+        "See [docs](https://example.com/docs) and <mailto:me@example.com>.\n",
+    )
+    .unwrap();
+    fs::write(
+        vault.join("b.md"),
+        "Also <https://example.com/docs> here.\n",
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "links",
+            "--external",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let urls = json["data"]["urls"].as_array().unwrap();
+    assert_eq!(urls[0]["url"], "https://example.com/docs");
+    assert_eq!(urls[0]["count"], 2);
+    let sources: Vec<&str> = urls[0]["sources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["source"].as_str().unwrap())
+        .collect();
+    assert_eq!(sources, vec!["a.md", "b.md"]);
+    assert_eq!(
+        urls.iter()
+            .filter(|u| u["url"] == "https://example.com/docs")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn links_subpath_usage_tallies_headings_and_blocks() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(&vault).unwrap();
+    fs::write(
+        vault.join("a.md"),
+        "[[b#Setup]] [[b#Setup]] [[b#Usage]] [[b#^x1]] [[b]]\n",
+    )
+    .unwrap();
+    fs::write(
+        vault.join("b.md"),
+        "# Setup\n# Usage\nLine ^x1\nSee [site](https://example.com).\n",
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args(["--vault", vault.to_str().unwrap(), "-o", "json"])
+        .args(["links", "--subpath-usage"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let data = &json["data"];
+    assert_eq!(data["whole_note"], 1);
+    assert_eq!(data["heading"], 3);
+    assert_eq!(data["block"], 1);
+    let top = data["top_headings"].as_array().unwrap();
+    assert_eq!(top[0]["target"], "b");
+    assert_eq!(top[0]["heading"], "Setup");
+    assert_eq!(top[0]["count"], 2);
+    assert_eq!(top.len(), 2);
+}
+
 // ---------------------------------------------------------------------------
 // search
 // ---------------------------------------------------------------------------
@@ -372,6 +593,47 @@ fn links_json_output() {
     assert!(json["data"]["links"].is_array());
 }
 
+#[test]
+fn links_resolve_annotates_internal_occurrences() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(vault.join("a")).unwrap();
+    fs::create_dir_all(vault.join("b")).unwrap();
+    fs::write(
+        vault.join("src.md"),
+        "[[target]] [[nowhere]] [[dup]] [site](https://example.com)\n",
+    )
+    .unwrap();
+    fs::write(vault.join("target.md"), "# Target\n").unwrap();
+    fs::write(vault.join("a/dup.md"), "# A\n").unwrap();
+    fs::write(vault.join("b/dup.md"), "# B\n").unwrap();
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "links",
+            "src.md",
+            "--resolve",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let links = json["data"]["links"].as_array().unwrap();
+    assert_eq!(links.len(), 4);
+    assert_eq!(links[0]["resolution"]["resolved"], "target.md");
+    assert_eq!(links[1]["resolution"], "missing");
+    assert_eq!(
+        links[2]["resolution"]["ambiguous"],
+        serde_json::json!(["a/dup.md", "b/dup.md"])
+    );
+    assert!(links[3].get("resolution").is_none());
+}
+
 // ---------------------------------------------------------------------------
 // backlinks (positional note arg)
 // ---------------------------------------------------------------------------
@@ -421,6 +683,98 @@ fn backlinks_json_output() {
     assert!(json["data"]["backlinks"].is_array());
 }
 
+#[test]
+fn backlinks_include_mentions_lists_links_and_mentions() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+    fs::write(
+        vault.join("notes/journal.md"),
+        "Talked about other note again today.\n",
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "backlinks",
+            "notes/other-note.md",
+            "--include-mentions",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let backlinks = json["data"]["backlinks"].as_array().unwrap();
+    assert!(backlinks.iter().any(|b| b["source"] == "notes/hello.md"));
+    let mentions = json["data"]["mentions"].as_array().unwrap();
+    assert_eq!(mentions.len(), 1);
+    assert_eq!(mentions[0]["source"], "notes/journal.md");
+}
+
+#[test]
+fn backlinks_show_ambiguous_lists_candidates() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+    fs::create_dir_all(vault.join("a")).unwrap();
+    fs::create_dir_all(vault.join("b")).unwrap();
+    fs::write(vault.join("a/dup.md"), "# Dup A\n").unwrap();
+    fs::write(vault.join("b/dup.md"), "# Dup B\n").unwrap();
+    fs::write(vault.join("notes/pointer.md"), "See [[dup]].\n").unwrap();
+
+    cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "backlinks",
+            "notes/other-note.md",
+            "--show-ambiguous",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "- ambiguous notes/pointer.md:1\traw=\"dup\"\tcandidates=a/dup.md,b/dup.md",
+        ));
+}
+
+#[test]
+fn backlinks_resolves_target_by_alias() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+    fs::write(
+        vault.join("notes/project.md"),
+        "---\naliases: [Apollo]\n---\n# Project\n",
+    )
+    .unwrap();
+    fs::write(vault.join("notes/log.md"), "Shipped [[Apollo]].\n").unwrap();
+
+    let output = cmd()
+        .args(["--vault", vault.to_str().unwrap(), "-o", "json"])
+        .args(["backlinks", "apollo"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["target"], "notes/project.md");
+    let backlinks = json["data"]["backlinks"].as_array().unwrap();
+    assert_eq!(backlinks.len(), 1);
+    assert_eq!(backlinks[0]["source"], "notes/log.md");
+
+    // An alias that collides with another note's stem is ambiguous.
+    fs::write(vault.join("notes/apollo.md"), "# Apollo\n").unwrap();
+    cmd()
+        .args(["--vault", vault.to_str().unwrap(), "backlinks", "Apollo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ambiguous target 'Apollo'"));
+}
+
 // ---------------------------------------------------------------------------
 // mentions (positional note arg)
 // ---------------------------------------------------------------------------
@@ -437,8 +791,389 @@ fn mentions_json_output() {
             vault.to_str().unwrap(),
             "-o",
             "json",
-            "mentions",
-            "notes/hello.md",
+            "mentions",
+            "notes/hello.md",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["ok"], true);
+    assert!(json["data"]["count"].is_u64());
+    assert!(json["data"]["mentions"].is_array());
+}
+
+// ---------------------------------------------------------------------------
+// query
+// ---------------------------------------------------------------------------
+
+#[test]
+fn query_text_output() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    cmd()
+        .args(["--vault", vault.to_str().unwrap(), "query", "--tag", "rust"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("notes/"));
+}
+
+#[test]
+fn query_json_output() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "query",
+            "--tag",
+            "rust",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["ok"], true);
+    let hits = json["data"].as_array().unwrap();
+    assert!(!hits.is_empty());
+    assert!(hits[0]["path"].is_string());
+}
+
+#[test]
+fn query_exists_any_and_missing_flags() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    let paths = |args: &[&str]| {
+        let output = cmd()
+            .args(["--vault", vault.to_str().unwrap(), "-o", "json", "query"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let mut paths: Vec<String> = json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| h["path"].as_str().unwrap().to_string())
+            .collect();
+        paths.sort();
+        paths
+    };
+
+    assert_eq!(
+        paths(&["--exists-any", "type", "--exists-any", "title"]),
+        vec!["notes/hello.md", "notes/other-note.md"]
+    );
+    assert_eq!(
+        paths(&["--missing", "type", "--missing", "title"]),
+        vec!["notes/no-frontmatter.md"]
+    );
+}
+
+#[test]
+fn tasks_due_within_keeps_open_tasks_in_window_sorted_by_due() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(&vault).unwrap();
+    let today = oxidian::Date::today();
+    fs::write(
+        vault.join("plan.md"),
+        format!(
+            "- [ ] later 📅 {}\n- [ ] soon [due:: {}]\n- [ ] now 📅 {today}\n- [x] finished 📅 {today}\n",
+            today.add_days(30),
+            today.add_days(3),
+        ),
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "tasks",
+            "--due-within",
+            "7",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let lines: Vec<u64> = json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["line"].as_u64().unwrap())
+        .collect();
+    assert_eq!(lines, vec![3, 2]);
+}
+
+#[test]
+fn tasks_completed_since_lists_recent_done_tasks_newest_first() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(&vault).unwrap();
+    let today = oxidian::Date::today();
+    fs::write(
+        vault.join("log.md"),
+        format!(
+            "- [x] shipped ✅ {}\n- [x] ancient ✅ {}\n- [x] undated\n\
+             - [x] fixed [completion:: {today}]\n- [ ] open ✅ {today}\n",
+            today.add_days(-2),
+            today.add_days(-60),
+        ),
+    )
+    .unwrap();
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "tasks",
+            "--completed-since",
+            "7",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let lines: Vec<u64> = json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["line"].as_u64().unwrap())
+        .collect();
+    assert_eq!(lines, vec![4, 1]);
+}
+
+#[test]
+fn query_numeric_range_flags_and_together() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(&vault).unwrap();
+    for (name, size) in [
+        ("tiny", "5"),
+        ("ten", "10"),
+        ("mid", "50"),
+        ("big", "100"),
+        ("odd", "\"n/a\""),
+    ] {
+        fs::write(
+            vault.join(format!("{name}.md")),
+            format!("---\nsize: {size}\n---\n"),
+        )
+        .unwrap();
+    }
+
+    let paths = |args: &[&str]| {
+        let output = cmd()
+            .args(["--vault", vault.to_str().unwrap(), "-o", "json", "query"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| h["path"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        paths(&["--gt", "size=10", "--lt", "size=100"]),
+        vec!["mid.md"]
+    );
+    assert_eq!(
+        paths(&["--gte", "size=10", "--lte", "size=100"]),
+        vec!["big.md", "mid.md", "ten.md"]
+    );
+    assert_eq!(paths(&["--lt", "size=10"]), vec!["tiny.md"]);
+    // A non-numeric bound adds no filter.
+    assert_eq!(paths(&["--lt", "size=abc"]).len(), 5);
+}
+
+#[test]
+fn query_or_flags_collapse_into_one_group() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(&vault).unwrap();
+    fs::write(vault.join("project.md"), "---\ntags: [project]\n---\n").unwrap();
+    fs::write(vault.join("area.md"), "---\ntags: [area]\n---\n").unwrap();
+    fs::write(vault.join("other.md"), "---\ntags: [misc]\n---\n").unwrap();
+
+    let output = cmd()
+        .args(["--vault", vault.to_str().unwrap(), "-o", "json", "query"])
+        .args(["--or", "tags=project", "--or", "tags=area"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let paths: Vec<&str> = json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|h| h["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, vec!["area.md", "project.md"]);
+
+    // Malformed pairs are skipped like `--eq`; a group with none left filters nothing.
+    cmd()
+        .args(["--vault", vault.to_str().unwrap(), "query", "--count"])
+        .args(["--or", "tags", "--or", "status"])
+        .assert()
+        .success()
+        .stdout("3\n");
+}
+
+#[test]
+fn query_negation_flags() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    let paths = |args: &[&str]| {
+        let output = cmd()
+            .args(["--vault", vault.to_str().unwrap(), "-o", "json", "query"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| h["path"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        paths(&["--not-exists", "type"]),
+        vec!["notes/no-frontmatter.md", "notes/other-note.md"]
+    );
+    assert_eq!(
+        paths(&["--ne", "type=doc"]),
+        vec!["notes/no-frontmatter.md", "notes/other-note.md"]
+    );
+    assert_eq!(
+        paths(&["--exclude-tag", "rust"]),
+        vec!["notes/no-frontmatter.md"]
+    );
+}
+
+#[test]
+fn links_orphaned_targets_skips_excluded_tags() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+    fs::write(vault.join("notes/index.md"), "#moc\n\n[[hello]]\n").unwrap();
+
+    let output = cmd()
+        .args(["--vault", vault.to_str().unwrap(), "-o", "json", "links"])
+        .args(["--orphaned-targets", "--exclude-tag", "#MOC"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["count"], 1);
+    assert_eq!(json["data"]["notes"][0]["path"], "notes/no-frontmatter.md");
+}
+
+#[test]
+fn query_and_tasks_count_ignore_limit() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    let count = |args: &[&str]| {
+        let output = cmd()
+            .args(["--vault", vault.to_str().unwrap(), "-o", "json"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["data"]["count"].as_u64().unwrap()
+    };
+
+    assert_eq!(
+        count(&["query", "--tag", "rust", "--limit", "1", "--count"]),
+        2
+    );
+    assert_eq!(count(&["query", "--limit", "1", "--count"]), 3);
+    assert_eq!(count(&["tasks", "--limit", "1", "--count"]), 2);
+    assert_eq!(count(&["tasks", "--status", "done", "--count"]), 1);
+
+    cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "query",
+            "--tag",
+            "rust",
+            "--count",
+        ])
+        .assert()
+        .success()
+        .stdout("2\n");
+}
+
+// ---------------------------------------------------------------------------
+// graph
+// ---------------------------------------------------------------------------
+
+#[test]
+fn graph_json_output() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    let output = cmd()
+        .args(["--vault", vault.to_str().unwrap(), "-o", "json", "graph"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["ok"], true);
+    assert!(json["data"]["issue_count"].is_u64());
+}
+
+// ---------------------------------------------------------------------------
+// check links
+// ---------------------------------------------------------------------------
+
+#[test]
+fn check_links_json_output() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "check",
+            "links",
         ])
         .output()
         .unwrap();
@@ -446,32 +1181,57 @@ fn mentions_json_output() {
     assert!(output.status.success());
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     assert_eq!(json["ok"], true);
-    assert!(json["data"]["count"].is_u64());
-    assert!(json["data"]["mentions"].is_array());
+    assert!(json["data"]["internal_occurrences"].is_u64());
+    assert!(json["data"]["ok"].is_u64());
+    assert!(json["data"]["broken"].is_array());
 }
 
-// ---------------------------------------------------------------------------
-// query
-// ---------------------------------------------------------------------------
-
 #[test]
-fn query_text_output() {
+fn check_links_broken_lines_include_source_title() {
     let tmp = tempfile::tempdir().unwrap();
     let vault = tmp.path().join("vault");
-    create_vault(&vault);
+    fs::create_dir_all(&vault).unwrap();
+    fs::write(
+        vault.join("src.md"),
+        "---\ntitle: Weekly Review\n---\nSee [[nowhere]].\n",
+    )
+    .unwrap();
 
     cmd()
-        .args(["--vault", vault.to_str().unwrap(), "query", "--tag", "rust"])
+        .args(["--vault", vault.to_str().unwrap(), "check", "links"])
         .assert()
         .success()
-        .stdout(predicate::str::contains("notes/"));
+        .stdout(predicate::str::contains(
+            "- src.md:4\tWeekly Review\tmissing",
+        ));
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "check",
+            "links",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["broken"][0]["source_title"], "Weekly Review");
 }
 
 #[test]
-fn query_json_output() {
+fn check_links_resolves_dotted_note_stems() {
     let tmp = tempfile::tempdir().unwrap();
     let vault = tmp.path().join("vault");
-    create_vault(&vault);
+    fs::create_dir_all(vault.join("releases")).unwrap();
+    fs::write(vault.join("v1.2.md"), "# v1.2\n").unwrap();
+    fs::write(vault.join("releases/v2.0.md"), "# v2.0\n").unwrap();
+    fs::write(
+        vault.join("src.md"),
+        "[[v1.2.md]] [[v1.2]] [a](v1.2.md) [b](releases/v2.0.md) [[releases/v2.0]]\n",
+    )
+    .unwrap();
 
     let output = cmd()
         .args([
@@ -479,48 +1239,87 @@ fn query_json_output() {
             vault.to_str().unwrap(),
             "-o",
             "json",
-            "query",
-            "--tag",
-            "rust",
+            "check",
+            "links",
         ])
         .output()
         .unwrap();
 
     assert!(output.status.success());
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
-    assert_eq!(json["ok"], true);
-    let hits = json["data"].as_array().unwrap();
-    assert!(!hits.is_empty());
-    assert!(hits[0]["path"].is_string());
+    assert_eq!(json["data"]["broken"].as_array().unwrap().len(), 0);
+    assert_eq!(json["data"]["ok"], 5);
 }
 
-// ---------------------------------------------------------------------------
-// graph
-// ---------------------------------------------------------------------------
-
 #[test]
-fn graph_json_output() {
+fn check_links_fail_on_error_exits_non_zero_with_broken_links() {
     let tmp = tempfile::tempdir().unwrap();
     let vault = tmp.path().join("vault");
     create_vault(&vault);
+    fs::write(vault.join("notes/broken.md"), "See [[nowhere]].\n").unwrap();
+
+    let links = |args: &[&str]| {
+        cmd()
+            .args(["--vault", vault.to_str().unwrap(), "check", "links"])
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    assert!(links(&[]).status.success());
+    let output = links(&["--fail-on", "error"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("broken:"));
+    assert!(
+        links(&["--fail-on", "error", "--reason", "ambiguous-target"])
+            .status
+            .success()
+    );
+}
 
-    let output = cmd()
-        .args(["--vault", vault.to_str().unwrap(), "-o", "json", "graph"])
-        .output()
-        .unwrap();
+#[test]
+fn check_health_fail_on_error_counts_broken_links() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+    fs::write(vault.join("notes/broken.md"), "See [[nowhere]].\n").unwrap();
+
+    let health = |args: &[&str]| {
+        cmd()
+            .args([
+                "--vault",
+                vault.to_str().unwrap(),
+                "-o",
+                "json",
+                "check",
+                "health",
+            ])
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = health(&["--fail-on", "error", "--signal", "links"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["ok"], true);
+    assert!(json["data"]["broken_links"].as_u64().unwrap() >= 1);
+    assert_eq!(json["data"]["failed"], true);
 
+    let output = health(&["--fail-on", "error", "--signal", "schema"]);
+    assert!(output.status.success());
+    let output = health(&[]);
     assert!(output.status.success());
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
-    assert_eq!(json["ok"], true);
-    assert!(json["data"]["issue_count"].is_u64());
+    assert_eq!(json["data"]["failed"], false);
 }
 
 // ---------------------------------------------------------------------------
-// check links
+// check frontmatter
 // ---------------------------------------------------------------------------
 
 #[test]
-fn check_links_json_output() {
+fn check_frontmatter_json_output() {
     let tmp = tempfile::tempdir().unwrap();
     let vault = tmp.path().join("vault");
     create_vault(&vault);
@@ -532,7 +1331,7 @@ fn check_links_json_output() {
             "-o",
             "json",
             "check",
-            "links",
+            "frontmatter",
         ])
         .output()
         .unwrap();
@@ -540,20 +1339,21 @@ fn check_links_json_output() {
     assert!(output.status.success());
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     assert_eq!(json["ok"], true);
-    assert!(json["data"]["internal_occurrences"].is_u64());
-    assert!(json["data"]["ok"].is_u64());
+    assert!(json["data"]["notes_without_frontmatter"].is_u64());
+    assert!(json["data"]["missing"].is_array());
     assert!(json["data"]["broken"].is_array());
 }
 
-// ---------------------------------------------------------------------------
-// check frontmatter
-// ---------------------------------------------------------------------------
-
 #[test]
-fn check_frontmatter_json_output() {
+fn check_frontmatter_prefix_scopes_counts() {
     let tmp = tempfile::tempdir().unwrap();
     let vault = tmp.path().join("vault");
-    create_vault(&vault);
+    fs::create_dir_all(vault.join("audit")).unwrap();
+    fs::create_dir_all(vault.join("other")).unwrap();
+    fs::write(vault.join("audit/plain.md"), "# Plain\n").unwrap();
+    fs::write(vault.join("audit/valid.md"), "---\ntitle: ok\n---\n").unwrap();
+    fs::write(vault.join("other/plain.md"), "# Plain\n").unwrap();
+    fs::write(vault.join("other/broken.md"), "---\ntags: [a\n---\n").unwrap();
 
     let output = cmd()
         .args([
@@ -563,16 +1363,20 @@ fn check_frontmatter_json_output() {
             "json",
             "check",
             "frontmatter",
+            "--prefix",
+            "audit/",
         ])
         .output()
         .unwrap();
 
     assert!(output.status.success());
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
-    assert_eq!(json["ok"], true);
-    assert!(json["data"]["notes_without_frontmatter"].is_u64());
-    assert!(json["data"]["missing"].is_array());
-    assert!(json["data"]["broken"].is_array());
+    let data = &json["data"];
+    assert_eq!(data["notes_without_frontmatter"], 1);
+    assert_eq!(data["notes_with_frontmatter_valid"], 1);
+    assert_eq!(data["notes_with_frontmatter_broken"], 0);
+    assert_eq!(data["missing"], serde_json::json!(["audit/plain.md"]));
+    assert_eq!(data["broken"], serde_json::json!([]));
 }
 
 // ---------------------------------------------------------------------------
@@ -705,6 +1509,114 @@ severity = "error"
     assert!(violation.get("detail").is_none());
 }
 
+#[test]
+fn check_schema_only_code_hides_other_codes() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(vault.join("memory/2026/02/3")).unwrap();
+    fs::create_dir_all(vault.join(".obsidian/oxidian")).unwrap();
+    fs::write(
+        vault.join(".obsidian/oxidian/schema.toml"),
+        r#"version = 1
+
+[types]
+memory = "Memory entry"
+
+[vault]
+
+[vault.scopes.memory]
+required = true
+unmatched = "error"
+allow = [{ template = "{year}/{month}/{day}/{slug}.md" }]
+
+[vault.scopes.memory.notes.type]
+required = true
+allowed = ["memory"]
+severity = "error"
+"#,
+    )
+    .unwrap();
+    fs::write(vault.join("memory/2026/02/3/remember.md"), "body\n").unwrap();
+
+    let codes = |extra: &[&str]| -> Vec<String> {
+        let output = cmd()
+            .args(["--vault", vault.to_str().unwrap(), "-o", "json"])
+            .args(["check", "schema"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["data"]["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["violation"]["code"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let all = codes(&[]);
+    assert!(all.iter().any(|c| c == "layout_template_mismatch"));
+    assert!(all.iter().any(|c| c == "note_type_missing"));
+
+    assert_eq!(
+        codes(&["--only-code", "note_type_missing"]),
+        vec!["note_type_missing"]
+    );
+    assert_eq!(
+        codes(&["--exclude-code", "note_type_missing"]),
+        vec!["layout_template_mismatch"]
+    );
+}
+
+#[test]
+fn check_schema_fail_on_warn_exits_non_zero_only_when_asked() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(vault.join("memory")).unwrap();
+    fs::create_dir_all(vault.join(".obsidian/oxidian")).unwrap();
+    fs::write(
+        vault.join(".obsidian/oxidian/schema.toml"),
+        r#"version = 1
+
+[vault]
+
+[vault.scopes.memory]
+unmatched = "warn"
+allow = [{ template = "{year}/{slug}.md" }]
+"#,
+    )
+    .unwrap();
+    fs::write(vault.join("memory/stray.md"), "body\n").unwrap();
+
+    let schema = |args: &[&str]| {
+        cmd()
+            .args([
+                "--vault",
+                vault.to_str().unwrap(),
+                "-o",
+                "json",
+                "check",
+                "schema",
+            ])
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = schema(&[]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["errors"], 0);
+    assert!(json["data"]["warnings"].as_u64().unwrap() >= 1);
+
+    assert!(schema(&["--fail-on", "error"]).status.success());
+    let output = schema(&["--fail-on", "warn"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["ok"], true);
+}
+
 // ---------------------------------------------------------------------------
 // check frontmatter shows details by default (no --show-broken needed)
 // ---------------------------------------------------------------------------
@@ -811,6 +1723,39 @@ fn schema_init_json_output() {
     assert!(json["data"]["template"].is_string());
 }
 
+#[test]
+fn schema_init_from_vault_scaffolds_one_scope_per_folder() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    fs::create_dir_all(vault.join("projects/web")).unwrap();
+    fs::create_dir_all(vault.join("journal")).unwrap();
+    fs::create_dir_all(vault.join(".trash")).unwrap();
+    fs::create_dir_all(vault.join(".obsidian")).unwrap();
+    fs::write(vault.join("root.md"), "# Root\n").unwrap();
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "schema",
+            "init",
+            "--from-vault",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["data"]["template"], "from-vault");
+
+    let text = fs::read_to_string(vault.join(".obsidian/oxidian/schema.toml")).unwrap();
+    let schema: oxidian::Schema = toml::from_str(&text).unwrap();
+    let ids: Vec<&str> = schema.vault.scopes.keys().map(String::as_str).collect();
+    assert_eq!(ids, vec!["journal", "projects"]);
+    assert!(!schema.vault.scopes["projects"].allow.is_empty());
+}
+
 // ---------------------------------------------------------------------------
 // JSON error envelope
 // ---------------------------------------------------------------------------
@@ -1085,3 +2030,164 @@ fn check_links_multiple_exclude_reasons() {
     // Should have missing_heading and missing_block remaining
     assert_eq!(broken.len(), 2, "expected 2 remaining issues");
 }
+
+// ---------------------------------------------------------------------------
+// Watch snapshot
+// ---------------------------------------------------------------------------
+
+fn read_snapshot(path: &Path, until: impl Fn(&serde_json::Value) -> bool) -> serde_json::Value {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(15);
+    loop {
+        if let Ok(bytes) = fs::read(path)
+            && let Ok(json) = serde_json::from_slice::<serde_json::Value>(&bytes)
+            && until(&json)
+        {
+            return json;
+        }
+        assert!(
+            std::time::Instant::now() < deadline,
+            "snapshot not updated in time"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
+/// Kills the wrapped child process on drop, so a failing assertion can't leak it.
+struct KillOnDrop(std::process::Child);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+#[test]
+fn watch_snapshot_path_rewrites_stats_on_change() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+    let snapshot = tmp.path().join("snapshot.json");
+
+    let _child = KillOnDrop(
+        std::process::Command::new(env!("CARGO_BIN_EXE_oxi"))
+            .args(["--vault", vault.to_str().unwrap(), "-q", "watch"])
+            .arg("--snapshot-path")
+            .arg(&snapshot)
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap(),
+    );
+
+    // The initial snapshot is written once the watcher is running.
+    let initial = read_snapshot(&snapshot, |_| true);
+    assert_eq!(initial["notes"], 3);
+    assert_eq!(initial["tasks"], 2);
+
+    fs::write(vault.join("notes/new.md"), "- [ ] fresh task #new\n").unwrap();
+    let updated = read_snapshot(&snapshot, |json| json["notes"] == 4);
+    assert_eq!(updated["tasks"], 3);
+}
+
+#[test]
+fn search_match_exact_returns_only_the_precise_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+    std::fs::write(vault.join("notes/hello-world.md"), "other\n").unwrap();
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "json",
+            "search",
+            "hello",
+            "--match",
+            "exact",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let hits = json["data"].as_array().unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["path"], "notes/hello.md");
+}
+
+#[test]
+fn search_match_prefix_is_rejected_outside_files_mode() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    cmd()
+        .args(["--vault", vault.to_str().unwrap(), "search", "hello"])
+        .args(["--mode", "content", "--match", "prefix"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only applies to --mode files"));
+}
+
+#[test]
+fn ndjson_prints_one_task_per_line() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    let output = cmd()
+        .args(["--vault", vault.to_str().unwrap(), "-o", "ndjson", "tasks"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let rows: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["text"], "Buy groceries");
+    assert_eq!(rows[1]["status"], "done");
+}
+
+#[test]
+fn ndjson_streams_content_hits_and_wraps_other_commands_in_one_line() {
+    let tmp = tempfile::tempdir().unwrap();
+    let vault = tmp.path().join("vault");
+    create_vault(&vault);
+
+    let output = cmd()
+        .args([
+            "--vault",
+            vault.to_str().unwrap(),
+            "-o",
+            "ndjson",
+            "search",
+            "content",
+            "--mode",
+            "content",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for line in stdout.lines() {
+        let hit: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert!(hit["path"].is_string());
+        assert!(hit["line"].is_u64());
+    }
+    assert!(!stdout.is_empty());
+
+    let output = cmd()
+        .args(["--vault", vault.to_str().unwrap(), "-o", "ndjson", "stats"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 1);
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(json["ok"], true);
+}
@@ -0,0 +1,164 @@
+use std::path::Path;
+
+use oxidian::{Vault, VaultPath, VaultService};
+
+#[tokio::test]
+async fn merge_plan_lists_inbound_links_for_each_duplicate() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("work"))?;
+    std::fs::create_dir_all(vault_root.join("home"))?;
+
+    std::fs::write(vault_root.join("work/Plan.md"), "# Work plan\n")?;
+    std::fs::write(vault_root.join("home/Plan.md"), "# Home plan\n")?;
+    std::fs::write(vault_root.join("work/Standup.md"), "See [[Plan]].\n")?;
+    std::fs::write(vault_root.join("home/Chores.md"), "See [[home/Plan]].\n")?;
+    std::fs::write(vault_root.join("Index.md"), "[[Plan]] and [[work/Plan]]\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let idx = service.index_snapshot();
+    let groups = idx.duplicate_basenames();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name, "plan");
+    assert_eq!(groups[0].notes.len(), 2);
+
+    let plan = idx.duplicate_merge_plan()?;
+    assert_eq!(plan.len(), 1);
+    let renames = &plan[0].renames;
+    assert_eq!(renames.len(), 2);
+
+    let home = &renames[0];
+    assert_eq!(home.path.as_str_lossy(), "home/Plan.md");
+    assert_eq!(home.suggested_path.as_str_lossy(), "home/Plan (home).md");
+    let home_sources: Vec<String> = home
+        .inbound
+        .iter()
+        .map(|b| b.source.as_str_lossy())
+        .collect();
+    assert!(home_sources.contains(&"home/Chores.md".to_string()));
+
+    let work = &renames[1];
+    assert_eq!(work.path.as_str_lossy(), "work/Plan.md");
+    assert_eq!(work.suggested_path.as_str_lossy(), "work/Plan (work).md");
+    let work_sources: Vec<String> = work
+        .inbound
+        .iter()
+        .map(|b| b.source.as_str_lossy())
+        .collect();
+    assert!(work_sources.contains(&"work/Standup.md".to_string()));
+    assert!(work_sources.contains(&"Index.md".to_string()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn near_duplicate_tags_cluster_with_most_used_as_canonical() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(vault_root.join("a.md"), "#ml #cooking\n")?;
+    std::fs::write(vault_root.join("b.md"), "#ml #recipes\n")?;
+    std::fs::write(vault_root.join("c.md"), "#m-l #recipe\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let clusters = service.index_snapshot().near_duplicate_tags();
+    let summary: Vec<(String, Vec<(String, usize)>)> = clusters
+        .iter()
+        .map(|c| {
+            let tags = c.tags.iter().map(|u| (u.tag.0.clone(), u.files)).collect();
+            (c.canonical.0.clone(), tags)
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "ml".to_string(),
+                vec![("ml".to_string(), 2), ("m-l".to_string(), 1)]
+            ),
+            (
+                "recipe".to_string(),
+                vec![("recipe".to_string(), 1), ("recipes".to_string(), 1)]
+            ),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn near_duplicate_tags_keep_numbered_series_apart() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(
+        vault_root.join("a.md"),
+        "#log/2024/01 #log/2024/02 #project1\n",
+    )?;
+    std::fs::write(
+        vault_root.join("b.md"),
+        "#log/2024/03 #log/2024/1 #project2\n",
+    )?;
+    std::fs::write(vault_root.join("c.md"), "#python3 #pythn3\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let clusters = service.index_snapshot().near_duplicate_tags();
+    let summary: Vec<(String, Vec<String>)> = clusters
+        .iter()
+        .map(|c| {
+            let tags = c.tags.iter().map(|u| u.tag.0.clone()).collect();
+            (c.canonical.0.clone(), tags)
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![(
+            "pythn3".to_string(),
+            vec!["pythn3".to_string(), "python3".to_string()]
+        )]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn identical_notes_share_content_hash() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("copy"))?;
+
+    let body = "# Meeting\n\nSame bytes in both files.\n";
+    std::fs::write(vault_root.join("meeting.md"), body)?;
+    std::fs::write(vault_root.join("copy/meeting-2.md"), body)?;
+    std::fs::write(vault_root.join("other.md"), "# Other\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+    let index = service.index_snapshot();
+
+    let a = index.file(&VaultPath::try_from(Path::new("meeting.md"))?);
+    let b = index.file(&VaultPath::try_from(Path::new("copy/meeting-2.md"))?);
+    let hash = a.and_then(|f| f.content_hash.clone());
+    assert!(hash.is_some());
+    assert_eq!(hash, b.and_then(|f| f.content_hash.clone()));
+
+    let groups = index.duplicate_contents();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].name, hash.unwrap());
+    let notes: Vec<String> = groups[0].notes.iter().map(|p| p.as_str_lossy()).collect();
+    assert_eq!(notes, vec!["copy/meeting-2.md", "meeting.md"]);
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use oxidian::{FrontmatterStatus, Vault, VaultService};
+use oxidian::{FieldValueKind, FrontmatterStatus, Vault, VaultService};
 
 #[tokio::test]
 async fn frontmatter_audit_detects_none_valid_broken() -> anyhow::Result<()> {
@@ -43,3 +43,67 @@ async fn frontmatter_audit_detects_none_valid_broken() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn field_source_conflicts_report_differing_values_only() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(
+        vault_root.join("conflict.md"),
+        "---\nstatus: draft\n---\n\nstatus:: final\n",
+    )?;
+    std::fs::write(
+        vault_root.join("consistent.md"),
+        "---\nstatus: draft\npriority: 2\n---\n\nstatus:: draft\npriority:: 2\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+    let snapshot = service.index_snapshot();
+
+    let conflicts: Vec<(String, String)> = snapshot
+        .field_source_conflicts()
+        .into_iter()
+        .map(|(p, k)| (p.as_str_lossy(), k))
+        .collect();
+    assert_eq!(conflicts, vec![("conflict.md".into(), "status".into())]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn field_report_infers_string_status_field() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(vault_root.join("a.md"), "---\nstatus: open\n---\n# A\n")?;
+    std::fs::write(vault_root.join("b.md"), "---\nstatus: done\n---\n# B\n")?;
+    std::fs::write(vault_root.join("c.md"), "---\nstatus: open\n---\n# C\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let draft = service.index_snapshot().infer_field_schema();
+    assert_eq!(draft.fields.len(), 1);
+    let status = &draft.fields[0];
+    assert_eq!(status.key, "status");
+    assert_eq!(status.kind, FieldValueKind::String);
+    assert_eq!(status.notes, 3);
+    assert_eq!(
+        status.values.as_deref(),
+        Some(&["done".to_string(), "open".to_string()][..])
+    );
+
+    let report = draft.to_report();
+    assert!(report.lines().all(|line| line.starts_with('#')));
+    assert!(report.contains(r#"# status: string, 3 notes, values ["done", "open"]"#));
+    let parsed: toml::Value = toml::from_str(&report)?;
+    assert!(parsed.as_table().is_some_and(|t| t.is_empty()));
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use oxidian::{ResolveResult, Vault, VaultService};
+use oxidian::{FrontmatterStatus, ResolveResult, Vault, VaultService};
 
 #[tokio::test]
 async fn graph_build_collects_backlinks_and_issues() -> anyhow::Result<()> {
@@ -55,3 +55,221 @@ async fn graph_build_collects_backlinks_and_issues() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn connected_components_separate_link_clusters() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    // Cluster one: a -> b -> c
+    std::fs::write(vault_root.join("a.md"), "[[b]]\n")?;
+    std::fs::write(vault_root.join("b.md"), "[[c]]\n")?;
+    std::fs::write(vault_root.join("c.md"), "# C\n")?;
+    // Cluster two: x <-> y
+    std::fs::write(vault_root.join("x.md"), "[[y]]\n")?;
+    std::fs::write(vault_root.join("y.md"), "[[x]]\n")?;
+    // Orphan: never part of a component.
+    std::fs::write(vault_root.join("lonely.md"), "# Alone\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let components = service.build_graph()?.connected_components();
+    assert_eq!(components.len(), 2);
+
+    assert_eq!(components[0].size, 3);
+    let first: Vec<String> = components[0]
+        .members
+        .iter()
+        .map(|p| p.as_str_lossy())
+        .collect();
+    assert_eq!(first, vec!["a.md", "b.md", "c.md"]);
+
+    assert_eq!(components[1].size, 2);
+    let second: Vec<String> = components[1]
+        .members
+        .iter()
+        .map(|p| p.as_str_lossy())
+        .collect();
+    assert_eq!(second, vec!["x.md", "y.md"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn graph_export_serializes_nodes_and_edges() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(vault_root.join("a.md"), "# Alpha\n\nSee [[b#Intro]].\n")?;
+    std::fs::write(vault_root.join("b.md"), "# Beta\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let export = service.build_graph()?.export(&service.index_snapshot());
+    let json = serde_json::to_value(&export)?;
+
+    assert_eq!(
+        json["nodes"],
+        serde_json::json!([
+            {
+                "path": "a.md",
+                "title": "Alpha",
+                "kind": "markdown",
+                "tags": 0,
+                "outgoing_links": 1,
+                "backlinks": 0,
+                "tasks": 0,
+                "frontmatter": "none",
+            },
+            {
+                "path": "b.md",
+                "title": "Beta",
+                "kind": "markdown",
+                "tags": 0,
+                "outgoing_links": 0,
+                "backlinks": 1,
+                "tasks": 0,
+                "frontmatter": "none",
+            },
+        ])
+    );
+    let edges = json["edges"].as_array().expect("edges array");
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0]["source"], "a.md");
+    assert_eq!(edges[0]["target"], "b.md");
+    assert_eq!(edges[0]["embed"], false);
+    assert!(!edges[0]["subpath"].is_null());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn note_summaries_count_tags_links_backlinks_and_tasks() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(
+        vault_root.join("a.md"),
+        "---\ntags: [x]\n---\n# Alpha\n\n[[b]] and [[b#Intro]] #y\n\n- [ ] one\n- [x] two\n",
+    )?;
+    std::fs::write(vault_root.join("b.md"), "# Beta\n\n[[a]]\n")?;
+    std::fs::write(vault_root.join("c.md"), "# Gamma\n\n[[b]] [[missing]]\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let summaries = service
+        .build_graph()?
+        .note_summaries(&service.index_snapshot());
+    let paths: Vec<String> = summaries.iter().map(|s| s.path.as_str_lossy()).collect();
+    assert_eq!(paths, vec!["a.md", "b.md", "c.md"]);
+
+    let a = &summaries[0];
+    assert_eq!(a.title, "Alpha");
+    assert_eq!(a.counts.tags, 2);
+    assert_eq!(a.counts.outgoing_links, 2);
+    assert_eq!(a.counts.backlinks, 1);
+    assert_eq!(a.counts.tasks, 2);
+    assert_eq!(a.counts.frontmatter, FrontmatterStatus::Valid);
+
+    let b = &summaries[1];
+    assert_eq!(b.counts.outgoing_links, 1);
+    assert_eq!(b.counts.backlinks, 3);
+    assert_eq!(summaries[2].counts.outgoing_links, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn dead_ends_lists_linked_notes_without_outgoing_links() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(
+        vault_root.join("hub.md"),
+        "[[leaf]] [[relay]] ![[pic.png]]\n",
+    )?;
+    std::fs::write(vault_root.join("relay.md"), "[[hub]]\n")?;
+    std::fs::write(vault_root.join("leaf.md"), "No links here. [[Missing]]\n")?;
+    std::fs::write(vault_root.join("loop.md"), "[[loop]]\n")?;
+    std::fs::write(vault_root.join("lonely.md"), "Nobody links here.\n")?;
+    std::fs::write(vault_root.join("pic.png"), "png")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let idx = service.index_snapshot();
+    let graph = service.build_graph()?;
+    let dead_ends: Vec<String> = graph
+        .dead_ends(&idx)
+        .iter()
+        .map(|p| p.as_str_lossy())
+        .collect();
+    assert_eq!(dead_ends, vec!["leaf.md".to_string()]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn directed_stats_count_reciprocal_pairs_and_degrees() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    // a <-> b is the only reciprocal pair; the repeated link counts once.
+    std::fs::write(vault_root.join("a.md"), "[[b]] [[b]] [[c]]\n")?;
+    std::fs::write(vault_root.join("b.md"), "[[a]] [[b]]\n")?;
+    std::fs::write(vault_root.join("c.md"), "# C\n")?;
+    std::fs::write(vault_root.join("lonely.md"), "# Alone\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let stats = service.build_graph()?.directed_stats();
+    assert_eq!(stats.nodes, 3);
+    assert_eq!(stats.edges, 3);
+    assert_eq!(stats.reciprocal_pairs, 1);
+    assert_eq!(stats.in_degree, [(1, 3)].into_iter().collect());
+    assert_eq!(
+        stats.out_degree,
+        [(0, 1), (1, 1), (2, 1)].into_iter().collect()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn gexf_export_lists_nodes_and_directed_edge() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    std::fs::write(vault_root.join("a.md"), "# Fish & <Chips>\n[[b]]\n")?;
+    std::fs::write(vault_root.join("b.md"), "# B\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let gexf = service.build_graph()?.to_gexf(&service.index_snapshot());
+    assert!(gexf.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<gexf "));
+    assert!(gexf.trim_end().ends_with("</gexf>"));
+    assert_eq!(gexf.matches("<node ").count(), 2);
+    assert_eq!(gexf.matches("</node>").count(), 2);
+    assert!(gexf.contains("<node id=\"a.md\" label=\"Fish &amp; &lt;Chips&gt;\">"));
+    assert!(gexf.contains("<attvalue for=\"kind\" value=\"markdown\"/>"));
+    assert!(gexf.contains("<edge id=\"0\" source=\"a.md\" target=\"b.md\" weight=\"1\"/>"));
+    assert!(!gexf.contains("<Chips>"));
+
+    Ok(())
+}
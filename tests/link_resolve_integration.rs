@@ -39,3 +39,90 @@ async fn resolver_prefers_same_folder_and_supports_aliases() -> anyhow::Result<(
 
     Ok(())
 }
+
+#[tokio::test]
+async fn extensionless_markdown_links_resolve_relative_to_source() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("projects/alpha/sub"))?;
+    std::fs::create_dir_all(vault_root.join("projects/shared"))?;
+
+    std::fs::write(vault_root.join("projects/alpha/sub/note.md"), "# Note\n")?;
+    std::fs::write(vault_root.join("projects/shared/common.md"), "# Common\n")?;
+    std::fs::write(
+        vault_root.join("projects/alpha/index.md"),
+        "[sub](sub/note) [here](./sub/note) [up](../shared/common) [root](/projects/shared/common)\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+    let idx = service.index_snapshot();
+
+    let source = VaultPath::try_from(std::path::Path::new("projects/alpha/index.md"))?;
+    let resolved: Vec<String> = idx
+        .resolved_outgoing_internal_links(&source)
+        .into_iter()
+        .map(|l| match l.resolution {
+            ResolveResult::Resolved(p) => p.as_str_lossy(),
+            other => format!("{other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        resolved,
+        vec![
+            "projects/alpha/sub/note.md",
+            "projects/alpha/sub/note.md",
+            "projects/shared/common.md",
+            "projects/shared/common.md",
+        ]
+    );
+
+    let resolver = idx.link_resolver();
+    assert_eq!(
+        resolver.resolve_internal("../../../escape", &source),
+        ResolveResult::Missing
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn display_for_prefers_explicit_text_then_title_then_reference() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(vault_root.join("target.md"), "# Target Title\n")?;
+    std::fs::write(
+        vault_root.join("source.md"),
+        "[[target|Shown]] [[target]] [[target#Setup]] [[target#^abc]] [[missing]] [[missing#Part]]\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+    let idx = service.index_snapshot();
+    let resolver = idx.link_resolver();
+
+    let source = VaultPath::try_from(std::path::Path::new("source.md"))?;
+    let note = idx.note(&source).expect("source note");
+    let shown: Vec<String> = note
+        .link_occurrences
+        .iter()
+        .map(|l| resolver.display_for(l, &source))
+        .collect();
+
+    assert_eq!(
+        shown,
+        vec![
+            "Shown",
+            "Target Title",
+            "Target Title > Setup",
+            "Target Title > ^abc",
+            "missing",
+            "missing > Part",
+        ]
+    );
+    Ok(())
+}
@@ -42,3 +42,72 @@ async fn unlinked_mentions_exclude_links_code_and_frontmatter() -> anyhow::Resul
 
     Ok(())
 }
+
+#[tokio::test]
+async fn mention_suggestions_report_exact_spans_and_replacements() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    std::fs::write(
+        vault_root.join("notes/Target.md"),
+        "---\naliases: [Alt]\n---\n\n# Target\n",
+    )?;
+    let line = "Café [[Target]] then target and ALT.";
+    std::fs::write(vault_root.join("notes/Source.md"), format!("{line}\n"))?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let target = VaultPath::try_from(std::path::Path::new("notes/Target.md"))?;
+    let suggestions = service.mention_suggestions(&target, 100).await?;
+
+    let found: Vec<(&str, &str)> = suggestions
+        .iter()
+        .map(|s| (s.text.as_str(), s.replacement.as_str()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![("target", "[[Target|target]]"), ("ALT", "[[Target|ALT]]")]
+    );
+    for s in &suggestions {
+        assert_eq!(s.line, 1);
+        let span = &line[s.column as usize - 1..s.end_column as usize - 1];
+        assert_eq!(span, s.text);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn link_candidates_skip_notes_the_source_already_links() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    std::fs::write(
+        vault_root.join("notes/source.md"),
+        "---\ntags: [rust, cli]\n---\nSee [[linked]].\n",
+    )?;
+    std::fs::write(
+        vault_root.join("notes/linked.md"),
+        "---\ntags: [rust, cli]\n---\n",
+    )?;
+    std::fs::write(vault_root.join("notes/related.md"), "Body #rust #cli\n")?;
+    std::fs::write(vault_root.join("notes/loose.md"), "Body #rust\n")?;
+    std::fs::write(vault_root.join("notes/unrelated.md"), "Body #cooking\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let source = VaultPath::try_from(std::path::Path::new("notes/source.md"))?;
+    let candidates = service.with_index(|idx| idx.link_candidates(&source));
+
+    let paths: Vec<String> = candidates.iter().map(|c| c.path.as_str_lossy()).collect();
+    assert_eq!(paths, vec!["notes/related.md", "notes/loose.md"]);
+    assert_eq!(candidates[0].shared_tags.len(), 2);
+
+    Ok(())
+}
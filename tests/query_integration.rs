@@ -1,4 +1,7 @@
-use oxidian::{FieldValue, Query, SortDir, Vault, VaultService};
+use oxidian::{
+    FieldMergePolicy, FieldPredicate, FieldValue, Query, SortDir, TagSource, TaskQuery, TaskStatus,
+    Vault, VaultConfig, VaultService,
+};
 
 #[tokio::test]
 async fn dataview_like_fields_are_indexed_and_queryable() -> anyhow::Result<()> {
@@ -58,3 +61,473 @@ async fn dataview_like_fields_are_indexed_and_queryable() -> anyhow::Result<()>
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn concurrent_async_queries_return_correct_results() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    for i in 0..20 {
+        let status = if i % 2 == 0 { "done" } else { "todo" };
+        std::fs::write(
+            vault_root.join(format!("notes/n{i:02}.md")),
+            format!("---\nstatus: {status}\n---\n\n- [ ] task {i}\n"),
+        )?;
+    }
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let done = Query::notes().where_field("status").eq("done");
+    let todo = Query::notes().where_field("status").eq("todo");
+    let tasks = TaskQuery::all().status(TaskStatus::Todo);
+    let first_three = Query::notes().sort_by_path(SortDir::Asc).limit(3);
+    let (done_hits, todo_hits, task_hits, limited) = tokio::try_join!(
+        service.query_async(&done),
+        service.query_async(&todo),
+        service.query_tasks_async(&tasks),
+        service.query_async(&first_three),
+    )?;
+
+    assert_eq!(done_hits, service.query(&done));
+    assert_eq!(done_hits.len(), 10);
+    assert_eq!(todo_hits.len(), 10);
+    assert_eq!(task_hits.len(), 20);
+    let limited: Vec<String> = limited.iter().map(|h| h.path.as_str_lossy()).collect();
+    assert_eq!(
+        limited,
+        vec!["notes/n00.md", "notes/n01.md", "notes/n02.md"]
+    );
+
+    Ok(())
+}
+
+async fn status_field_under(policy: FieldMergePolicy) -> anyhow::Result<Option<FieldValue>> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    std::fs::write(
+        vault_root.join("a.md"),
+        "---\nstatus: draft\n---\n\nstatus:: final\nstatus:: shipped\n",
+    )?;
+
+    let cfg = VaultConfig {
+        field_merge_policy: policy,
+        ..VaultConfig::default()
+    };
+    let vault = Vault::with_config(&vault_root, cfg)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+    let idx = service.index_snapshot();
+    let note = idx
+        .note(&oxidian::VaultPath::try_from(std::path::Path::new("a.md"))?)
+        .expect("a present");
+    Ok(note.fields.get("status").cloned())
+}
+
+#[tokio::test]
+async fn field_merge_policy_controls_frontmatter_inline_precedence() -> anyhow::Result<()> {
+    let s = |v: &str| FieldValue::String(v.into());
+
+    assert_eq!(
+        status_field_under(FieldMergePolicy::Append).await?,
+        Some(FieldValue::List(vec![s("draft"), s("final"), s("shipped")]))
+    );
+    assert_eq!(
+        status_field_under(FieldMergePolicy::InlineOverrides).await?,
+        Some(FieldValue::List(vec![s("final"), s("shipped")]))
+    );
+    assert_eq!(
+        status_field_under(FieldMergePolicy::FrontmatterWins).await?,
+        Some(s("draft"))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_filters_by_tag_count() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(vault_root.join("busy.md"), "#one #two #three\n")?;
+    std::fs::write(vault_root.join("single.md"), "#one\n")?;
+    std::fs::write(vault_root.join("bare.md"), "no tags\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let paths = |q: &Query| -> Vec<String> {
+        service
+            .query(q)
+            .into_iter()
+            .map(|h| h.path.as_str_lossy())
+            .collect()
+    };
+
+    let q = Query::notes()
+        .where_tag_count(Some(2), None)
+        .sort_by_path(SortDir::Asc);
+    assert_eq!(paths(&q), vec!["busy.md"]);
+
+    let q = Query::notes()
+        .where_tag_count(None, Some(0))
+        .sort_by_path(SortDir::Asc);
+    assert_eq!(paths(&q), vec!["bare.md"]);
+
+    let q = Query::notes()
+        .where_tag_count(Some(1), Some(1))
+        .sort_by_path(SortDir::Asc);
+    assert_eq!(paths(&q), vec!["single.md"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_filters_by_any_or_none_of_several_fields() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(
+        vault_root.join("both.md"),
+        "---\nowner: me\ndue: 2024-01-01\n---\n",
+    )?;
+    std::fs::write(vault_root.join("owner.md"), "---\nowner: me\n---\n")?;
+    std::fs::write(vault_root.join("inline.md"), "due:: tomorrow\n")?;
+    std::fs::write(vault_root.join("neither.md"), "---\nstatus: draft\n---\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let paths = |q: &Query| -> Vec<String> {
+        service
+            .query(q)
+            .into_iter()
+            .map(|h| h.path.as_str_lossy())
+            .collect()
+    };
+    let keys = || vec!["owner".to_string(), "Due".to_string()];
+
+    let q = Query::notes().exists_any(keys()).sort_by_path(SortDir::Asc);
+    assert_eq!(paths(&q), vec!["both.md", "inline.md", "owner.md"]);
+
+    let q = Query::notes()
+        .missing_all(keys())
+        .sort_by_path(SortDir::Asc);
+    assert_eq!(paths(&q), vec!["neither.md"]);
+
+    // An empty key list adds no filter.
+    let q = Query::notes()
+        .missing_all(Vec::new())
+        .sort_by_path(SortDir::Asc);
+    assert_eq!(paths(&q).len(), 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn any_of_groups_or_their_predicates_and_and_with_the_rest() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(
+        vault_root.join("project.md"),
+        "---\ntags: [project]\nstatus: open\n---\n",
+    )?;
+    std::fs::write(
+        vault_root.join("area.md"),
+        "---\ntags: [area]\nstatus: open\n---\n",
+    )?;
+    std::fs::write(
+        vault_root.join("closed.md"),
+        "---\ntags: [project]\nstatus: done\n---\n",
+    )?;
+    std::fs::write(vault_root.join("other.md"), "---\nstatus: open\n---\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let paths = |q: Query| -> Vec<String> {
+        service
+            .query(&q.sort_by_path(SortDir::Asc))
+            .into_iter()
+            .map(|h| h.path.as_str_lossy())
+            .collect()
+    };
+
+    let both = Query::notes().any_of(vec![
+        FieldPredicate::eq("tags", "project"),
+        FieldPredicate::eq("tags", "area"),
+    ]);
+    assert_eq!(
+        paths(both.clone()),
+        vec!["area.md", "closed.md", "project.md"]
+    );
+
+    // The group is one conjunct alongside the ordinary AND predicates.
+    let open = both.where_field("status").eq("open");
+    assert_eq!(paths(open), vec!["area.md", "project.md"]);
+
+    // A single-element group behaves like the predicate on its own.
+    let single = Query::notes().any_of(vec![FieldPredicate::eq("tags", "area")]);
+    assert_eq!(
+        paths(single),
+        paths(Query::notes().where_field("tags").eq("area"))
+    );
+
+    // An empty group has no alternative that can match.
+    assert!(paths(Query::notes().any_of(Vec::new())).is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn negated_predicates_treat_missing_fields_as_matches() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(
+        vault_root.join("done.md"),
+        "---\nstatus: done\ntags: [archive]\n---\n",
+    )?;
+    std::fs::write(vault_root.join("open.md"), "---\nstatus: open\n---\n")?;
+    std::fs::write(vault_root.join("empty.md"), "---\nstatus: \"\"\n---\n")?;
+    std::fs::write(vault_root.join("bare.md"), "Body #archive\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let paths = |q: Query| -> Vec<String> {
+        service
+            .query(&q.sort_by_path(SortDir::Asc))
+            .into_iter()
+            .map(|h| h.path.as_str_lossy())
+            .collect()
+    };
+
+    // An empty value still counts as present.
+    assert_eq!(
+        paths(Query::notes().where_field("status").not_exists()),
+        vec!["bare.md"]
+    );
+    assert_eq!(
+        paths(Query::notes().where_field("status").not_eq("done")),
+        vec!["bare.md", "empty.md", "open.md"]
+    );
+    assert_eq!(
+        paths(Query::notes().exclude_tag("#Archive")),
+        vec!["empty.md", "open.md"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_sorts_by_mtime_and_size_with_path_tiebreak() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    let base = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+    for (name, body, offset) in [("b.md", "bb", 0), ("a.md", "aaaa", 0), ("c.md", "c", 60)] {
+        let path = vault_root.join(name);
+        std::fs::write(&path, body)?;
+        std::fs::File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(base + std::time::Duration::from_secs(offset))?;
+    }
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let paths = |q: Query| -> Vec<String> {
+        service
+            .query(&q)
+            .into_iter()
+            .map(|h| h.path.as_str_lossy())
+            .collect()
+    };
+
+    assert_eq!(
+        paths(Query::notes().sort_by_mtime(SortDir::Desc)),
+        vec!["c.md", "a.md", "b.md"]
+    );
+    assert_eq!(
+        paths(Query::notes().sort_by_mtime(SortDir::Asc)),
+        vec!["a.md", "b.md", "c.md"]
+    );
+    assert_eq!(
+        paths(Query::notes().sort_by_size(SortDir::Desc)),
+        vec!["a.md", "b.md", "c.md"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_filters_by_tag_source() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    std::fs::write(
+        vault_root.join("notes/fm.md"),
+        "---\ntags: [rust]\n---\nBody\n",
+    )?;
+    std::fs::write(vault_root.join("notes/inline.md"), "Body #rust\n")?;
+    std::fs::write(
+        vault_root.join("notes/both.md"),
+        "---\ntags: [rust]\n---\nBody #rust\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let paths = |source: TagSource| {
+        let q = Query::notes()
+            .from_tag("rust")
+            .tag_source(source)
+            .sort_by_path(SortDir::Asc);
+        service
+            .query(&q)
+            .into_iter()
+            .map(|h| h.path.as_str_lossy())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        paths(TagSource::Any),
+        vec!["notes/both.md", "notes/fm.md", "notes/inline.md"]
+    );
+    assert_eq!(
+        paths(TagSource::Frontmatter),
+        vec!["notes/both.md", "notes/fm.md"]
+    );
+    assert_eq!(
+        paths(TagSource::Inline),
+        vec!["notes/both.md", "notes/inline.md"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn tasks_group_by_status_buckets_filtered_hits() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("work"))?;
+    std::fs::create_dir_all(vault_root.join("home"))?;
+
+    std::fs::write(
+        vault_root.join("work/a.md"),
+        "- [ ] write spec\n- [x] ship v1\n- [>] review\n- [ ] plan\n",
+    )?;
+    std::fs::write(vault_root.join("home/b.md"), "- [ ] groceries\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let groups = service.query_tasks_by_status(&TaskQuery::all().from_path_prefix("work/"));
+    let texts = |status: TaskStatus| -> Vec<String> {
+        groups[&status].iter().map(|h| h.text.clone()).collect()
+    };
+    assert_eq!(groups.len(), 3);
+    assert_eq!(texts(TaskStatus::Todo), vec!["write spec", "plan"]);
+    assert_eq!(texts(TaskStatus::Done), vec!["ship v1"]);
+    assert_eq!(texts(TaskStatus::InProgress), vec!["review"]);
+
+    let json = serde_json::to_value(&groups)?;
+    assert_eq!(json["in_progress"][0]["text"], "review");
+    assert!(json.get("cancelled").is_none());
+    Ok(())
+}
+
+#[cfg(feature = "language")]
+#[tokio::test]
+async fn detected_language_is_stored_and_queryable() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    std::fs::write(
+        vault_root.join("english.md"),
+        "---\ntitle: Weekly review\n---\n\
+         This week I finished the migration and wrote down everything that went wrong, \
+         so that the next release will be much easier for the whole team.\n",
+    )?;
+    std::fs::write(
+        vault_root.join("german.md"),
+        "Diese Woche habe ich die Migration abgeschlossen und alles aufgeschrieben, \
+         was schiefgelaufen ist, damit die nächste Version für das ganze Team einfacher wird.\n",
+    )?;
+
+    let cfg = VaultConfig {
+        detect_language: true,
+        ..Default::default()
+    };
+    let service = VaultService::new(Vault::with_config(&vault_root, cfg)?)?;
+    service.build_index().await?;
+
+    let idx = service.index_snapshot();
+    let path = oxidian::VaultPath::try_from(std::path::Path::new("english.md"))?;
+    assert_eq!(idx.note(&path).unwrap().language.as_deref(), Some("en"));
+
+    let hits = service.query(&Query::notes().where_language("EN"));
+    let paths: Vec<_> = hits.iter().map(|h| h.path.as_str_lossy()).collect();
+    assert_eq!(paths, vec!["english.md"]);
+
+    let service = VaultService::new(Vault::open(&vault_root)?)?;
+    service.build_index().await?;
+    assert_eq!(service.index_snapshot().note(&path).unwrap().language, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn tag_prefix_matches_nested_tags_on_slash_boundary() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    std::fs::write(vault_root.join("root.md"), "#project\n")?;
+    std::fs::write(vault_root.join("web.md"), "#project/web\n")?;
+    std::fs::write(vault_root.join("deep.md"), "#project/web/api\n")?;
+    std::fs::write(vault_root.join("other.md"), "#projectx\n")?;
+
+    let service = VaultService::new(Vault::open(&vault_root)?)?;
+    service.build_index().await?;
+
+    let idx = service.index_snapshot();
+    let files: Vec<_> = idx
+        .files_with_tag_prefix(&oxidian::Tag("project".into()))
+        .iter()
+        .map(|p| p.as_str_lossy())
+        .collect();
+    assert_eq!(files, vec!["deep.md", "root.md", "web.md"]);
+
+    let paths = |q: Query| -> Vec<String> {
+        service
+            .query(&q)
+            .iter()
+            .map(|h| h.path.as_str_lossy())
+            .collect()
+    };
+    assert_eq!(
+        paths(Query::notes().from_tag_prefix("#project/web")),
+        vec!["deep.md", "web.md"]
+    );
+    assert_eq!(paths(Query::notes().from_tag("project")), vec!["root.md"]);
+
+    Ok(())
+}
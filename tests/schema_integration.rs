@@ -1,6 +1,7 @@
 use std::fs;
+use std::path::Path;
 
-use oxidian::{SchemaStatus, Vault, VaultIndex};
+use oxidian::{ResolveResult, Schema, SchemaSeverity, SchemaStatus, Vault, VaultIndex, VaultPath};
 
 fn write_schema(root: &std::path::Path, schema: &str) {
     let dir = root.join(".obsidian/oxidian");
@@ -113,6 +114,51 @@ deny = [{{ glob = "secret.md", severity = "error" }}]
     );
 }
 
+#[test]
+fn deny_overrides_matching_allow_with_one_violation() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().join("vault");
+    fs::create_dir_all(&root).expect("create vault");
+
+    let schema = format!(
+        r#"{}
+
+[vault.scopes.projects]
+unmatched = "error"
+allow = ["secret.md"]
+deny = [
+  {{ glob = "*.md", severity = "warn" }},
+  {{ glob = "secret.md", severity = "error" }},
+]
+"#,
+        base_schema()
+    );
+
+    write_schema(&root, &schema);
+    write_note(&root, "projects/secret.md", "body");
+    write_note(&root, "projects/plan.md", "body");
+
+    let vault = Vault::open(&root).expect("open vault");
+    let index = VaultIndex::build(&vault).expect("build index");
+    let violations = |p: &str| -> Vec<_> {
+        let path = VaultPath::try_from(std::path::Path::new(p)).unwrap();
+        index
+            .schema_violations_for(&path)
+            .into_iter()
+            .map(|v| (v.code, v.severity))
+            .collect()
+    };
+
+    assert_eq!(
+        violations("projects/secret.md"),
+        vec![("layout_denied".to_string(), SchemaSeverity::Error)]
+    );
+    assert_eq!(
+        violations("projects/plan.md"),
+        vec![("layout_denied".to_string(), SchemaSeverity::Warn)]
+    );
+}
+
 #[test]
 fn inherit_allow_applies_to_nested_scope() {
     let dir = tempfile::tempdir().expect("tempdir");
@@ -253,6 +299,66 @@ severity = "error"
     );
 }
 
+#[test]
+fn scope_timestamps_flag_missing_and_invalid_dates() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().join("vault");
+    fs::create_dir_all(&root).expect("create vault");
+
+    let schema = format!(
+        r#"{}
+
+[vault.scopes.notes]
+allow = ["**/*.md"]
+
+[vault.scopes.notes.notes.timestamps]
+severity = "error"
+"#,
+        base_schema()
+    );
+
+    write_schema(&root, &schema);
+    write_note(
+        &root,
+        "notes/ok.md",
+        "---\ncreated: 2024-01-15\nupdated: 2024-02-01T10:30:00Z\n---\nbody\n",
+    );
+    write_note(
+        &root,
+        "notes/missing.md",
+        "---\nupdated: 2024-02-01\n---\nbody\n",
+    );
+    write_note(
+        &root,
+        "notes/invalid.md",
+        "---\ncreated: 2024-01-15\nupdated: last tuesday\n---\nbody\n",
+    );
+
+    let vault = Vault::open(&root).expect("open vault");
+    let index = VaultIndex::build(&vault).expect("build index");
+    let report = index.schema_report();
+
+    let codes_for = |path: &str| -> Vec<String> {
+        report
+            .violations
+            .iter()
+            .filter(|v| v.path.as_ref().is_some_and(|p| p.as_str_lossy() == path))
+            .map(|v| v.violation.code.clone())
+            .collect()
+    };
+
+    assert!(codes_for("notes/ok.md").is_empty());
+    assert_eq!(
+        codes_for("notes/missing.md"),
+        vec!["note_timestamp_missing"]
+    );
+    assert_eq!(
+        codes_for("notes/invalid.md"),
+        vec!["note_timestamp_invalid"]
+    );
+    assert_eq!(report.errors, 2);
+}
+
 #[test]
 fn memory_scope_requires_date_structure() {
     let dir = tempfile::tempdir().expect("tempdir");
@@ -696,6 +802,51 @@ unmatched = "allow"
     );
 }
 
+#[test]
+fn type_normalization_unifies_separators_on_both_sides() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().join("vault");
+    fs::create_dir_all(&root).expect("create vault");
+
+    let schema = r#"
+version = 1
+
+[types]
+daily_note = "Daily notes"
+
+[vault.type_normalization]
+unify_separators = true
+
+[vault.scopes.journal]
+allow = ["**/*.md"]
+
+[vault.scopes.journal.notes.type]
+required = true
+allowed = ["Daily Note"]
+severity = "error"
+"#;
+    write_schema(&root, schema);
+    write_note(&root, "journal/a.md", "---\ntype: Daily-Note\n---\n");
+    write_note(&root, "journal/b.md", "---\ntype: weekly-note\n---\n");
+
+    let vault = Vault::open(&root).expect("open vault");
+    let index = VaultIndex::build(&vault).expect("build index");
+    let codes = |rel: &str| -> Vec<String> {
+        let path = VaultPath::try_from(Path::new(rel)).unwrap();
+        index
+            .schema_violations_for(&path)
+            .into_iter()
+            .map(|v| v.code)
+            .collect()
+    };
+
+    assert!(codes("journal/a.md").is_empty());
+    assert_eq!(
+        codes("journal/b.md"),
+        vec!["node_type_unknown", "note_type_mismatch"]
+    );
+}
+
 #[test]
 fn unknown_predicate_link_is_reported_as_warning() {
     let dir = tempfile::tempdir().expect("tempdir");
@@ -741,3 +892,204 @@ unmatched = "allow"
             .any(|v| v.violation.code == "predicate_domain")
     );
 }
+
+#[test]
+fn untyped_note_takes_scope_type_for_predicate_domain_when_inferred() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().join("vault");
+    fs::create_dir_all(&root).expect("create vault");
+    write_note(&root, "journal/today.md", "depends_on:: [[Target]]\n");
+
+    let has_domain_violation = |infer: bool| {
+        let schema = format!(
+            r#"{}infer_type_from_scope = {infer}
+
+[vault.scopes.journal]
+unmatched = "allow"
+
+[vault.scopes.journal.notes.type]
+allowed = ["journal"]
+"#,
+            base_schema()
+        );
+        write_schema(&root, &schema);
+        let vault = Vault::open(&root).expect("open vault");
+        let index = VaultIndex::build(&vault).expect("build index");
+        index
+            .schema_report()
+            .violations
+            .iter()
+            .any(|v| v.violation.code == "predicate_domain")
+    };
+
+    assert!(!has_domain_violation(false));
+    assert!(has_domain_violation(true));
+}
+
+#[test]
+fn predicate_report_groups_resolved_edges() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().join("vault");
+    fs::create_dir_all(&root).expect("create vault");
+
+    write_schema(&root, &base_schema());
+    write_note(
+        &root,
+        "notes/a.md",
+        "---\ntype: concept\n---\ndepends_on:: [[Target]]\nrequires:: [[Nowhere]]\n",
+    );
+    write_note(&root, "notes/Target.md", "# Target\n");
+
+    let vault = Vault::open(&root).expect("open vault");
+    let index = VaultIndex::build(&vault).expect("build index");
+    let report = index.predicate_report();
+
+    assert_eq!(report.predicates.len(), 1);
+    let group = &report.predicates[0];
+    assert_eq!(group.predicate, "depends_on");
+    assert_eq!(report.total_edges, 2);
+    assert_eq!(report.unresolved, 1);
+
+    let resolved = group
+        .edges
+        .iter()
+        .find(|e| e.reference == "Target")
+        .expect("depends_on edge");
+    assert_eq!(resolved.key, "depends_on");
+    assert_eq!(
+        resolved.resolution,
+        ResolveResult::Resolved(VaultPath::try_from(Path::new("notes/Target.md")).unwrap())
+    );
+    let missing = group
+        .edges
+        .iter()
+        .find(|e| e.reference == "Nowhere")
+        .expect("aliased edge");
+    assert_eq!(missing.key, "requires");
+    assert_eq!(missing.resolution, ResolveResult::Missing);
+}
+
+#[test]
+fn frontmatter_attachment_reference_is_not_orphaned() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().join("vault");
+    fs::create_dir_all(&root).expect("create vault");
+
+    let schema = format!(
+        r#"{}
+attachment_keys = ["image"]
+
+[vault.scopes.memory_assets]
+path = "memory/assets"
+required = true
+unmatched = "warn"
+kinds = ["attachment"]
+orphans = "warn"
+allow = ["**/*"]
+"#,
+        base_schema()
+    );
+
+    write_schema(&root, &schema);
+    write_note(&root, "memory/assets/cover.png", "png");
+    write_note(&root, "memory/assets/unused.png", "png");
+    write_note(&root, "post.md", "---\nimage: cover.png\n---\n# Post\n");
+
+    let vault = Vault::open(&root).expect("open vault");
+    let index = VaultIndex::build(&vault).expect("build index");
+    let report = index.schema_report();
+
+    let orphaned: Vec<String> = report
+        .violations
+        .iter()
+        .filter(|v| v.violation.code == "attachment_orphaned")
+        .filter_map(|v| v.path.as_ref().map(|p| p.as_str_lossy()))
+        .collect();
+    assert_eq!(orphaned, vec!["memory/assets/unused.png".to_string()]);
+}
+
+#[test]
+fn schema_lint_flags_impossible_allow_rule_and_overlaps() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().join("vault");
+    fs::create_dir_all(&root).expect("create vault");
+
+    let schema = format!(
+        r#"{}
+
+[vault.scopes.assets]
+kinds = ["attachment"]
+allow = ["**/*.md", "**/*.png", "**/*.png"]
+
+[vault.scopes.assets_icons]
+path = "assets/icons"
+extensions = ["svg"]
+allow = ["*.svg", "*.png"]
+"#,
+        base_schema()
+    );
+
+    write_schema(&root, &schema);
+    let vault = Vault::open(&root).expect("open vault");
+    let index = VaultIndex::build(&vault).expect("build index");
+    let lints = index.schema_lint(&vault);
+
+    let codes: Vec<(&str, Option<&str>)> = lints
+        .iter()
+        .map(|l| (l.code.as_str(), l.scope.as_deref()))
+        .collect();
+    assert_eq!(
+        codes,
+        vec![
+            ("lint_scope_overlap", Some("assets_icons")),
+            ("lint_rule_unreachable", Some("assets")),
+            ("lint_rule_duplicate", Some("assets")),
+            ("lint_rule_unreachable", Some("assets_icons")),
+        ]
+    );
+    assert_eq!(
+        lints[1].message,
+        "allow rule '**/*.md' can never match: 'md' files are Note, not in kinds [Attachment]"
+    );
+    assert!(
+        lints[3]
+            .message
+            .contains("extension 'png' is not in extensions")
+    );
+}
+
+#[test]
+fn schema_lint_reports_equal_scope_paths_once_as_duplicate() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().join("vault");
+    fs::create_dir_all(&root).expect("create vault");
+    let vault = Vault::open(&root).expect("open vault");
+
+    // Loading rejects equal scope paths, so build the schema in code as a caller might.
+    let mut schema = Schema::from_toml_str(&format!(
+        r#"{}
+
+[vault.scopes.docs]
+path = "shared"
+allow = ["*.md"]
+
+[vault.scopes.notes]
+path = "other"
+allow = ["*.md"]
+"#,
+        base_schema()
+    ))
+    .expect("parse schema");
+    schema.vault.scopes.get_mut("notes").unwrap().path = Some("shared/".to_string());
+
+    let lints = schema.lint(&vault);
+    let codes: Vec<(&str, Option<&str>)> = lints
+        .iter()
+        .map(|l| (l.code.as_str(), l.scope.as_deref()))
+        .collect();
+    assert_eq!(codes, vec![("lint_scope_duplicate_path", Some("docs"))]);
+    assert_eq!(
+        lints[0].message,
+        "scope 'docs' has the same path 'shared' as scope 'notes'; paths under it resolve to 'notes'"
+    );
+}
@@ -1,3 +1,4 @@
+use std::ops::ControlFlow;
 use std::time::Duration;
 
 use oxidian::{Vault, VaultConfig, VaultService};
@@ -38,3 +39,118 @@ async fn fuzzy_search_filenames_and_content_work() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn streaming_content_search_emits_each_matching_file() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    std::fs::write(vault_root.join("notes/a.md"), "kiwi smoothie\n")?;
+    std::fs::write(vault_root.join("notes/b.md"), "nothing here\n")?;
+    std::fs::write(vault_root.join("notes/c.md"), "intro\n\nkiwi tart\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault.clone())?;
+    service.build_index().await?;
+
+    let mut seen = Vec::new();
+    service
+        .index_snapshot()
+        .search_content_fuzzy_each(&vault, "kiwi", |hit| {
+            seen.push((hit.path.as_str_lossy(), hit.line));
+            ControlFlow::Continue(())
+        })?;
+    assert_eq!(
+        seen,
+        vec![("notes/a.md".to_string(), 1), ("notes/c.md".to_string(), 3)]
+    );
+
+    let mut rx = service.search_content_fuzzy_stream("kiwi");
+    let mut streamed = Vec::new();
+    while let Some(hit) = rx.recv().await {
+        streamed.push(hit?.path.as_str_lossy());
+    }
+    assert_eq!(streamed, vec!["notes/a.md", "notes/c.md"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn content_search_scans_configured_text_attachments() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(vault_root.join("note.md"), "nothing to see\n")?;
+    std::fs::write(vault_root.join("log.txt"), "first\nquokka sighting #wild\n")?;
+    std::fs::write(vault_root.join("data.csv"), "quokka,1\n")?;
+
+    let cfg = VaultConfig {
+        text_attachment_extensions: vec!["txt".into()],
+        ..Default::default()
+    };
+    let vault = Vault::with_config(&vault_root, cfg)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let hits = service.search_content_fuzzy("quokka", 10).await?;
+    let paths: Vec<String> = hits.iter().map(|h| h.path.as_str_lossy()).collect();
+    assert_eq!(paths, vec!["log.txt"]);
+    assert_eq!(hits[0].line, 2);
+
+    // Still not a note: no tags are taken from it.
+    service.with_index(|idx| {
+        assert_eq!(idx.all_tags().count(), 0);
+        assert!(idx.note(&hits[0].path).is_none());
+    });
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn exact_and_prefix_filename_search_are_deterministic() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("projects/web"))?;
+    std::fs::create_dir_all(vault_root.join("archive"))?;
+
+    std::fs::write(vault_root.join("projects/alpha.md"), "a\n")?;
+    std::fs::write(vault_root.join("projects/web/alpha-notes.md"), "b\n")?;
+    std::fs::write(vault_root.join("projects/beta.md"), "c\n")?;
+    std::fs::write(vault_root.join("archive/alphabet.md"), "d\n")?;
+    std::fs::write(vault_root.join("projectsx.md"), "e\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let paths = |hits: Vec<oxidian::SearchHit>| -> Vec<String> {
+        hits.iter().map(|h| h.path.as_str_lossy()).collect()
+    };
+
+    assert_eq!(
+        paths(service.search_filenames_exact("alpha", 10)),
+        vec!["projects/alpha.md"]
+    );
+    assert_eq!(
+        paths(service.search_filenames_exact("projects/beta.md", 10)),
+        vec!["projects/beta.md"]
+    );
+    assert!(service.search_filenames_exact("alph", 10).is_empty());
+
+    assert_eq!(
+        paths(service.search_filenames_prefix("projects/", 10)),
+        vec![
+            "projects/alpha.md",
+            "projects/beta.md",
+            "projects/web/alpha-notes.md"
+        ]
+    );
+    assert_eq!(
+        paths(service.search_filenames_prefix("projects/", 1)),
+        vec!["projects/alpha.md"]
+    );
+
+    Ok(())
+}
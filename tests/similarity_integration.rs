@@ -1,6 +1,11 @@
 #![cfg(feature = "similarity")]
 
-use oxidian::{Vault, VaultConfig, VaultPath, VaultService};
+use std::path::Path;
+
+use oxidian::{
+    NoteSimilarityHit, NoteSimilarityReport, TokenOverlapReranker, Vault, VaultConfig, VaultIndex,
+    VaultPath, VaultService, rerank_note_hits,
+};
 
 #[tokio::test]
 async fn similarity_reports_identical_notes_as_top_hit() -> anyhow::Result<()> {
@@ -86,3 +91,228 @@ async fn semantic_search_ranks_related_notes_first() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn token_overlap_rerank_reorders_cosine_hits() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(vault_root.join("src.md"), "apple banana cherry\n")?;
+    std::fs::write(vault_root.join("far.md"), "zebra yurt quantum\n")?;
+    std::fs::write(vault_root.join("near.md"), "apple banana cherry date\n")?;
+    let vault = Vault::open(&vault_root)?;
+
+    let path = |p: &str| VaultPath::try_from(std::path::Path::new(p));
+    // Raw cosine (crafted) prefers `far.md`.
+    let hits = vec![
+        NoteSimilarityHit {
+            source: path("src.md")?,
+            target: path("far.md")?,
+            score: 0.9,
+        },
+        NoteSimilarityHit {
+            source: path("src.md")?,
+            target: path("near.md")?,
+            score: 0.5,
+        },
+    ];
+
+    let reranked = rerank_note_hits(&vault, hits, &TokenOverlapReranker)?;
+    let order: Vec<String> = reranked.iter().map(|h| h.target.as_str_lossy()).collect();
+    assert_eq!(order, vec!["near.md", "far.md"]);
+    assert!((reranked[0].score - 0.75).abs() < 1e-6);
+    assert_eq!(reranked[1].score, 0.0);
+
+    Ok(())
+}
+
+#[test]
+fn check_similarity_json_lists_hits() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    std::fs::write(
+        vault_root.join("notes/a.md"),
+        "# Test\nApple banana orange.\n",
+    )?;
+    std::fs::write(
+        vault_root.join("notes/b.md"),
+        "# Test\nApple banana orange.\n",
+    )?;
+    std::fs::write(
+        vault_root.join("notes/c.md"),
+        "# Different\nZebra yurt quantum.\n",
+    )?;
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_oxi"))
+        .args(["--vault"])
+        .arg(&vault_root)
+        .args([
+            "-o",
+            "json",
+            "-q",
+            "check",
+            "similarity",
+            "--min-score",
+            "0.95",
+            "--top-k",
+            "3",
+        ])
+        .output()?;
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let data = &json["data"];
+    assert_eq!(data["total_notes"], 3);
+    assert!(data["pairs_checked"].is_u64());
+    // a <-> b are identical; each lists the other.
+    let hits = data["hits"].as_array().expect("hits array");
+    assert_eq!(hits.len(), 2);
+    for hit in hits {
+        assert!(hit["score"].as_f64().unwrap() >= 0.95);
+        assert!(hit["source"].is_string() && hit["target"].is_string());
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn semantic_search_clamps_out_of_range_min_score_and_rejects_nan() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    std::fs::write(
+        vault_root.join("notes/a.md"),
+        "# Test\nApple banana orange.\n",
+    )?;
+    std::fs::write(
+        vault_root.join("notes/b.md"),
+        "# Different\nZebra yurt quantum.\n",
+    )?;
+
+    let mut cfg = VaultConfig::default();
+    cfg.similarity_max_notes = 100;
+    cfg.embedding_cache_dir = temp.path().join("embeddings");
+
+    let vault = Vault::with_config(&vault_root, cfg)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let query = "apple banana orange";
+    let floor = service
+        .search_content_semantic_with_min_score(query, 5, 0.0)
+        .await?;
+    let below = service
+        .search_content_semantic_with_min_score(query, 5, -3.0)
+        .await?;
+    assert!(!floor.is_empty());
+    assert_eq!(below, floor);
+
+    let above = service
+        .search_content_semantic_with_min_score(query, 5, 7.5)
+        .await?;
+    let ceiling = service
+        .search_content_semantic_with_min_score(query, 5, 1.0)
+        .await?;
+    assert_eq!(above, ceiling);
+
+    let err = service
+        .search_content_semantic_with_min_score(query, 5, f32::NAN)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("NaN"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn equal_score_similarity_hits_are_path_ordered() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    // Identical bodies give every pair the same score.
+    for name in ["c.md", "a.md", "b.md"] {
+        std::fs::write(vault_root.join(name), "# Same\nApple banana orange.\n")?;
+    }
+
+    let mut cfg = VaultConfig::default();
+    cfg.similarity_min_score = 0.5;
+    cfg.similarity_top_k = 3;
+    cfg.similarity_max_notes = 100;
+    cfg.embedding_cache_dir = temp.path().join("embeddings");
+
+    let vault = Vault::with_config(&vault_root, cfg)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    for _ in 0..2 {
+        let report = service.note_similarity_report()?;
+        let pairs: Vec<(String, String)> = report
+            .hits
+            .iter()
+            .map(|h| (h.source.as_str_lossy(), h.target.as_str_lossy()))
+            .collect();
+        let expected: Vec<(String, String)> = [
+            ("a.md", "b.md"),
+            ("a.md", "c.md"),
+            ("b.md", "a.md"),
+            ("b.md", "c.md"),
+            ("c.md", "a.md"),
+            ("c.md", "b.md"),
+        ]
+        .iter()
+        .map(|(s, t)| (s.to_string(), t.to_string()))
+        .collect();
+        assert_eq!(pairs, expected);
+
+        let hits = service.note_similarity_for(&VaultPath::try_from(Path::new("b.md"))?)?;
+        let targets: Vec<String> = hits.iter().map(|h| h.target.as_str_lossy()).collect();
+        assert_eq!(targets, vec!["a.md", "c.md"]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn min_shared_tags_drops_pairs_without_common_tags() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    std::fs::write(vault_root.join("a.md"), "Apple banana. #fruit #food\n")?;
+    std::fs::write(vault_root.join("b.md"), "Apple banana. #fruit\n")?;
+    std::fs::write(vault_root.join("c.md"), "Apple banana. #tools\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let index = VaultIndex::build(&vault)?;
+    let path = |p: &str| VaultPath::try_from(Path::new(p));
+    let hit = |source: &str, target: &str| -> anyhow::Result<NoteSimilarityHit> {
+        Ok(NoteSimilarityHit {
+            source: path(source)?,
+            target: path(target)?,
+            score: 0.99,
+        })
+    };
+
+    let mut report = NoteSimilarityReport {
+        total_notes: 3,
+        pairs_checked: 2,
+        hits: vec![hit("a.md", "b.md")?, hit("a.md", "c.md")?],
+    };
+    report.retain_shared_tags(&index, 0);
+    assert_eq!(report.hits.len(), 2);
+
+    report.retain_shared_tags(&index, 1);
+    let targets: Vec<String> = report
+        .hits
+        .iter()
+        .map(|h| h.target.as_str_lossy())
+        .collect();
+    assert_eq!(targets, vec!["b.md"]);
+
+    report.retain_shared_tags(&index, 2);
+    assert!(report.hits.is_empty());
+    Ok(())
+}
@@ -0,0 +1,47 @@
+#![cfg(all(feature = "similarity", not(feature = "sqlite")))]
+
+use oxidian::{Vault, VaultConfig, VaultService};
+
+#[tokio::test]
+async fn in_memory_similarity_report_pairs_identical_notes() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    std::fs::write(
+        vault_root.join("notes/a.md"),
+        "# Test\nApple banana orange.\n",
+    )?;
+    std::fs::write(
+        vault_root.join("notes/b.md"),
+        "# Test\nApple banana orange.\n",
+    )?;
+    std::fs::write(
+        vault_root.join("notes/c.md"),
+        "# Different\nZebra yurt quantum.\n",
+    )?;
+
+    let mut cfg = VaultConfig::default();
+    cfg.similarity_min_score = 0.95;
+    cfg.similarity_top_k = 3;
+    cfg.similarity_max_notes = 100;
+    cfg.embedding_cache_dir = temp.path().join("embeddings");
+
+    let vault = Vault::with_config(&vault_root, cfg)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let report = service.note_similarity_report()?;
+    assert_eq!(report.total_notes, 3);
+    assert_eq!(report.pairs_checked, 6);
+    let pairs: Vec<(String, String)> = report
+        .hits
+        .iter()
+        .map(|h| (h.source.as_str_lossy(), h.target.as_str_lossy()))
+        .collect();
+    assert!(pairs.contains(&("notes/a.md".into(), "notes/b.md".into())));
+    assert!(pairs.contains(&("notes/b.md".into(), "notes/a.md".into())));
+    assert!(report.hits.iter().all(|h| (0.0..=1.0).contains(&h.score)));
+
+    Ok(())
+}
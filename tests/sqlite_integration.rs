@@ -29,3 +29,41 @@ async fn sqlite_store_can_persist_full_index() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn sqlite_sync_skips_unchanged_files() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+    std::fs::write(vault_root.join("notes/a.md"), "# A\n[[b]]\n")?;
+    std::fs::write(vault_root.join("notes/b.md"), "# B\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let db_path = temp.path().join("idx.sqlite");
+    let mut store = SqliteIndexStore::open_path(&db_path)?;
+    let first = store.sync_index(service.vault(), &service.index_snapshot())?;
+    assert_eq!(first.upserted, 2);
+    assert_eq!(first.unchanged, 0);
+
+    // Reopen, as a restart would.
+    drop(store);
+    let mut store = SqliteIndexStore::open_path(&db_path)?;
+    let second = store.sync_index(service.vault(), &service.index_snapshot())?;
+    assert_eq!(second.upserted, 0);
+    assert_eq!(second.removed, 0);
+    assert_eq!(second.unchanged, 2);
+
+    // A deleted file is dropped from the store.
+    std::fs::remove_file(vault_root.join("notes/b.md"))?;
+    service.build_index().await?;
+    let third = store.sync_index(service.vault(), &service.index_snapshot())?;
+    assert_eq!(third.removed, 1);
+    assert_eq!(third.upserted, 0);
+    let (files, notes, _, _, _) = store.counts()?;
+    assert_eq!((files, notes), (1, 1));
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use oxidian::{TaskQuery, TaskStatus, Vault, VaultService};
+use oxidian::{SortDir, TaskPriority, TaskQuery, TaskSortKey, TaskStatus, Vault, VaultService};
 
 #[tokio::test]
 async fn tasks_are_indexed_and_queryable() -> anyhow::Result<()> {
@@ -34,3 +34,252 @@ async fn tasks_are_indexed_and_queryable() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn tasks_sort_by_status_groups_open_work_first() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(
+        vault_root.join("a.md"),
+        "- [x] a done\n- [ ] a todo\n- [-] a cancelled\n",
+    )?;
+    std::fs::write(vault_root.join("b.md"), "- [ ] b todo\n- [x] b done\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let q = TaskQuery::all().sort_by(TaskSortKey::Status, SortDir::Asc);
+    let texts: Vec<String> = service
+        .query_tasks(&q)
+        .into_iter()
+        .map(|h| h.text)
+        .collect();
+    // Within a status group the default path-then-line order is kept.
+    assert_eq!(
+        texts,
+        vec!["a todo", "b todo", "a done", "b done", "a cancelled"]
+    );
+
+    let q = TaskQuery::all()
+        .sort_by(TaskSortKey::Status, SortDir::Desc)
+        .limit(1);
+    let hits = service.query_tasks(&q);
+    assert_eq!(hits[0].status, TaskStatus::Cancelled);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn tasks_exclude_status_drops_closed_work() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(
+        vault_root.join("a.md"),
+        "- [ ] open\n- [x] done\n- [>] going\n- [-] dropped\n- [?] stuck\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let q = TaskQuery::all()
+        .exclude_status(TaskStatus::Done)
+        .exclude_status(TaskStatus::Cancelled);
+    let statuses: Vec<TaskStatus> = service.query_tasks(&q).iter().map(|h| h.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            TaskStatus::Todo,
+            TaskStatus::InProgress,
+            TaskStatus::Blocked
+        ]
+    );
+
+    // Composes with the inclusive filter.
+    let q = TaskQuery::all()
+        .status(TaskStatus::Done)
+        .exclude_status(TaskStatus::Done);
+    assert!(service.query_tasks(&q).is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn tasks_text_regex_selects_ticket_ids() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+
+    std::fs::write(
+        vault_root.join("a.md"),
+        "- [ ] fix OPS-123 login\n- [ ] review ops-9 draft\n- [ ] OPS-45 deploy docs\n- [ ] call OPS team\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let texts = |q: &TaskQuery| -> Vec<String> {
+        service.query_tasks(q).into_iter().map(|h| h.text).collect()
+    };
+
+    let q = TaskQuery::all().text_matches(r"\bOPS-\d+\b")?;
+    assert_eq!(texts(&q), vec!["fix OPS-123 login", "OPS-45 deploy docs"]);
+
+    // ANDed with the substring filter.
+    let q = TaskQuery::all()
+        .contains_text("deploy")
+        .text_matches(r"\bOPS-\d+\b")?;
+    assert_eq!(texts(&q), vec!["OPS-45 deploy docs"]);
+
+    assert!(TaskQuery::all().text_matches("OPS-(").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn task_status_names_round_trip_and_match_json() {
+    for status in TaskStatus::ALL {
+        let name = status.to_string();
+        assert_eq!(name.parse::<TaskStatus>().ok(), Some(status));
+        assert_eq!(serde_json::to_value(status).unwrap(), name.as_str());
+    }
+    assert_eq!(
+        "In_Progress".parse::<TaskStatus>().ok(),
+        Some(TaskStatus::InProgress)
+    );
+    assert!(matches!(
+        "started".parse::<TaskStatus>(),
+        Err(oxidian::Error::InvalidTaskStatus(s)) if s == "started"
+    ));
+}
+
+#[tokio::test]
+async fn due_before_and_after_drop_undated_tasks() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    std::fs::write(
+        vault_root.join("todo.md"),
+        "- [ ] early 📅 2024-01-10\n\
+         - [ ] mid [due:: 2024-01-15]\n\
+         - [ ] late 📅 2024-01-20\n\
+         - [ ] someday\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let date = |s: &str| s.parse::<oxidian::Date>().unwrap();
+    let lines =
+        |q: TaskQuery| -> Vec<u32> { service.query_tasks(&q).iter().map(|h| h.line).collect() };
+
+    let hits = service.query_tasks(&TaskQuery::all());
+    assert_eq!(hits[1].due, Some(date("2024-01-15")));
+    assert_eq!(hits[3].due, None);
+
+    assert_eq!(
+        lines(TaskQuery::all().due_before(date("2024-01-15"))),
+        vec![1]
+    );
+    assert_eq!(
+        lines(TaskQuery::all().due_after(date("2024-01-15"))),
+        vec![3]
+    );
+    assert_eq!(
+        lines(
+            TaskQuery::all()
+                .due_after(date("2024-01-01"))
+                .due_before(date("2024-12-31"))
+        ),
+        vec![1, 2, 3]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn priority_markers_filter_sort_and_leave_text() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    std::fs::write(
+        vault_root.join("todo.md"),
+        "- [ ] high ⏫ deploy\n\
+         - [ ] plain\n\
+         - [ ] fix bug 🔺\n\
+         - [ ] tidy 🔽\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let hits = service.query_tasks(&TaskQuery::all());
+    assert_eq!(hits[0].text, "high deploy");
+    assert_eq!(hits[0].priority, TaskPriority::High);
+    assert_eq!(hits[1].priority, TaskPriority::None);
+    assert_eq!(hits[2].text, "fix bug");
+
+    let hits = service.query_tasks(
+        &TaskQuery::all()
+            .min_priority(TaskPriority::High)
+            .sort_by_priority(),
+    );
+    let texts: Vec<_> = hits.iter().map(|h| h.text.as_str()).collect();
+    assert_eq!(texts, vec!["fix bug", "high deploy"]);
+
+    let lines: Vec<u32> = service
+        .query_tasks(&TaskQuery::all().min_priority(TaskPriority::None))
+        .iter()
+        .map(|h| h.line)
+        .collect();
+    assert_eq!(lines, vec![1, 2, 3]);
+
+    assert!(
+        service
+            .query_tasks(&TaskQuery::all().contains_text("🔺"))
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn task_tags_are_queryable_and_still_tag_the_note() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    std::fs::write(
+        vault_root.join("todo.md"),
+        "#home\n\n\
+         - [ ] rotate keys #ops\n\
+         - [ ] water plants\n\
+         - [x] page on-call #OPS #urgent\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let hits = service.query_tasks(&TaskQuery::all().with_tag("#ops"));
+    let lines: Vec<u32> = hits.iter().map(|h| h.line).collect();
+    assert_eq!(lines, vec![3, 5]);
+    assert!(hits[1].tags.iter().any(|t| t.0 == "urgent"));
+    assert!(
+        service
+            .query_tasks(&TaskQuery::all().with_tag("home"))
+            .is_empty()
+    );
+
+    let notes = service.query(&oxidian::Query::notes().from_tag("urgent"));
+    assert_eq!(notes.len(), 1);
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use std::path::PathBuf;
 use std::time::Duration;
 
-use oxidian::{Tag, Vault, VaultConfig, VaultEvent, VaultService};
+use oxidian::{
+    Error, FileKind, Tag, Vault, VaultConfig, VaultEvent, VaultIndex, VaultPath, VaultService,
+};
 
 #[tokio::test]
 async fn vault_service_indexes_and_reindexes_on_change() -> anyhow::Result<()> {
@@ -95,3 +97,323 @@ async fn vault_service_indexes_and_reindexes_on_change() -> anyhow::Result<()> {
     service.shutdown().await;
     Ok(())
 }
+
+#[test]
+fn vault_path_display_native_uses_platform_separator() -> anyhow::Result<()> {
+    let path = oxidian::VaultPath::try_from(std::path::Path::new("notes/sub/a.md"))?;
+
+    #[cfg(windows)]
+    assert_eq!(path.display_native(), "notes\\sub\\a.md");
+    #[cfg(not(windows))]
+    assert_eq!(path.display_native(), "notes/sub/a.md");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn note_count_by_kind_breaks_down_mixed_vault() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("assets"))?;
+
+    std::fs::write(vault_root.join("a.md"), "# A\n")?;
+    std::fs::write(vault_root.join("b.md"), "# B\n")?;
+    std::fs::write(vault_root.join("board.canvas"), "{}")?;
+    std::fs::write(vault_root.join("assets/pic.png"), [0u8; 4])?;
+    std::fs::write(vault_root.join("data.xyz"), "raw")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let counts = service.index_snapshot().note_count_by_kind();
+    assert_eq!(counts.get(&FileKind::Markdown), Some(&2));
+    assert_eq!(counts.get(&FileKind::Canvas), Some(&1));
+    assert_eq!(counts.get(&FileKind::Attachment), Some(&1));
+    assert_eq!(counts.get(&FileKind::Other), Some(&1));
+
+    Ok(())
+}
+
+#[test]
+fn vault_open_rejects_missing_path() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let missing = temp.path().join("vault");
+
+    let err = Vault::open(&missing).unwrap_err();
+    assert!(matches!(&err, Error::VaultNotFound(p) if p == &missing));
+    assert!(err.to_string().contains(&missing.display().to_string()));
+    Ok(())
+}
+
+#[test]
+fn to_rel_distinguishes_outside_and_invalid_paths() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    let outside = temp.path().join("outside.md");
+    std::fs::write(&outside, "# outside\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    assert!(matches!(
+        vault.to_rel(&outside),
+        Err(Error::PathOutsideVault(_))
+    ));
+    assert!(matches!(
+        vault.to_rel(&vault_root.join("../outside.md")),
+        Err(Error::PathOutsideVault(_))
+    ));
+    assert!(matches!(
+        vault.to_rel(&vault_root),
+        Err(Error::InvalidVaultPath(_))
+    ));
+    assert_eq!(
+        vault.to_rel(&vault_root.join("notes/a.md"))?.as_str_lossy(),
+        "notes/a.md"
+    );
+    Ok(())
+}
+
+#[test]
+fn vault_open_rejects_file_path() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let file = temp.path().join("vault.md");
+    std::fs::write(&file, "# not a vault\n")?;
+
+    let err = Vault::open(&file).unwrap_err();
+    assert!(matches!(err, Error::InvalidVaultPath(_)));
+    assert_eq!(
+        err.to_string(),
+        format!("invalid vault path: not a directory: {}", file.display())
+    );
+    Ok(())
+}
+
+#[test]
+fn tag_index_verifies_clean_after_upserts_and_removes() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+    std::fs::write(vault_root.join("notes/a.md"), "#alpha #shared\n")?;
+    std::fs::write(vault_root.join("notes/b.md"), "#beta #shared\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let mut index = VaultIndex::build(&vault)?;
+    assert_eq!(index.verify_tag_index(), Ok(()));
+
+    std::fs::write(vault_root.join("notes/a.md"), "#gamma\n")?;
+    index.upsert_path(
+        &vault,
+        VaultPath::try_from(std::path::Path::new("notes/a.md"))?,
+    )?;
+    index.remove_path(&VaultPath::try_from(std::path::Path::new("notes/b.md"))?);
+    assert_eq!(index.verify_tag_index(), Ok(()));
+
+    index.rebuild_tag_index();
+    assert_eq!(index.verify_tag_index(), Ok(()));
+    let mut tags: Vec<_> = index.all_tags().map(|t| t.0.clone()).collect();
+    tags.sort();
+    assert_eq!(tags, vec!["gamma".to_string()]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn set_config_rebuilds_with_new_note_extensions() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    std::fs::write(vault_root.join("a.md"), "# A\n")?;
+    std::fs::write(
+        vault_root.join("board.canvas"),
+        "{\"nodes\":[],\"edges\":[]}",
+    )?;
+
+    let cfg = VaultConfig {
+        note_extensions: vec!["md".into()],
+        ..VaultConfig::default()
+    };
+    let vault = Vault::with_config(&vault_root, cfg)?;
+    let mut service = VaultService::new(vault)?;
+    service.build_index().await?;
+
+    let canvas = VaultPath::try_from(std::path::Path::new("board.canvas"))?;
+    assert_eq!(
+        service.with_index(|idx| idx.file(&canvas).map(|f| f.kind)),
+        Some(FileKind::Other)
+    );
+
+    let mut cfg = service.vault().config().clone();
+    cfg.note_extensions.push("canvas".into());
+    service.set_config(cfg).await?;
+
+    assert_eq!(
+        service.vault().config().note_extensions,
+        vec!["md".to_string(), "canvas".to_string()]
+    );
+    assert_eq!(
+        service.with_index(|idx| idx.file(&canvas).map(|f| f.kind)),
+        Some(FileKind::Canvas)
+    );
+    assert_eq!(
+        service.with_index(|idx| idx.note_count_by_kind()[&FileKind::Canvas]),
+        1
+    );
+    Ok(())
+}
+
+#[test]
+fn max_walk_depth_skips_deeper_notes() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("a/b/c"))?;
+    std::fs::write(vault_root.join("a/shallow.md"), "# Shallow\n")?;
+    std::fs::write(vault_root.join("a/b/c/deep.md"), "# Deep\n")?;
+
+    let cfg = VaultConfig {
+        max_walk_depth: Some(2),
+        ..VaultConfig::default()
+    };
+    let vault = Vault::with_config(&vault_root, cfg)?;
+    let index = VaultIndex::build(&vault)?;
+
+    let shallow = VaultPath::try_from(std::path::Path::new("a/shallow.md"))?;
+    let deep = VaultPath::try_from(std::path::Path::new("a/b/c/deep.md"))?;
+    assert!(index.note(&shallow).is_some());
+    assert!(index.note(&deep).is_none());
+    Ok(())
+}
+
+#[test]
+fn rename_path_moves_note_and_rekeys_tags() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+    std::fs::write(
+        vault_root.join("notes/old.md"),
+        "# Old\n\n#keep [[other]]\n",
+    )?;
+
+    let vault = Vault::open(&vault_root)?;
+    let mut index = VaultIndex::build(&vault)?;
+
+    let from = VaultPath::try_from(std::path::Path::new("notes/old.md"))?;
+    let to = VaultPath::try_from(std::path::Path::new("notes/new.md"))?;
+    std::fs::rename(vault.to_abs(&from), vault.to_abs(&to))?;
+    let delta = index.rename_path(&vault, &from, to.clone())?;
+
+    assert!(index.note(&from).is_none());
+    assert!(index.file(&from).is_none());
+    assert_eq!(index.note(&to).map(|n| n.title.as_str()), Some("Old"));
+    let tagged: Vec<_> = index.files_with_tag(&Tag("keep".into())).cloned().collect();
+    assert_eq!(tagged, vec![to.clone()]);
+    assert_eq!(index.verify_tag_index(), Ok(()));
+    assert!(delta.removed_tags.contains(&Tag("keep".into())));
+    assert!(delta.added_tags.contains(&Tag("keep".into())));
+    Ok(())
+}
+
+#[test]
+fn uppercase_extension_note_is_indexed_as_markdown() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+    std::fs::create_dir_all(vault_root.join(".obsidian/oxidian"))?;
+    std::fs::write(
+        vault_root.join(".obsidian/oxidian/schema.toml"),
+        "version = 1\n\n[vault.scopes.notes]\nunmatched = \"allow\"\nkinds = [\"note\"]\nextensions = [\"md\"]\n",
+    )?;
+    std::fs::write(vault_root.join("notes/LOUD.MD"), "# Loud\nSee [[quiet]].\n")?;
+    std::fs::write(vault_root.join("notes/quiet.md"), "Back to [[LOUD]].\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let index = VaultIndex::build(&vault)?;
+
+    let loud = VaultPath::try_from(std::path::Path::new("notes/LOUD.MD"))?;
+    assert_eq!(index.file(&loud).map(|f| f.kind), Some(FileKind::Markdown));
+    let note = index.note(&loud).expect("uppercase note is parsed");
+    assert_eq!(note.title, "Loud");
+    assert_eq!(note.link_occurrences.len(), 1);
+
+    let graph = index.build_graph(&vault)?;
+    assert_eq!(graph.backlinks.backlinks(&loud).len(), 1);
+    assert!(index.schema_report().violations.is_empty());
+    Ok(())
+}
+
+#[test]
+fn full_build_records_phase_metrics() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+    std::fs::create_dir_all(vault_root.join(".obsidian/oxidian"))?;
+    std::fs::write(
+        vault_root.join(".obsidian/oxidian/schema.toml"),
+        "version = 1\n\n[vault.scopes.notes]\nunmatched = \"allow\"\nkinds = [\"note\"]\n",
+    )?;
+    std::fs::write(vault_root.join("notes/a.md"), "# A\n#x [[b]]\n- [ ] task\n")?;
+    std::fs::write(vault_root.join("notes/b.md"), "# B\nBack to [[a]].\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let index = VaultIndex::build(&vault)?;
+    let metrics = index.build_metrics().expect("full build records metrics");
+
+    assert_eq!(metrics.files, 2);
+    assert!(metrics.parse > Duration::ZERO);
+    assert!(metrics.schema > Duration::ZERO);
+    assert!(metrics.reconcile > Duration::ZERO);
+    assert!(metrics.total() > Duration::ZERO);
+    assert!(VaultIndex::default().build_metrics().is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn wait_for_idle_observes_written_note() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(vault_root.join("notes"))?;
+
+    let cfg = VaultConfig {
+        watch_debounce: Duration::from_millis(100),
+        ..Default::default()
+    };
+    let vault = Vault::with_config(&vault_root, cfg)?;
+    let mut service = VaultService::new(vault)?;
+    service.build_index().await?;
+    service.start_watching().await?;
+
+    std::fs::write(vault_root.join("notes/new.md"), "# New\nBody #fresh\n")?;
+    tokio::time::timeout(Duration::from_secs(5), service.wait_for_idle()).await?;
+
+    let rel = VaultPath::try_from(std::path::Path::new("notes/new.md"))?;
+    service.with_index(|idx| {
+        assert!(idx.note(&rel).is_some());
+        assert_eq!(idx.files_with_tag(&Tag("fresh".into())).count(), 1);
+    });
+
+    service.shutdown().await;
+    Ok(())
+}
+
+#[test]
+fn aliases_index_maps_each_alias_to_declaring_notes() -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let vault_root = temp.path().join("vault");
+    std::fs::create_dir_all(&vault_root)?;
+    std::fs::write(
+        vault_root.join("person.md"),
+        "---\naliases: [Ada, The Countess]\n---\n# Ada Lovelace\n",
+    )?;
+    std::fs::write(vault_root.join("other.md"), "---\nalias: ada\n---\n")?;
+    std::fs::write(vault_root.join("plain.md"), "# Plain\n")?;
+
+    let vault = Vault::open(&vault_root)?;
+    let index = VaultIndex::build(&vault)?;
+    let aliases = index.aliases_index();
+
+    let person = VaultPath::try_from(std::path::Path::new("person.md"))?;
+    let other = VaultPath::try_from(std::path::Path::new("other.md"))?;
+    assert_eq!(aliases.len(), 2);
+    assert_eq!(aliases["the countess"], vec![person.clone()]);
+    assert_eq!(aliases["ada"], vec![other, person]);
+    Ok(())
+}
@@ -15,13 +15,21 @@ export function App() {
         settings.clusterEnabled,
         settings.minScore,
         settings.topK,
+        settings.similarityEdges,
       );
       initialSent.current = true;
     }
     if (status === "disconnected") {
       initialSent.current = false;
     }
-  }, [status, settings.clusterEnabled, settings.minScore, settings.topK, sendSimilaritySettings]);
+  }, [
+    status,
+    settings.clusterEnabled,
+    settings.minScore,
+    settings.topK,
+    settings.similarityEdges,
+    sendSimilaritySettings,
+  ]);
 
   const handleSimilarityChange = useCallback(() => {
     // use a microtask so the settings state has flushed
@@ -30,8 +38,14 @@ export function App() {
         enabled: localStorage.getItem("oxidian.clusterEnabled") === "true",
         minScore: parseFloat(localStorage.getItem("oxidian.minScore") ?? "0.6"),
         topK: parseInt(localStorage.getItem("oxidian.topK") ?? "8", 10),
+        edges: localStorage.getItem("oxidian.similarityEdges") === "true",
       };
-      sendSimilaritySettings(stored.enabled, stored.minScore, stored.topK);
+      sendSimilaritySettings(
+        stored.enabled,
+        stored.minScore,
+        stored.topK,
+        stored.edges,
+      );
     });
   }, [sendSimilaritySettings]);
 
@@ -135,6 +135,12 @@ export function SettingsPanel({
         onChange={(v) => onSettingChange("hideLabels", v)}
       />
 
+      <Toggle
+        label="Path labels"
+        checked={settings.pathLabels}
+        onChange={(v) => onSettingChange("pathLabels", v)}
+      />
+
       <Toggle
         label="Show tags"
         checked={settings.showTags}
@@ -154,6 +160,15 @@ export function SettingsPanel({
             }}
           />
 
+          <Toggle
+            label="Similarity edges"
+            checked={settings.similarityEdges}
+            onChange={(v) => {
+              onSettingChange("similarityEdges", v);
+              onSimilarityChange();
+            }}
+          />
+
           <SliderControl
             label="Min score"
             value={settings.minScore}
@@ -2,7 +2,13 @@ import { useEffect, useRef, useCallback } from "react";
 import Graph from "graphology";
 import Sigma from "sigma";
 import forceAtlas2 from "graphology-layout-forceatlas2";
-import type { GraphPayload, GraphNode, GraphSettings, LayoutMode } from "../types";
+import type {
+  GraphEdge,
+  GraphPayload,
+  GraphNode,
+  GraphSettings,
+  LayoutMode,
+} from "../types";
 
 interface SigmaNodeAttributes {
   label: string;
@@ -20,6 +26,7 @@ interface SigmaEdgeAttributes {
   size?: number;
   color?: string;
   weight?: number;
+  edgeKind?: GraphEdge["kind"];
 }
 
 interface GraphInstance {
@@ -57,6 +64,8 @@ const HIGHLIGHT = {
   dim: "#262b38",
 };
 
+const SIMILARITY_EDGE_COLOR = "#b48cf4";
+
 function hslToHex(h: number, s: number, l: number): string {
   const c = (1 - Math.abs(2 * l - 1)) * s;
   const x = c * (1 - Math.abs(((h / 60) % 2) - 1));
@@ -289,9 +298,12 @@ export function SigmaCanvas({ payload, settings }: SigmaCanvasProps) {
     for (const node of filtered.nodes) {
       const pos = positions.get(node.id) ?? positionFor(node.id);
       positions.set(node.id, pos);
+      // Note ids are vault paths; tag nodes keep their `#tag` label.
+      const label =
+        settings.pathLabels && node.kind !== "tag" ? node.id : node.label;
       graph.addNode(node.id, {
-        label: node.label,
-        fullLabel: node.label,
+        label,
+        fullLabel: label,
         size: Math.max(2, node.size || 1),
         color: effectiveNodeColor(node),
         nodeKind: node.kind,
@@ -303,9 +315,11 @@ export function SigmaCanvas({ payload, settings }: SigmaCanvasProps) {
 
     for (const edge of filtered.edges) {
       if (!graph.hasNode(edge.source) || !graph.hasNode(edge.target)) continue;
+      const similarity = edge.kind === "similarity";
       graph.addEdgeWithKey(edge.id, edge.source, edge.target, {
-        size: 0.5,
-        color: "#2d3548",
+        size: similarity ? 0.8 : 0.5,
+        color: similarity ? SIMILARITY_EDGE_COLOR : "#2d3548",
+        edgeKind: edge.kind,
       });
     }
 
@@ -408,7 +422,7 @@ export function SigmaCanvas({ payload, settings }: SigmaCanvasProps) {
     } else if (layoutChanged) {
       applyLayout(settings.layout);
     }
-  }, [payload, settings.showTags, settings.hideLabels, settings.clusterEnabled, settings.layout, settings.forceAuto, effectiveNodeColor, applyLayout]);
+  }, [payload, settings.showTags, settings.hideLabels, settings.pathLabels, settings.clusterEnabled, settings.layout, settings.forceAuto, effectiveNodeColor, applyLayout]);
 
   /* ── cleanup ────────────────────────────────────────────────────── */
 
@@ -21,9 +21,11 @@ function loadDefaults(): GraphSettings {
   return {
     layout: load<LayoutMode>("layout", "static", (v) => v as LayoutMode),
     hideLabels: load("hideLabels", false, (v) => v === "true"),
+    pathLabels: load("pathLabels", false, (v) => v === "true"),
     showTags: load("showTags", true, (v) => v === "true"),
     forceAuto: load("forceAuto", false, (v) => v === "true"),
     clusterEnabled: load("clusterEnabled", false, (v) => v === "true"),
+    similarityEdges: load("similarityEdges", false, (v) => v === "true"),
     minScore: load("minScore", 0.6, parseFloat),
     topK: load("topK", 8, (v) => parseInt(v, 10)),
   };
@@ -14,6 +14,7 @@ export interface GraphWs {
     enabled: boolean,
     minScore: number,
     topK: number,
+    edges: boolean,
   ) => void;
 }
 
@@ -64,7 +65,7 @@ export function useGraphWs(): GraphWs {
   }, [connect]);
 
   const sendSimilaritySettings = useCallback(
-    (enabled: boolean, minScore: number, topK: number) => {
+    (enabled: boolean, minScore: number, topK: number, edges: boolean) => {
       const ws = socketRef.current;
       if (!ws || ws.readyState !== WebSocket.OPEN) return;
       const msg: SimilaritySettingsMessage = {
@@ -72,6 +73,7 @@ export function useGraphWs(): GraphWs {
         enabled,
         min_score: minScore,
         top_k: topK,
+        edges,
       };
       ws.send(JSON.stringify(msg));
     },
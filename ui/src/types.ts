@@ -10,6 +10,7 @@ export interface GraphEdge {
   id: string;
   source: string;
   target: string;
+  kind: "link" | "tag" | "similarity";
 }
 
 export interface SimilarityMeta {
@@ -17,6 +18,7 @@ export interface SimilarityMeta {
   enabled: boolean;
   min_score: number;
   top_k: number;
+  edges: boolean;
 }
 
 export interface GraphPayload {
@@ -30,6 +32,7 @@ export interface SimilaritySettingsMessage {
   enabled: boolean;
   min_score: number;
   top_k: number;
+  edges: boolean;
 }
 
 export type ConnectionStatus = "connecting" | "connected" | "disconnected";
@@ -44,9 +47,11 @@ export type LayoutMode =
 export interface GraphSettings {
   layout: LayoutMode;
   hideLabels: boolean;
+  pathLabels: boolean;
   showTags: boolean;
   forceAuto: boolean;
   clusterEnabled: boolean;
+  similarityEdges: boolean;
   minScore: number;
   topK: number;
 }